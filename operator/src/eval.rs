@@ -0,0 +1,480 @@
+//!
+//! Evaluation of the extend [Function]s on a [SolutionMapping].
+//!
+//! The term functions ([Function::Iri], [Function::BlankNode] and
+//! [Function::Literal]) evaluate to a [Value::Term], such that the datatype
+//! and language tag of the generated literals are kept together with their
+//! lexical form.
+//!
+use crate::term::{Literal, RdfTerm};
+use crate::tuples::SolutionMapping;
+use crate::value::Value;
+use crate::xsd::XsdError;
+use crate::{Function, TermType};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EvalError {
+    #[error(transparent)]
+    InvalidLiteral(#[from] XsdError),
+    #[error("{0:?} is not an absolute IRI and there is no base IRI")]
+    RelativeIri(String),
+    #[error("Unclosed variable in template {0:?}")]
+    InvalidTemplate(String),
+    #[error("Evaluating the {0} function is not supported")]
+    Unsupported(&'static str),
+}
+
+impl Function {
+    /// Evaluates the function on the `mapping`.
+    ///
+    /// Evaluates to [Value::Null] if an attribute referenced by the function
+    /// is null or missing, in which case no term is generated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generated term is invalid (e.g. a literal
+    /// which is not valid for its datatype) or if the function has to keep
+    /// state across mappings, like [Function::AutoIncrement].
+    pub fn evaluate(
+        &self,
+        mapping: &SolutionMapping,
+    ) -> Result<Value, EvalError> {
+        let value = match self {
+            Function::Nop => Value::Null,
+            Function::Reference { value } => {
+                mapping.get(value).cloned().unwrap_or(Value::Null)
+            }
+            Function::Constant { value } => value.as_str().into(),
+            Function::TypedConstant { value, term_type } => {
+                match term_type {
+                    TermType::Literal => Literal::simple(value).into(),
+                    TermType::IRI => RdfTerm::iri(value).into(),
+                }
+            }
+            Function::TemplateString { value } => {
+                fill_template(value, |variable| {
+                    Ok(mapping.get(variable).cloned().unwrap_or(Value::Null))
+                })?
+            }
+            Function::TemplateFunctionValue {
+                template,
+                variable_function_pairs,
+            } => {
+                fill_template(template, |variable| {
+                    match variable_function_pairs
+                        .iter()
+                        .find(|(name, _)| name == variable)
+                    {
+                        Some((_, function)) => function.evaluate(mapping),
+                        None => Ok(Value::Null),
+                    }
+                })?
+            }
+            Function::Concatenate {
+                left_value,
+                separator,
+                right_value,
+            } => {
+                let left = left_value.evaluate(mapping)?;
+                let right = right_value.evaluate(mapping)?;
+                if left == Value::Null || right == Value::Null {
+                    return Ok(Value::Null);
+                }
+                format!(
+                    "{}{}{}",
+                    left.to_string(),
+                    separator,
+                    right.to_string()
+                )
+                .into()
+            }
+            Function::Replace {
+                replace_map,
+                inner_function,
+            } => {
+                map_string(inner_function.evaluate(mapping)?, |value| {
+                    replace_map
+                        .iter()
+                        .find(|(_, values)| values.contains(&value))
+                        .map_or(value.clone(), |(renamed, _)| renamed.clone())
+                })
+            }
+            Function::UriEncode { inner_function } => {
+                map_string(inner_function.evaluate(mapping)?, |value| {
+                    uri_encode(&value)
+                })
+            }
+            Function::Upper { inner_function } => {
+                map_string(inner_function.evaluate(mapping)?, |value| {
+                    value.to_uppercase()
+                })
+            }
+            Function::Lower { inner_function } => {
+                map_string(inner_function.evaluate(mapping)?, |value| {
+                    value.to_lowercase()
+                })
+            }
+            Function::Iri {
+                base_iri,
+                inner_function,
+            } => {
+                match inner_function.evaluate(mapping)? {
+                    Value::Null => Value::Null,
+                    Value::Term(RdfTerm::Iri { value }) => {
+                        RdfTerm::iri(value).into()
+                    }
+                    value => {
+                        RdfTerm::iri(resolve_iri(value.to_string(), base_iri)?)
+                            .into()
+                    }
+                }
+            }
+            Function::BlankNode { inner_function } => {
+                match inner_function.evaluate(mapping)? {
+                    Value::Null => Value::Null,
+                    value => RdfTerm::blank_node(value.to_string()).into(),
+                }
+            }
+            Function::Literal {
+                inner_function,
+                dtype_function,
+                langtype_function,
+            } => {
+                evaluate_literal(
+                    inner_function.evaluate(mapping)?,
+                    evaluate_optional(dtype_function, mapping)?,
+                    evaluate_optional(langtype_function, mapping)?,
+                )?
+            }
+            Function::Conditional {
+                condition_function,
+                inner_function,
+            } => {
+                if is_true(&condition_function.evaluate(mapping)?) {
+                    inner_function.evaluate(mapping)?
+                } else {
+                    Value::Null
+                }
+            }
+            Function::SimpleConcat { .. } => {
+                return Err(EvalError::Unsupported("simple concat"))
+            }
+            Function::FnO { .. } => return Err(EvalError::Unsupported("FnO")),
+            Function::AutoIncrement { .. } => {
+                return Err(EvalError::Unsupported("auto increment"))
+            }
+            Function::Star {} => return Err(EvalError::Unsupported("star")),
+        };
+
+        Ok(value)
+    }
+}
+
+fn evaluate_optional(
+    function: &Option<crate::RcExtendFunction>,
+    mapping: &SolutionMapping,
+) -> Result<Option<String>, EvalError> {
+    let value = match function {
+        Some(function) => function.evaluate(mapping)?,
+        None => return Ok(None),
+    };
+
+    match value {
+        Value::Null => Ok(None),
+        value => Ok(Some(value.to_string())),
+    }
+}
+
+/// Creates the literal of the `value`, with a language tag or datatype if
+/// given. Otherwise, the value becomes its natural RDF literal and source
+/// values which are typed literals already keep their datatype.
+fn evaluate_literal(
+    value: Value,
+    datatype: Option<String>,
+    language: Option<String>,
+) -> Result<Value, EvalError> {
+    let literal: Value = match (&value, datatype, language) {
+        (Value::Null, _, _) => Value::Null,
+        (_, _, Some(language)) => {
+            Literal::lang_tagged(value.to_string(), language).into()
+        }
+        (_, Some(datatype), None) => {
+            Value::typed_literal(&value.to_string(), &datatype)?
+        }
+        (Value::Term(RdfTerm::Literal(_)), None, None) => value,
+        (Value::Term(_), None, None) => {
+            Literal::simple(value.to_string()).into()
+        }
+        (_, None, None) => {
+            value.to_natural_rdf_term().map_or(Value::Null, Value::Term)
+        }
+    };
+
+    Ok(literal)
+}
+
+/// Fills in the `{variable}` parts of the `template` with the values
+/// returned by `variable_value`. Braces and backslashes preceded by a
+/// backslash are kept as is.
+///
+/// Evaluates to [Value::Null] if any of the variables is null.
+fn fill_template<F>(
+    template: &str,
+    mut variable_value: F,
+) -> Result<Value, EvalError>
+where
+    F: FnMut(&str) -> Result<Value, EvalError>,
+{
+    let mut result = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            '{' => {
+                let mut variable = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => variable.push(c),
+                        None => {
+                            return Err(EvalError::InvalidTemplate(
+                                template.to_string(),
+                            ))
+                        }
+                    }
+                }
+                match variable_value(&variable)? {
+                    Value::Null => return Ok(Value::Null),
+                    value => result.push_str(&value.to_string()),
+                }
+            }
+            c => result.push(c),
+        }
+    }
+
+    Ok(result.into())
+}
+
+fn map_string<F>(value: Value, f: F) -> Value
+where
+    F: FnOnce(String) -> String,
+{
+    match value {
+        Value::Null => Value::Null,
+        value => f(value.to_string()).into(),
+    }
+}
+
+/// Percent-encodes all the characters of the `value` except the unreserved
+/// characters of RFC 3986.
+fn uri_encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => {
+                encoded.push(byte as char)
+            }
+            b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            byte => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Resolves the `iri` against the `base_iri` by concatenation if it is
+/// not absolute.
+fn resolve_iri(
+    iri: String,
+    base_iri: &Option<String>,
+) -> Result<String, EvalError> {
+    if has_scheme(&iri) {
+        return Ok(iri);
+    }
+
+    match base_iri {
+        Some(base_iri) => Ok(format!("{}{}", base_iri, iri)),
+        None => Err(EvalError::RelativeIri(iri)),
+    }
+}
+
+fn has_scheme(iri: &str) -> bool {
+    match iri.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| {
+                    c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')
+                })
+        }
+        None => false,
+    }
+}
+
+fn is_true(value: &Value) -> bool {
+    match value {
+        Value::Boolean(bool) => *bool,
+        Value::Null => false,
+        value => matches!(value.to_string().as_str(), "true" | "1"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+    use crate::xsd::XsdDatatype;
+
+    fn mapping() -> SolutionMapping {
+        HashMap::from([
+            ("name".to_string(), Value::from("Venus Express")),
+            ("mass".to_string(), Value::from(1270_i64)),
+            (
+                "launched".to_string(),
+                Value::from_sql("2005-11-09", "DATE").unwrap(),
+            ),
+        ])
+    }
+
+    fn reference(value: &str) -> crate::RcExtendFunction {
+        Function::Reference {
+            value: value.to_string(),
+        }
+        .into()
+    }
+
+    fn literal(
+        inner: &str,
+        datatype: Option<&str>,
+        language: Option<&str>,
+    ) -> Function {
+        let constant = |value: &str| {
+            Function::Constant {
+                value: value.to_string(),
+            }
+            .into()
+        };
+        Function::Literal {
+            inner_function:    reference(inner),
+            dtype_function:    datatype.map(constant),
+            langtype_function: language.map(constant),
+        }
+    }
+
+    #[test]
+    fn literal_test() {
+        let mapping = mapping();
+
+        let natural = literal("mass", None, None).evaluate(&mapping).unwrap();
+        assert_eq!(
+            natural,
+            Literal::typed("1270", XsdDatatype::Integer.iri()).into()
+        );
+
+        let source_typed =
+            literal("launched", None, None).evaluate(&mapping).unwrap();
+        assert_eq!(
+            source_typed,
+            Literal::typed("2005-11-09", XsdDatatype::Date.iri()).into()
+        );
+
+        let decimal = literal("mass", Some(&XsdDatatype::Decimal.iri()), None)
+            .evaluate(&mapping)
+            .unwrap();
+        assert_eq!(
+            decimal,
+            Literal::typed("1270.0", XsdDatatype::Decimal.iri()).into()
+        );
+
+        let lang_tagged =
+            literal("name", None, Some("EN")).evaluate(&mapping).unwrap();
+        assert_eq!(
+            lang_tagged,
+            Literal::lang_tagged("Venus Express", "en").into()
+        );
+
+        let missing = literal("crew", None, None).evaluate(&mapping).unwrap();
+        assert_eq!(missing, Value::Null);
+    }
+
+    #[test]
+    fn invalid_literal_test() {
+        let function = literal("name", Some(&XsdDatatype::Date.iri()), None);
+
+        assert!(matches!(
+            function.evaluate(&mapping()),
+            Err(EvalError::InvalidLiteral(_))
+        ));
+    }
+
+    #[test]
+    fn iri_test() {
+        let template = Function::TemplateFunctionValue {
+            template:                "spacecraft/{name}".to_string(),
+            variable_function_pairs: vec![(
+                "name".to_string(),
+                Function::UriEncode {
+                    inner_function: reference("name"),
+                }
+                .into(),
+            )],
+        };
+        let iri = Function::Iri {
+            base_iri:       Some("http://example.com/".to_string()),
+            inner_function: template.clone().into(),
+        };
+
+        assert_eq!(
+            iri.evaluate(&mapping()).unwrap(),
+            RdfTerm::iri("http://example.com/spacecraft/Venus%20Express")
+                .into()
+        );
+
+        let relative = Function::Iri {
+            base_iri:       None,
+            inner_function: template.into(),
+        };
+        assert!(matches!(
+            relative.evaluate(&mapping()),
+            Err(EvalError::RelativeIri(_))
+        ));
+    }
+
+    #[test]
+    fn template_test() {
+        let template = Function::TemplateString {
+            value: "\\{{name}\\}: {mass}".to_string(),
+        };
+        assert_eq!(
+            template.evaluate(&mapping()).unwrap(),
+            Value::from("{Venus Express}: 1270")
+        );
+
+        let missing = Function::TemplateString {
+            value: "{name} {crew}".to_string(),
+        };
+        assert_eq!(missing.evaluate(&mapping()).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn conditional_replace_test() {
+        let replace = Function::Replace {
+            replace_map:    HashMap::from([(
+                "VEX".to_string(),
+                HashSet::from(["Venus Express".to_string()]),
+            )]),
+            inner_function: reference("name"),
+        };
+        let conditional = Function::Conditional {
+            condition_function: Function::Constant {
+                value: "true".to_string(),
+            }
+            .into(),
+            inner_function:     replace.into(),
+        };
+
+        assert_eq!(
+            conditional.evaluate(&mapping()).unwrap(),
+            Value::from("VEX")
+        );
+    }
+}
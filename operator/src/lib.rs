@@ -1,8 +1,11 @@
 pub mod display;
+pub mod eval;
 pub mod formats;
+pub mod term;
 mod test_util;
 pub mod tuples;
 pub mod value;
+pub mod xsd;
 
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
@@ -27,6 +30,11 @@ pub enum Operator {
     SerializerOp { config: Serializer },
     TargetOp { config: Target },
     FragmentOp { config: Fragmenter },
+    UnnestOp { config: Unnest },
+    AggregateOp { config: Aggregate },
+    SortOp { config: Sort },
+    LimitOp { config: Limit },
+    LookupOp { config: Lookup },
 }
 
 impl From<Extend> for Operator {
@@ -35,6 +43,36 @@ impl From<Extend> for Operator {
     }
 }
 
+impl From<Aggregate> for Operator {
+    fn from(config: Aggregate) -> Self {
+        Self::AggregateOp { config }
+    }
+}
+
+impl From<Sort> for Operator {
+    fn from(config: Sort) -> Self {
+        Self::SortOp { config }
+    }
+}
+
+impl From<Limit> for Operator {
+    fn from(config: Limit) -> Self {
+        Self::LimitOp { config }
+    }
+}
+
+impl From<Lookup> for Operator {
+    fn from(config: Lookup) -> Self {
+        Self::LookupOp { config }
+    }
+}
+
+impl From<Unnest> for Operator {
+    fn from(config: Unnest) -> Self {
+        Self::UnnestOp { config }
+    }
+}
+
 impl JsonDisplay for Operator {
     fn json_string(&self) -> Result<String> {
         Ok(serde_json::to_string(self)?)
@@ -68,6 +106,21 @@ impl PrettyDisplay for Operator {
             Operator::FragmentOp { config } => {
                 ("Fragment Operator".to_string(), config.pretty_string()?)
             }
+            Operator::UnnestOp { config } => {
+                ("Unnest Operator".to_string(), config.pretty_string()?)
+            }
+            Operator::AggregateOp { config } => {
+                ("Aggregate Operator".to_string(), config.pretty_string()?)
+            }
+            Operator::SortOp { config } => {
+                ("Sort Operator".to_string(), config.pretty_string()?)
+            }
+            Operator::LimitOp { config } => {
+                ("Limit Operator".to_string(), config.pretty_string()?)
+            }
+            Operator::LookupOp { config } => {
+                ("Lookup Operator".to_string(), config.pretty_string()?)
+            }
             Operator::UnionOp => ("Union Operator".to_string(), "".to_string()),
         };
 
@@ -290,6 +343,287 @@ impl PrettyDisplay for Extend {
     }
 }
 
+/// Enums to denote whether mapping tuples without any nested values are
+/// dropped ([UnnestType::Inner]) or kept with null values for the unnested
+/// attributes ([UnnestType::Outer]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
+pub enum UnnestType {
+    #[default]
+    Inner,
+    Outer,
+}
+
+/// Explodes the nested values of the given `attribute` into multiple mapping
+/// tuples, one for each nested value.
+///
+/// If an `iterator` is given, it is evaluated on the value of the `attribute`
+/// with the `reference_formulation` to retrieve the nested values.
+/// Otherwise, the `attribute` itself has to be array-valued.
+///
+/// The `fields` are evaluated on each nested value and are added to the
+/// exploded mapping tuple as `<alias>.<field alias>` attributes.
+/// The index of the nested value is added as the `ordinality` attribute if
+/// it is given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unnest {
+    pub attribute:             String,
+    pub alias:                 String,
+    pub iterator:              Option<String>,
+    pub reference_formulation: ReferenceFormulation,
+    pub fields:                Vec<Field>,
+    pub ordinality:            Option<String>,
+    pub unnest_type:           UnnestType,
+}
+
+impl PrettyDisplay for Unnest {
+    fn pretty_string(&self) -> Result<String> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| format!("{}.{}", self.alias, field.alias))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        Ok(format!(
+            "type: {:?}
+attribute: {}
+iterator: {:?}
+reference formulation: {:?}
+fields: {}
+ordinality: {:?}",
+            self.unnest_type,
+            self.attribute,
+            self.iterator,
+            self.reference_formulation,
+            fields,
+            self.ordinality,
+        ))
+    }
+}
+
+/// Aggregate functions which are applied on the values of an attribute
+/// for each group of mapping tuples.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[serde(tag = "type")]
+pub enum AggregateFunction {
+    /// Collects all the values in order of appearance into a list
+    /// (e.g. for `rdf:List` generation).
+    CollectList { attribute: String },
+    /// Counts the non-null values of the attribute or the number of mapping
+    /// tuples if no attribute is given.
+    Count { attribute: Option<String> },
+    Min { attribute: String },
+    Max { attribute: String },
+    StringJoin {
+        attribute: String,
+        separator: String,
+    },
+    First { attribute: String },
+}
+
+/// Groups the mapping tuples by the `group_by` attributes and generates one
+/// mapping tuple per group with the `group_by` attributes and the results of
+/// the aggregate functions.
+///
+/// An empty `group_by` aggregates all the mapping tuples into a single group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Aggregate {
+    pub group_by:        Vec<String>,
+    pub aggregate_pairs: HashMap<String, AggregateFunction>,
+}
+
+impl Aggregate {
+    pub fn new<A: Into<String>>(group_by: Vec<A>) -> Aggregate {
+        Aggregate {
+            group_by:        group_by.into_iter().map(Into::into).collect(),
+            aggregate_pairs: HashMap::new(),
+        }
+    }
+
+    /// Adds an aggregate function which result will be available under the
+    /// given `alias` attribute.
+    pub fn with_function<A: Into<String>>(
+        mut self,
+        alias: A,
+        function: AggregateFunction,
+    ) -> Aggregate {
+        self.aggregate_pairs.insert(alias.into(), function);
+        self
+    }
+}
+
+impl Hash for Aggregate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.group_by.hash(state);
+        hash_hashmap(&self.aggregate_pairs, state);
+    }
+}
+
+impl PrettyDisplay for Aggregate {
+    fn pretty_string(&self) -> Result<String> {
+        let mut pairs: Vec<_> = self
+            .aggregate_pairs
+            .iter()
+            .map(|(alias, function)| format!("{} -> {:?}", alias, function))
+            .collect();
+        pairs.sort();
+
+        Ok(format!(
+            "Group by: {}\nAggregate pairs: \n {}",
+            self.group_by.join(", "),
+            pairs.join("\n")
+        ))
+    }
+}
+
+/// Enums to denote the sort direction of a [SortKey].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Enums to denote whether null values are placed before or after the
+/// non-null values regardless of the [SortDirection].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
+pub enum NullOrder {
+    First,
+    #[default]
+    Last,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
+pub struct SortKey {
+    pub attribute: String,
+    pub direction: SortDirection,
+    pub nulls:     NullOrder,
+}
+
+impl SortKey {
+    pub fn ascending<A: Into<String>>(attribute: A) -> SortKey {
+        SortKey {
+            attribute: attribute.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn descending<A: Into<String>>(attribute: A) -> SortKey {
+        SortKey {
+            attribute: attribute.into(),
+            direction: SortDirection::Descending,
+            ..Default::default()
+        }
+    }
+}
+
+/// Sorts the mapping tuples on the `keys` in lexicographical order, i.e. the
+/// second key is only used to break ties of the first key and so on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
+pub struct Sort {
+    pub keys: Vec<SortKey>,
+}
+
+impl PrettyDisplay for Sort {
+    fn pretty_string(&self) -> Result<String> {
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| {
+                format!(
+                    "{} {:?} (nulls {:?})",
+                    key.attribute, key.direction, key.nulls
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Ok(format!("Sort keys: \n{}", keys))
+    }
+}
+
+/// Skips the first `offset` mapping tuples and passes at most `limit`
+/// mapping tuples afterwards.
+///
+/// Without a preceding [Sort] operator, the selected mapping tuples are
+/// not guaranteed to be the same across executions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct Limit {
+    pub limit:  usize,
+    pub offset: usize,
+}
+
+impl Limit {
+    pub fn new(limit: usize) -> Limit {
+        Limit { limit, offset: 0 }
+    }
+}
+
+impl PrettyDisplay for Limit {
+    fn pretty_string(&self) -> Result<String> {
+        Ok(format!("limit: {}\noffset: {}", self.limit, self.offset))
+    }
+}
+
+/// Enums to denote how mapping tuples are handled if their key is not
+/// present in the lookup table and there is no default value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
+pub enum LookupMissPolicy {
+    /// Keeps the mapping tuple with a null value for the lookup attribute.
+    #[default]
+    Keep,
+    /// Drops the mapping tuple.
+    Drop,
+    /// Stops the execution with an error.
+    Error,
+    /// Keeps the mapping tuple with the value of the lookup attribute
+    /// itself, e.g. for values which are only renamed if they are listed.
+    Passthrough,
+}
+
+/// Config key of a lookup table with its CSV content inlined in the plan.
+pub const LOOKUP_INLINE_TABLE: &str = "inline";
+
+/// Enriches the mapping tuples by looking up the value of the `attribute`
+/// in an external key→value table and adding the found value as the
+/// `alias` attribute.
+///
+/// The `table` is read once with the `key_field` and `value_field` as the
+/// key and value of the table, which allows engines to broadcast the table
+/// instead of inlining all the pairs in the plan (cf. [Function::Replace]).
+/// Misses use the `default_value` if given, otherwise the `miss_policy`
+/// decides the fate of the mapping tuple.
+///
+/// Small tables which are declared in the mapping document itself, e.g.
+/// the matchers of ShExML, are given as CSV in the [LOOKUP_INLINE_TABLE]
+/// config of the `table`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct Lookup {
+    pub attribute:     String,
+    pub alias:         String,
+    pub table:         Source,
+    pub key_field:     String,
+    pub value_field:   String,
+    pub default_value: Option<String>,
+    pub miss_policy:   LookupMissPolicy,
+}
+
+impl PrettyDisplay for Lookup {
+    fn pretty_string(&self) -> Result<String> {
+        Ok(format!(
+            "attribute: {}\nalias: {}\nkey field: {}\nvalue field: {}\n\
+             default value: {:?}\nmiss policy: {:?}\ntable: {}",
+            self.attribute,
+            self.alias,
+            self.key_field,
+            self.value_field,
+            self.default_value,
+            self.miss_policy,
+            self.table.pretty_string()?
+        ))
+    }
+}
+
 pub type RcExtendFunction = Rc<Function>;
 
 
@@ -362,6 +696,24 @@ pub enum Function {
         fno_identifier:   String,
         parameters: HashMap<String, RcExtendFunction>,
     },
+    /// Evaluates to the next value of a counter for every mapping tuple,
+    /// starting from `start` and increasing by `step` (1 if not given).
+    /// Once the counter exceeds `end`, it restarts from `start`.
+    /// The `prefix` and `suffix` are added to the counter value.
+    AutoIncrement {
+        start:  u32,
+        step:   Option<u32>,
+        end:    Option<u32>,
+        prefix: Option<String>,
+        suffix: Option<String>,
+    },
+    /// Evaluates to the value of the `inner_function` only if the
+    /// `condition_function` evaluates to `true`, otherwise it evaluates to
+    /// null such that no term is generated.
+    Conditional {
+        condition_function: RcExtendFunction,
+        inner_function:     RcExtendFunction,
+    },
     Star {
        // TODO: Implement star function
     }
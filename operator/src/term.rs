@@ -0,0 +1,211 @@
+//!
+//! Typed RDF term model for the values generated by the mapping operators.
+//!
+//! In contrast to the untyped [Value](crate::value::Value), an [RdfTerm]
+//! carries the kind of the term (IRI, blank node or literal) together with
+//! the datatype and language tag of literals.
+//!
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use vocab::ToString;
+
+use crate::xsd::{XsdDatatype, XsdError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RdfTerm {
+    Iri { value: String },
+    BlankNode { value: String },
+    Literal(Literal),
+}
+
+impl RdfTerm {
+    pub fn iri<S: Into<String>>(value: S) -> RdfTerm {
+        RdfTerm::Iri {
+            value: value.into(),
+        }
+    }
+
+    pub fn blank_node<S: Into<String>>(value: S) -> RdfTerm {
+        RdfTerm::BlankNode {
+            value: value.into(),
+        }
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self, RdfTerm::Literal(_))
+    }
+}
+
+impl From<Literal> for RdfTerm {
+    fn from(value: Literal) -> Self {
+        RdfTerm::Literal(value)
+    }
+}
+
+impl Display for RdfTerm {
+    /// Formats the term in N-Triples syntax.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RdfTerm::Iri { value } => write!(f, "<{}>", value),
+            RdfTerm::BlankNode { value } => write!(f, "_:{}", value),
+            RdfTerm::Literal(literal) => write!(f, "{}", literal),
+        }
+    }
+}
+
+/// RDF literal consisting of a lexical form, a datatype IRI and an
+/// optional language tag.
+///
+/// Language tagged literals always have `rdf:langString` as datatype and
+/// simple literals have `xsd:string` as datatype (RDF 1.1).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Literal {
+    pub lexical_form: String,
+    pub datatype:     String,
+    pub language:     Option<String>,
+}
+
+impl Literal {
+    /// Creates a simple literal with `xsd:string` as datatype.
+    pub fn simple<S: Into<String>>(lexical_form: S) -> Literal {
+        Literal {
+            lexical_form: lexical_form.into(),
+            datatype:     vocab::xsd::TYPE::XSD_STRING.to_string(),
+            language:     None,
+        }
+    }
+
+    /// Creates a literal with the given datatype IRI without any
+    /// canonicalization of the lexical form.
+    pub fn typed<S: Into<String>, D: Into<String>>(
+        lexical_form: S,
+        datatype: D,
+    ) -> Literal {
+        Literal {
+            lexical_form: lexical_form.into(),
+            datatype:     datatype.into(),
+            language:     None,
+        }
+    }
+
+    /// Creates a language tagged literal. Language tags are case-insensitive
+    /// so they are normalized to lower case.
+    pub fn lang_tagged<S: Into<String>, L: AsRef<str>>(
+        lexical_form: S,
+        language: L,
+    ) -> Literal {
+        Literal {
+            lexical_form: lexical_form.into(),
+            datatype:     vocab::rdf::CLASS::LANG_STRING.to_string(),
+            language:     Some(language.as_ref().to_lowercase()),
+        }
+    }
+
+    /// Creates a literal with the given XSD datatype where the lexical form
+    /// is converted to its canonical form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lexical form is not valid for the datatype.
+    pub fn canonical<S: AsRef<str>>(
+        lexical_form: S,
+        datatype: &XsdDatatype,
+    ) -> Result<Literal, XsdError> {
+        Ok(Literal::typed(
+            datatype.canonical_form(lexical_form.as_ref())?,
+            datatype.iri(),
+        ))
+    }
+
+    /// Returns a copy of the literal with its lexical form in canonical form
+    /// if the datatype supports canonicalization.
+    pub fn to_canonical(&self) -> Result<Literal, XsdError> {
+        match XsdDatatype::from_iri(&self.datatype) {
+            Some(datatype) => {
+                Ok(Literal {
+                    lexical_form: datatype
+                        .canonical_form(&self.lexical_form)?,
+                    ..self.clone()
+                })
+            }
+            None => Ok(self.clone()),
+        }
+    }
+
+    pub fn is_simple(&self) -> bool {
+        self.language.is_none()
+            && self.datatype == vocab::xsd::TYPE::XSD_STRING.to_string()
+    }
+}
+
+impl Display for Literal {
+    /// Formats the literal in N-Triples syntax.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\"", escape_lexical_form(&self.lexical_form))?;
+        if let Some(language) = &self.language {
+            write!(f, "@{}", language)
+        } else if !self.is_simple() {
+            write!(f, "^^<{}>", self.datatype)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn escape_lexical_form(lexical_form: &str) -> String {
+    let mut result = String::with_capacity(lexical_form.len());
+    for c in lexical_form.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_display() {
+        let simple = Literal::simple("say \"hi\"");
+        assert_eq!(simple.to_string(), "\"say \\\"hi\\\"\"");
+
+        let tagged = Literal::lang_tagged("hallo", "NL");
+        assert_eq!(tagged.to_string(), "\"hallo\"@nl");
+
+        let typed =
+            Literal::canonical("0042", &XsdDatatype::Integer).unwrap();
+        assert_eq!(
+            typed.to_string(),
+            "\"42\"^^<http://www.w3.org/2001/XMLSchema#integer>"
+        );
+    }
+
+    #[test]
+    fn test_term_display() {
+        assert_eq!(
+            RdfTerm::iri("http://example.com/a").to_string(),
+            "<http://example.com/a>"
+        );
+        assert_eq!(RdfTerm::blank_node("b0").to_string(), "_:b0");
+    }
+
+    #[test]
+    fn test_to_canonical() {
+        let literal = Literal::typed(
+            "1",
+            vocab::xsd::TYPE::XSD_BOOLEAN.to_string(),
+        );
+        assert_eq!(literal.to_canonical().unwrap().lexical_form, "true");
+
+        let custom = Literal::typed("1", "http://example.com/myType");
+        assert_eq!(custom.to_canonical().unwrap(), custom);
+    }
+}
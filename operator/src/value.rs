@@ -2,6 +2,9 @@ use std::collections::HashMap;
 
 use serde::Serialize;
 
+use crate::term::{Literal, RdfTerm};
+use crate::xsd::{double_to_canonical, XsdDatatype, XsdError};
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Value {
     Null,
@@ -15,6 +18,70 @@ pub enum Value {
     String(String),
 
     Object(HashMap<String, Value>),
+
+    Term(RdfTerm),
+}
+
+impl Value {
+    /// Creates the value of a literal with the given datatype IRI, in
+    /// canonical form if the datatype is one of the supported
+    /// [XsdDatatype]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lexical form is not valid for the datatype.
+    pub fn typed_literal(
+        lexical_form: &str,
+        datatype: &str,
+    ) -> Result<Value, XsdError> {
+        let literal = match XsdDatatype::from_iri(datatype) {
+            Some(datatype) => Literal::canonical(lexical_form, &datatype)?,
+            None => Literal::typed(lexical_form, datatype),
+        };
+
+        Ok(literal.into())
+    }
+
+    /// Creates the value of a field read from a source column with the
+    /// given SQL datatype, which is the natural RDF literal of the field
+    /// (e.g. an `xsd:date` literal for a `DATE` column).
+    /// Fields of character string columns stay strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field is not valid for the SQL datatype.
+    pub fn from_sql(
+        lexical_form: &str,
+        sql_type: &str,
+    ) -> Result<Value, XsdError> {
+        match XsdDatatype::from_sql_type(sql_type) {
+            Some(datatype) => {
+                Ok(Literal::canonical(lexical_form, &datatype)?.into())
+            }
+            None => Ok(lexical_form.into()),
+        }
+    }
+
+    /// Returns the RDF term of this value according to the
+    /// [natural RDF literal](https://www.w3.org/TR/r2rml/#natural-mapping)
+    /// rules: booleans become `xsd:boolean`, integers `xsd:integer` and
+    /// floating point numbers `xsd:double` literals in canonical form.
+    /// All the other values become simple literals.
+    ///
+    /// Returns [None] for [Value::Null] since it doesn't generate any term.
+    pub fn to_natural_rdf_term(&self) -> Option<RdfTerm> {
+        let literal = match self {
+            Value::Null => return None,
+            Value::Term(term) => return Some(term.clone()),
+            Value::Boolean(bool) => {
+                Literal::typed(bool.to_string(), XsdDatatype::Boolean.iri())
+            }
+            Value::Number(number) => number.to_natural_literal(),
+            value => Literal::simple(String::from(value)),
+        };
+
+        Some(literal.into())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -30,13 +97,43 @@ pub enum Number {
     Float(f32),
 }
 
+impl Number {
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Number::Byte(_)
+                | Number::Short(_)
+                | Number::UShort(_)
+                | Number::Int(_)
+                | Number::UInt(_)
+        )
+    }
+
+    /// Returns the natural RDF literal of the number which is an
+    /// `xsd:integer` for integers and an `xsd:double` otherwise.
+    pub fn to_natural_literal(&self) -> Literal {
+        if self.is_integer() {
+            return Literal::typed(self.to_string(), XsdDatatype::Integer.iri());
+        }
+
+        let lexical_form = match self {
+            Number::PosInfinity => "INF".to_string(),
+            Number::NegInfinity => "-INF".to_string(),
+            Number::Double(db) => double_to_canonical(*db),
+            Number::Float(float) => double_to_canonical(*float as f64),
+            _ => unreachable!("Integers are handled above"),
+        };
+        Literal::typed(lexical_form, XsdDatatype::Double.iri())
+    }
+}
+
 impl ToString for Number {
     fn to_string(&self) -> String {
         match self {
             Number::PosInfinity => "+∞".to_string(),
             Number::NegInfinity => "-∞".to_string(),
             Number::Double(db) => db.to_string(),
-            Number::Byte(byte) => format!("{:b}", byte),
+            Number::Byte(byte) => byte.to_string(),
             Number::Short(sh) => sh.to_string(),
             Number::UShort(ush) => ush.to_string(),
             Number::Int(int) => int.to_string(),
@@ -75,6 +172,11 @@ impl From<&Value> for String {
             Value::Array(values) => serde_json::to_string(values).unwrap(),
             Value::String(str) => str.to_owned(),
             Value::Object(obj) => serde_json::to_string(obj).unwrap(),
+            Value::Term(RdfTerm::Literal(literal)) => {
+                literal.lexical_form.to_owned()
+            }
+            Value::Term(RdfTerm::Iri { value })
+            | Value::Term(RdfTerm::BlankNode { value }) => value.to_owned(),
         }
     }
 }
@@ -85,27 +187,26 @@ impl ToString for Value {
     }
 }
 
-macro_rules! from_str_val_parse {
-    ($t:ident, $value:expr) => {
-        if let Ok(parsed) = $value.trim().parse::<$t>() {
-            return parsed.into();
-        }
-    };
-}
-
 impl From<String> for Value {
+    /// Strings are kept as strings. Use [Value::Term] with a typed
+    /// [Literal] to denote values of another datatype.
     fn from(value: String) -> Self {
-        from_str_val_parse!(u8, value);
-        from_str_val_parse!(u32, value);
-        from_str_val_parse!(u64, value);
-        from_str_val_parse!(i32, value);
-        from_str_val_parse!(i64, value);
-        from_str_val_parse!(f32, value);
-        from_str_val_parse!(f64, value);
         Self::String(value)
     }
 }
 
+impl From<RdfTerm> for Value {
+    fn from(value: RdfTerm) -> Self {
+        Self::Term(value)
+    }
+}
+
+impl From<Literal> for Value {
+    fn from(value: Literal) -> Self {
+        Self::Term(value.into())
+    }
+}
+
 impl<T> From<HashMap<&str, T>> for Value
 where
     Value: From<T>,
@@ -185,7 +286,7 @@ mod tests {
         let intoed_map: Value = map.into();
         let map_val = Value::Object(HashMap::from([
             ("name".to_string(), "foobar".into()),
-            ("age".to_string(), 23_u8.into()),
+            ("age".to_string(), "23".into()),
         ]));
 
         assert!(map_val == intoed_map, "{:?} == {:?}", map_val, intoed_map);
@@ -198,4 +299,73 @@ mod tests {
 
         assert!(num_str != f64_val, "{:?} != {:?}", num_str, f64_val);
     }
+
+    #[test]
+    fn test_byte_to_string() {
+        let byte_val: Value = 23_u8.into();
+
+        assert_eq!(byte_val.to_string(), "23");
+    }
+
+    #[test]
+    fn test_natural_rdf_term() {
+        let int_term = Value::from(23_u8).to_natural_rdf_term().unwrap();
+        assert_eq!(
+            int_term,
+            Literal::typed("23", XsdDatatype::Integer.iri()).into()
+        );
+
+        let double_term = Value::from(150_f64).to_natural_rdf_term().unwrap();
+        assert_eq!(
+            double_term,
+            Literal::typed("1.5E2", XsdDatatype::Double.iri()).into()
+        );
+
+        let bool_term = Value::from(true).to_natural_rdf_term().unwrap();
+        assert_eq!(
+            bool_term,
+            Literal::typed("true", XsdDatatype::Boolean.iri()).into()
+        );
+
+        let str_term = Value::from("23").to_natural_rdf_term().unwrap();
+        assert_eq!(str_term, Literal::simple("23").into());
+
+        assert!(Value::Null.to_natural_rdf_term().is_none());
+    }
+
+    #[test]
+    fn test_typed_literal() {
+        let decimal =
+            Value::typed_literal("01.50", &XsdDatatype::Decimal.iri()).unwrap();
+        assert_eq!(
+            decimal,
+            Literal::typed("1.5", XsdDatatype::Decimal.iri()).into()
+        );
+
+        let custom = Value::typed_literal("x", "http://example.com/t").unwrap();
+        assert_eq!(custom, Literal::typed("x", "http://example.com/t").into());
+
+        assert!(Value::typed_literal("2024-02-31", &XsdDatatype::Date.iri())
+            .is_err());
+    }
+
+    #[test]
+    fn test_from_sql() {
+        let date = Value::from_sql("2024-02-29", "DATE").unwrap();
+        assert_eq!(
+            date.to_natural_rdf_term().unwrap(),
+            Literal::typed("2024-02-29", XsdDatatype::Date.iri()).into()
+        );
+
+        let amount = Value::from_sql("+010", "NUMERIC(5)").unwrap();
+        assert_eq!(
+            amount,
+            Literal::typed("10.0", XsdDatatype::Decimal.iri()).into()
+        );
+
+        let name = Value::from_sql("Venus", "VARCHAR(20)").unwrap();
+        assert_eq!(name, Value::from("Venus"));
+
+        assert!(Value::from_sql("24:30", "TIMESTAMP").is_err());
+    }
 }
@@ -0,0 +1,591 @@
+//!
+//! Canonical lexical forms of the XSD datatypes which are relevant for the
+//! [natural RDF literal](https://www.w3.org/TR/r2rml/#natural-mapping)
+//! generation of the mapping languages.
+//!
+//! Datatypes which are not listed in [XsdDatatype] are kept as-is since we
+//! cannot assume anything about their lexical space.
+//!
+use std::fmt::Display;
+
+use vocab::ToString;
+
+/// Supported XSD datatypes for canonical form conversion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum XsdDatatype {
+    String,
+    Integer,
+    Decimal,
+    Double,
+    Float,
+    Boolean,
+    Date,
+    DateTime,
+}
+
+impl XsdDatatype {
+    /// Returns the [XsdDatatype] for the given datatype IRI if its canonical
+    /// form conversion is supported.
+    pub fn from_iri(iri: &str) -> Option<XsdDatatype> {
+        match iri {
+            iri if iri == vocab::xsd::TYPE::XSD_STRING.to_string() => {
+                Some(XsdDatatype::String)
+            }
+            iri if iri == vocab::xsd::TYPE::XSD_INTEGER.to_string() => {
+                Some(XsdDatatype::Integer)
+            }
+            iri if iri == vocab::xsd::TYPE::XSD_DECIMAL.to_string() => {
+                Some(XsdDatatype::Decimal)
+            }
+            iri if iri == vocab::xsd::TYPE::XSD_DOUBLE.to_string() => {
+                Some(XsdDatatype::Double)
+            }
+            iri if iri == vocab::xsd::TYPE::XSD_FLOAT.to_string() => {
+                Some(XsdDatatype::Float)
+            }
+            iri if iri == vocab::xsd::TYPE::XSD_BOOLEAN.to_string() => {
+                Some(XsdDatatype::Boolean)
+            }
+            iri if iri == vocab::xsd::TYPE::XSD_DATE.to_string() => {
+                Some(XsdDatatype::Date)
+            }
+            iri if iri == vocab::xsd::TYPE::XSD_DATETIME.to_string() => {
+                Some(XsdDatatype::DateTime)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the [XsdDatatype] of the natural RDF literals of the values
+    /// of a column with the given SQL datatype, following the
+    /// natural mapping of SQL values
+    /// (<https://www.w3.org/TR/r2rml/#natural-mapping>).
+    ///
+    /// Returns [None] for character strings and any other SQL datatype,
+    /// whose values become simple literals.
+    pub fn from_sql_type(sql_type: &str) -> Option<XsdDatatype> {
+        // Drop the precision and scale, e.g. of DECIMAL(10, 2)
+        let sql_type = sql_type.split('(').next().unwrap_or(sql_type);
+        match sql_type.trim().to_uppercase().as_str() {
+            "INTEGER" | "INT" | "SMALLINT" | "BIGINT" => {
+                Some(XsdDatatype::Integer)
+            }
+            "DECIMAL" | "NUMERIC" => Some(XsdDatatype::Decimal),
+            "FLOAT" | "REAL" | "DOUBLE" | "DOUBLE PRECISION" => {
+                Some(XsdDatatype::Double)
+            }
+            "BOOLEAN" => Some(XsdDatatype::Boolean),
+            "DATE" => Some(XsdDatatype::Date),
+            "TIMESTAMP" => Some(XsdDatatype::DateTime),
+            _ => None,
+        }
+    }
+
+    pub fn iri(&self) -> String {
+        match self {
+            XsdDatatype::String => vocab::xsd::TYPE::XSD_STRING.to_string(),
+            XsdDatatype::Integer => vocab::xsd::TYPE::XSD_INTEGER.to_string(),
+            XsdDatatype::Decimal => vocab::xsd::TYPE::XSD_DECIMAL.to_string(),
+            XsdDatatype::Double => vocab::xsd::TYPE::XSD_DOUBLE.to_string(),
+            XsdDatatype::Float => vocab::xsd::TYPE::XSD_FLOAT.to_string(),
+            XsdDatatype::Boolean => vocab::xsd::TYPE::XSD_BOOLEAN.to_string(),
+            XsdDatatype::Date => vocab::xsd::TYPE::XSD_DATE.to_string(),
+            XsdDatatype::DateTime => {
+                vocab::xsd::TYPE::XSD_DATETIME.to_string()
+            }
+        }
+    }
+
+    /// Converts the given lexical form to the canonical lexical form of
+    /// this datatype.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lexical form is not part of the lexical space
+    /// of this datatype.
+    pub fn canonical_form(&self, lexical: &str) -> Result<String, XsdError> {
+        match self {
+            XsdDatatype::String => Ok(lexical.to_string()),
+            XsdDatatype::Integer => canonical_integer(lexical),
+            XsdDatatype::Decimal => canonical_decimal(lexical),
+            XsdDatatype::Double => canonical_double(lexical),
+            XsdDatatype::Float => canonical_float(lexical),
+            XsdDatatype::Boolean => canonical_boolean(lexical),
+            XsdDatatype::Date => canonical_date(lexical),
+            XsdDatatype::DateTime => canonical_datetime(lexical),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{lexical:?} is not a valid lexical form for {datatype}")]
+pub struct XsdError {
+    pub lexical:  String,
+    pub datatype: String,
+}
+
+impl XsdError {
+    fn new(lexical: &str, datatype: &XsdDatatype) -> XsdError {
+        XsdError {
+            lexical:  lexical.to_string(),
+            datatype: datatype.to_string(),
+        }
+    }
+}
+
+impl Display for XsdDatatype {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.iri())
+    }
+}
+
+fn canonical_integer(lexical: &str) -> Result<String, XsdError> {
+    let trimmed = lexical.trim();
+    let (negative, digits) = match trimmed.as_bytes().first() {
+        Some(b'-') => (true, &trimmed[1..]),
+        Some(b'+') => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(XsdError::new(lexical, &XsdDatatype::Integer));
+    }
+
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        Ok("0".to_string())
+    } else if negative {
+        Ok(format!("-{}", digits))
+    } else {
+        Ok(digits.to_string())
+    }
+}
+
+/// Canonical form of xsd:decimal, which always has a digit on both sides
+/// of the decimal point (e.g. `1.0` for `01` and `-0.5` for `-.50`).
+fn canonical_decimal(lexical: &str) -> Result<String, XsdError> {
+    let err = || XsdError::new(lexical, &XsdDatatype::Decimal);
+    let trimmed = lexical.trim();
+    let (negative, unsigned) = match trimmed.as_bytes().first() {
+        Some(b'-') => (true, &trimmed[1..]),
+        Some(b'+') => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+
+    let (integer, fraction) =
+        unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty())
+        || !is_digits(integer)
+        || !is_digits(fraction)
+    {
+        return Err(err());
+    }
+
+    let integer = match integer.trim_start_matches('0') {
+        "" => "0",
+        integer => integer,
+    };
+    let fraction = match fraction.trim_end_matches('0') {
+        "" => "0",
+        fraction => fraction,
+    };
+    let is_zero = integer == "0" && fraction == "0";
+    let sign = if negative && !is_zero { "-" } else { "" };
+
+    Ok(format!("{}{}.{}", sign, integer, fraction))
+}
+
+fn canonical_double(lexical: &str) -> Result<String, XsdError> {
+    let value = parse_floating_point(lexical, &XsdDatatype::Double)?;
+    Ok(double_to_canonical(value))
+}
+
+fn canonical_float(lexical: &str) -> Result<String, XsdError> {
+    let value = parse_floating_point(lexical, &XsdDatatype::Float)?;
+    Ok(float_to_canonical(value as f32))
+}
+
+fn parse_floating_point(
+    lexical: &str,
+    datatype: &XsdDatatype,
+) -> Result<f64, XsdError> {
+    let trimmed = lexical.trim();
+    match trimmed {
+        "INF" | "+INF" => return Ok(f64::INFINITY),
+        "-INF" => return Ok(f64::NEG_INFINITY),
+        "NaN" => return Ok(f64::NAN),
+        _ => (),
+    };
+
+    // Rust's float parser also accepts "inf", "infinity" and "nan" which are
+    // not part of the lexical space of xsd:double and xsd:float.
+    if trimmed.chars().any(|c| c.is_ascii_alphabetic() && c != 'e' && c != 'E')
+    {
+        return Err(XsdError::new(lexical, datatype));
+    }
+
+    trimmed
+        .parse()
+        .map_err(|_| XsdError::new(lexical, datatype))
+}
+
+/// Formats the given [f64] in the canonical form of xsd:double
+/// (e.g. `1.5E2` for `150`).
+pub fn double_to_canonical(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    } else if value.is_infinite() {
+        return if value.is_sign_positive() {
+            "INF".to_string()
+        } else {
+            "-INF".to_string()
+        };
+    }
+
+    scientific_to_canonical(format!("{:E}", value))
+}
+
+/// Formats the given [f32] in the canonical form of xsd:float, with only
+/// the digits needed to tell the [f32] apart (e.g. `1.0E-1` for `0.1`).
+pub fn float_to_canonical(value: f32) -> String {
+    if value.is_nan() || value.is_infinite() {
+        return double_to_canonical(value as f64);
+    }
+
+    scientific_to_canonical(format!("{:E}", value))
+}
+
+fn scientific_to_canonical(scientific: String) -> String {
+    let (mantissa, exponent) = scientific.split_once('E').unwrap();
+    if mantissa.contains('.') {
+        format!("{}E{}", mantissa, exponent)
+    } else {
+        format!("{}.0E{}", mantissa, exponent)
+    }
+}
+
+fn canonical_boolean(lexical: &str) -> Result<String, XsdError> {
+    match lexical.trim() {
+        "true" | "1" => Ok("true".to_string()),
+        "false" | "0" => Ok("false".to_string()),
+        _ => Err(XsdError::new(lexical, &XsdDatatype::Boolean)),
+    }
+}
+
+/// Canonical form of xsd:date, the date with the canonical form of its
+/// timezone if it has one.
+fn canonical_date(lexical: &str) -> Result<String, XsdError> {
+    let err = || XsdError::new(lexical, &XsdDatatype::Date);
+    let trimmed = lexical.trim();
+
+    // The timezone offset is separated from the day by a '-' or '+' as well
+    let has_offset = trimmed.len() > 6
+        && trimmed.is_char_boundary(trimmed.len() - 6)
+        && trimmed.as_bytes()[trimmed.len() - 3] == b':';
+    let (date, timezone) = match trimmed.strip_suffix('Z') {
+        Some(date) => (date, Some("Z")),
+        None if has_offset => {
+            let idx = trimmed.len() - 6;
+            (&trimmed[..idx], Some(&trimmed[idx..]))
+        }
+        None => (trimmed, None),
+    };
+    if parse_date(date).is_none() {
+        return Err(err());
+    }
+
+    let mut result = date.to_string();
+    push_canonical_timezone(&mut result, timezone).ok_or_else(err)?;
+    Ok(result)
+}
+
+/// Canonical form of xsd:dateTime, in which a time of `24:00:00` is the
+/// first moment of the next day.
+fn canonical_datetime(lexical: &str) -> Result<String, XsdError> {
+    let err = || XsdError::new(lexical, &XsdDatatype::DateTime);
+    // SQL timestamps use a space instead of 'T' as separator
+    let trimmed = lexical.trim().replacen(' ', "T", 1);
+
+    let (date, time_tz) = trimmed.split_once('T').ok_or_else(err)?;
+    let (year, month, day) = parse_date(date).ok_or_else(err)?;
+
+    let (time, timezone) = split_timezone(time_tz);
+    let (hms, fraction) = match time.split_once('.') {
+        Some((hms, fraction)) => (hms, Some(fraction)),
+        None => (time, None),
+    };
+    let is_end_of_day = hms == "24:00:00";
+    if !is_end_of_day && !is_valid_time(hms) {
+        return Err(err());
+    }
+
+    let fraction = match fraction {
+        Some(fraction)
+            if fraction.is_empty()
+                || !fraction.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            return Err(err());
+        }
+        Some(fraction) => fraction.trim_end_matches('0'),
+        None => "",
+    };
+
+    let mut result = if is_end_of_day {
+        // Only the very end of the day is allowed with hour 24
+        if !fraction.is_empty() {
+            return Err(err());
+        }
+        let (year, month, day) = next_day(year, month, day);
+        format!("{}T00:00:00", format_date(year, month, day))
+    } else {
+        format!("{}T{}", date, hms)
+    };
+    if !fraction.is_empty() {
+        result = format!("{}.{}", result, fraction);
+    }
+
+    push_canonical_timezone(&mut result, timezone).ok_or_else(err)?;
+    Ok(result)
+}
+
+fn split_timezone(time_tz: &str) -> (&str, Option<&str>) {
+    if let Some(time) = time_tz.strip_suffix('Z') {
+        (time, Some("Z"))
+    } else if let Some(idx) = time_tz.rfind(['+', '-']) {
+        (&time_tz[..idx], Some(&time_tz[idx..]))
+    } else {
+        (time_tz, None)
+    }
+}
+
+/// Appends the canonical form of the `timezone`, in which UTC is `Z`, to
+/// the `result`. Returns [None] if the timezone is invalid.
+fn push_canonical_timezone(
+    result: &mut String,
+    timezone: Option<&str>,
+) -> Option<()> {
+    match timezone {
+        Some("Z") | Some("+00:00") | Some("-00:00") => result.push('Z'),
+        Some(tz) if is_valid_timezone_offset(tz) => result.push_str(tz),
+        Some(_) => return None,
+        None => (),
+    };
+    Some(())
+}
+
+fn digits_in_range(value: &str, len: usize, min: u32, max: u32) -> bool {
+    value.len() == len
+        && value.chars().all(|c| c.is_ascii_digit())
+        && value
+            .parse::<u32>()
+            .is_ok_and(|num| (min..=max).contains(&num))
+}
+
+/// Parses the year, month and day of the `date`, which has to exist in
+/// the proleptic Gregorian calendar.
+fn parse_date(date: &str) -> Option<(i64, u32, u32)> {
+    let (negative, unsigned) = match date.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, date),
+    };
+    let parts: Vec<&str> = unsigned.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return None;
+    };
+    if year.len() < 4 || !year.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let year: i64 = year.parse().ok()?;
+    let year = if negative { -year } else { year };
+    if !digits_in_range(month, 2, 1, 12) {
+        return None;
+    }
+    let month: u32 = month.parse().ok()?;
+    if !digits_in_range(day, 2, 1, days_in_month(year, month)) {
+        return None;
+    }
+
+    Some((year, month, day.parse().ok()?))
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn next_day(year: i64, month: u32, day: u32) -> (i64, u32, u32) {
+    if day < days_in_month(year, month) {
+        (year, month, day + 1)
+    } else if month < 12 {
+        (year, month + 1, 1)
+    } else {
+        (year + 1, 1, 1)
+    }
+}
+
+fn format_date(year: i64, month: u32, day: u32) -> String {
+    let sign = if year < 0 { "-" } else { "" };
+    format!("{}{:04}-{:02}-{:02}", sign, year.abs(), month, day)
+}
+
+/// Checks the hours, minutes and seconds of a `time` before the end of the
+/// day, which is `24:00:00` only.
+fn is_valid_time(time: &str) -> bool {
+    let parts: Vec<&str> = time.split(':').collect();
+    match parts.as_slice() {
+        [hour, minute, second] => {
+            digits_in_range(hour, 2, 0, 23)
+                && digits_in_range(minute, 2, 0, 59)
+                && digits_in_range(second, 2, 0, 59)
+        }
+        _ => false,
+    }
+}
+
+fn is_valid_timezone_offset(tz: &str) -> bool {
+    let offset = &tz[1..];
+    match offset.split_once(':') {
+        Some((hour, minute)) => {
+            digits_in_range(hour, 2, 0, 14) && digits_in_range(minute, 2, 0, 59)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_canonical() {
+        let datatype = XsdDatatype::Integer;
+        assert_eq!(datatype.canonical_form("0023").unwrap(), "23");
+        assert_eq!(datatype.canonical_form("+5").unwrap(), "5");
+        assert_eq!(datatype.canonical_form("-000").unwrap(), "0");
+        assert_eq!(datatype.canonical_form(" -12 ").unwrap(), "-12");
+        assert!(datatype.canonical_form("12.0").is_err());
+        assert!(datatype.canonical_form("").is_err());
+    }
+
+    #[test]
+    fn test_double_canonical() {
+        let datatype = XsdDatatype::Double;
+        assert_eq!(datatype.canonical_form("150").unwrap(), "1.5E2");
+        assert_eq!(datatype.canonical_form("1").unwrap(), "1.0E0");
+        assert_eq!(datatype.canonical_form("0.0015").unwrap(), "1.5E-3");
+        assert_eq!(datatype.canonical_form("-INF").unwrap(), "-INF");
+        assert!(datatype.canonical_form("inf").is_err());
+        assert!(datatype.canonical_form("abc").is_err());
+    }
+
+    #[test]
+    fn test_decimal_canonical() {
+        let datatype = XsdDatatype::Decimal;
+        assert_eq!(datatype.canonical_form("01").unwrap(), "1.0");
+        assert_eq!(datatype.canonical_form("-.50").unwrap(), "-0.5");
+        assert_eq!(datatype.canonical_form("+12.340").unwrap(), "12.34");
+        assert_eq!(datatype.canonical_form("-0.0").unwrap(), "0.0");
+        assert!(datatype.canonical_form(".").is_err());
+        assert!(datatype.canonical_form("1E2").is_err());
+    }
+
+    #[test]
+    fn test_float_canonical() {
+        let datatype = XsdDatatype::Float;
+        assert_eq!(datatype.canonical_form("0.1").unwrap(), "1.0E-1");
+        assert_eq!(datatype.canonical_form("150").unwrap(), "1.5E2");
+        assert_eq!(datatype.canonical_form("+INF").unwrap(), "INF");
+        assert!(datatype.canonical_form("nan").is_err());
+    }
+
+    #[test]
+    fn test_boolean_canonical() {
+        let datatype = XsdDatatype::Boolean;
+        assert_eq!(datatype.canonical_form("1").unwrap(), "true");
+        assert_eq!(datatype.canonical_form("false").unwrap(), "false");
+        assert!(datatype.canonical_form("TRUE").is_err());
+    }
+
+    #[test]
+    fn test_datetime_canonical() {
+        let datatype = XsdDatatype::DateTime;
+        assert_eq!(
+            datatype.canonical_form("2011-08-23 22:17:00").unwrap(),
+            "2011-08-23T22:17:00"
+        );
+        assert_eq!(
+            datatype.canonical_form("2011-08-23T22:17:00.500+00:00").unwrap(),
+            "2011-08-23T22:17:00.5Z"
+        );
+        assert_eq!(
+            datatype.canonical_form("2011-08-23T22:17:00-05:00").unwrap(),
+            "2011-08-23T22:17:00-05:00"
+        );
+        assert!(datatype.canonical_form("2011-13-23T22:17:00").is_err());
+        assert!(datatype.canonical_form("2011-08-23").is_err());
+    }
+
+    #[test]
+    fn test_datetime_end_of_day() {
+        let datatype = XsdDatatype::DateTime;
+        assert_eq!(
+            datatype.canonical_form("2011-08-23T24:00:00").unwrap(),
+            "2011-08-24T00:00:00"
+        );
+        assert_eq!(
+            datatype.canonical_form("2024-02-29T24:00:00.000Z").unwrap(),
+            "2024-03-01T00:00:00Z"
+        );
+        assert_eq!(
+            datatype.canonical_form("2011-12-31T24:00:00").unwrap(),
+            "2012-01-01T00:00:00"
+        );
+        assert!(datatype.canonical_form("2011-08-23T24:30:00").is_err());
+        assert!(datatype.canonical_form("2011-08-23T24:00:00.5").is_err());
+    }
+
+    #[test]
+    fn test_date_canonical() {
+        let datatype = XsdDatatype::Date;
+        assert_eq!(
+            datatype.canonical_form("2024-02-29").unwrap(),
+            "2024-02-29"
+        );
+        assert_eq!(
+            datatype.canonical_form("2011-08-23+00:00").unwrap(),
+            "2011-08-23Z"
+        );
+        assert_eq!(
+            datatype.canonical_form("2011-08-23-05:00").unwrap(),
+            "2011-08-23-05:00"
+        );
+        assert!(datatype.canonical_form("2024-02-31").is_err());
+        assert!(datatype.canonical_form("2023-02-29").is_err());
+        assert!(datatype.canonical_form("1900-02-29").is_err());
+        assert!(datatype.canonical_form("2011-04-31T00:00:00").is_err());
+    }
+
+    #[test]
+    fn test_from_sql_type() {
+        assert_eq!(
+            XsdDatatype::from_sql_type("DECIMAL(10, 2)"),
+            Some(XsdDatatype::Decimal)
+        );
+        assert_eq!(
+            XsdDatatype::from_sql_type("bigint"),
+            Some(XsdDatatype::Integer)
+        );
+        assert_eq!(XsdDatatype::from_sql_type("DATE"), Some(XsdDatatype::Date));
+        assert_eq!(XsdDatatype::from_sql_type("VARCHAR(20)"), None);
+    }
+}
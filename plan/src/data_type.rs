@@ -108,3 +108,34 @@ impl Display for PlanNode {
         )
     }
 }
+
+/// Differences between the operators of a mapping plan and a previous
+/// version of it, independent of the order and ids of the nodes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanDiff {
+    /// Operators which are only in the new plan
+    pub added:   Vec<Operator>,
+    /// Operators which are only in the previous plan
+    pub removed: Vec<Operator>,
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl PrettyDisplay for PlanDiff {
+    fn pretty_string(&self) -> Result<String> {
+        let mut diff = String::new();
+        for (sign, operators) in [('-', &self.removed), ('+', &self.added)] {
+            for operator in operators {
+                for line in operator.pretty_string()?.lines() {
+                    diff += &format!("{} {}\n", sign, line);
+                }
+            }
+        }
+
+        Ok(diff)
+    }
+}
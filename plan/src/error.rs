@@ -16,6 +16,9 @@ pub enum PlanError {
     #[error("The given operator needs to be connected to a previous operator: \n{0:?}")]
     DanglingApplyOperator(Operator),
 
+    #[error("No values given for the parameters: {}", .0.join(", "))]
+    UnresolvedParameters(Vec<String>),
+
     #[error("Something else happened: {0:?}")]
     GenericError(String),
 }
@@ -8,6 +8,7 @@
 //!
 //!
 use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
 use std::fs::File;
 use std::io::{BufWriter, Read, Write};
 use std::marker::PhantomData;
@@ -16,13 +17,16 @@ use std::rc::Rc;
 
 use anyhow::Result;
 use data_type::RcRefCellPlan;
+use operator::display::PrettyDisplay;
 use operator::{Fragmenter, Operator};
 use petgraph::dot::Dot;
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
 
 use crate::data_type::{
-    DiGraphOperators, PlanEdge, PlanNode, RcRefCellDiGraph,
-    RcRefCellVSourceIdxs, DEFAULT_FRAGMENT,
+    DiGraphOperators, EdgeDirection, PlanDiff, PlanEdge, PlanNode,
+    RcRefCellDiGraph, RcRefCellVSourceIdxs, DEFAULT_FRAGMENT,
 };
 use crate::error::PlanError;
 use crate::states::Init;
@@ -220,6 +224,120 @@ impl<T> Plan<T> {
         }
     }
 
+    /// Inserts a node with the given `operator` right before every
+    /// [Operator::SerializerOp] node in the plan and returns the number of
+    /// inserted nodes.
+    ///
+    /// Useful to post-process an already translated plan, e.g. to insert a
+    /// [Limit](operator::Limit) operator for sampling runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given operator is either a
+    /// [Operator::SourceOp], [Operator::FragmentOp], [Operator::TargetOp] or
+    /// [Operator::SerializerOp].
+    pub fn insert_before_serializers(
+        &mut self,
+        operator: &Operator,
+        node_id_prefix: &str,
+    ) -> Result<usize, PlanError> {
+        match operator {
+            Operator::SourceOp { .. }
+            | Operator::FragmentOp { .. }
+            | Operator::TargetOp { .. }
+            | Operator::SerializerOp { .. } => {
+                return Err(PlanError::WrongApplyOperator(operator.clone()))
+            }
+            _ => (),
+        };
+
+        let mut graph = self.graph.borrow_mut();
+        let serializer_idxs: Vec<NodeIndex> = graph
+            .node_indices()
+            .filter(|idx| {
+                matches!(graph[*idx].operator, Operator::SerializerOp { .. })
+            })
+            .collect();
+
+        for serializer_idx in &serializer_idxs {
+            let incoming_edges: Vec<_> = graph
+                .edges_directed(*serializer_idx, Direction::Incoming)
+                .map(|edge| (edge.id(), edge.source(), edge.weight().clone()))
+                .collect();
+
+            let plan_node = PlanNode {
+                id:       format!("{}_{}", node_id_prefix, graph.node_count()),
+                operator: operator.clone(),
+            };
+            let new_node_idx = graph.add_node(plan_node);
+
+            // Remove in reverse order since removing an edge invalidates the
+            // last edge index of the graph.
+            let mut edge_ids: Vec<_> =
+                incoming_edges.iter().map(|(id, _, _)| *id).collect();
+            edge_ids.sort();
+            for edge_id in edge_ids.into_iter().rev() {
+                graph.remove_edge(edge_id);
+            }
+
+            let mut out_edge = PlanEdge::default();
+            for (_, source_idx, edge) in incoming_edges {
+                out_edge.fragment = edge.fragment.clone();
+                graph.add_edge(source_idx, new_node_idx, edge);
+            }
+            graph.add_edge(new_node_idx, *serializer_idx, out_edge);
+        }
+
+        Ok(serializer_idxs.len())
+    }
+
+    /// Substitutes the `${name}` placeholders in the configuration of the
+    /// sources and targets of the plan with the value of the parameter
+    /// `name` in `params`, and returns the number of substituted
+    /// placeholders.
+    ///
+    /// Placeholders can be escaped as `$${name}` to keep them verbatim.
+    ///
+    /// # Errors
+    ///
+    /// Returns [PlanError::UnresolvedParameters] with the sorted names of
+    /// every placeholder without a value in `params`. The plan is left
+    /// unchanged in that case.
+    pub fn resolve_parameters(
+        &mut self,
+        params: &HashMap<String, String>,
+    ) -> Result<usize, PlanError> {
+        let mut graph = self.graph.borrow_mut();
+        let mut unresolved = BTreeSet::new();
+        let mut resolved_graph = graph.clone();
+        let mut count = 0;
+
+        for node in resolved_graph.node_weights_mut() {
+            let config = match &mut node.operator {
+                Operator::SourceOp { config } => &mut config.config,
+                Operator::TargetOp { config } => &mut config.configuration,
+                Operator::LookupOp { config } => &mut config.table.config,
+                _ => continue,
+            };
+
+            for value in config.values_mut() {
+                let (substituted, substitutions) =
+                    substitute_parameters(value, params, &mut unresolved);
+                *value = substituted;
+                count += substitutions;
+            }
+        }
+
+        if !unresolved.is_empty() {
+            return Err(PlanError::UnresolvedParameters(
+                unresolved.into_iter().collect(),
+            ));
+        }
+
+        *graph = resolved_graph;
+        Ok(count)
+    }
+
     /// Serializes the plan with the given [dot](Dot) formatter, `fmt`, to a file 
     /// at the given `path`.
     pub fn write_fmt(
@@ -248,6 +366,88 @@ impl<T> Plan<T> {
         Ok(())
     }
 
+    /// Serializes the plan to a [String] in the [dot](Dot) format, using
+    /// the [Display](std::fmt::Display) trait of the nodes if `pretty` or
+    /// else the more verbose [Debug](std::fmt::Debug) trait.
+    pub fn to_dot_string(&self, pretty: bool) -> String {
+        let graph = &*self.graph.borrow();
+        let dot = Dot::with_config(graph, &[]);
+        if pretty {
+            format!("{}", dot)
+        } else {
+            format!("{:?}", dot)
+        }
+    }
+
+    /// Serializes the plan to a [String] as a
+    /// [Mermaid flowchart](https://mermaid.js.org/syntax/flowchart.html)
+    /// with the id and operator type of every node.
+    ///
+    /// # Error
+    /// Returns an error if an operator cannot be displayed.
+    pub fn to_mermaid_string(&self) -> Result<String> {
+        let graph = &*self.graph.borrow();
+        let mut mermaid = String::from("flowchart TD\n");
+
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            let operator_string = node.operator.pretty_string()?;
+            let title = operator_string.lines().next().unwrap_or_default();
+            mermaid += &format!(
+                "    n{}[\"{}<br/>{}\"]\n",
+                idx.index(),
+                node.id.replace('"', "#quot;"),
+                title
+            );
+        }
+
+        for edge in graph.edge_references() {
+            let weight = edge.weight();
+            let label = match weight.direction {
+                EdgeDirection::Center => weight.fragment.clone(),
+                ref direction => {
+                    format!("{} ({:?})", weight.fragment, direction)
+                }
+            };
+            mermaid += &format!(
+                "    n{} -->|\"{}\"| n{}\n",
+                edge.source().index(),
+                label.replace('"', "#quot;"),
+                edge.target().index()
+            );
+        }
+
+        Ok(mermaid)
+    }
+
+    /// Compares the operators of the plan with the ones of a `previous`
+    /// version of it, e.g. to show the effect of an edited mapping document.
+    pub fn diff<O>(&self, previous: &Plan<O>) -> PlanDiff {
+        let graph = &*self.graph.borrow();
+        let previous_graph = &*previous.graph.borrow();
+        let mut removed: Vec<&Operator> = previous_graph
+            .node_weights()
+            .map(|node| &node.operator)
+            .collect();
+        let mut added = Vec::new();
+
+        for node in graph.node_weights() {
+            let unchanged =
+                removed.iter().position(|operator| **operator == node.operator);
+            match unchanged {
+                Some(pos) => {
+                    removed.remove(pos);
+                }
+                None => added.push(node.operator.clone()),
+            }
+        }
+
+        PlanDiff {
+            added,
+            removed: removed.into_iter().cloned().collect(),
+        }
+    }
+
     /// Serializes the plan in JSON format to a file at the given `path`. 
     /// Delegates the actual serialization to [Plan::to_json_string()].  
     pub fn write_json(&self, path: PathBuf) -> Result<()> {
@@ -291,6 +491,62 @@ impl<T> Plan<T> {
         let json_str = serde_json::to_string(&graph)?;
         Ok(json_str)
     }
+
+    /// Same as [Plan::to_json_string()] but indented to be human-readable.
+    pub fn to_json_pretty_string(&self) -> Result<String> {
+        let graph = &*self.graph.borrow();
+        let json_str = serde_json::to_string_pretty(&graph)?;
+        Ok(json_str)
+    }
+}
+
+/// Substitutes the `${name}` placeholders in `value` with the parameters
+/// in `params` and returns the substituted string with the number of
+/// substitutions. Names without a parameter are added to `unresolved`.
+fn substitute_parameters(
+    value: &str,
+    params: &HashMap<String, String>,
+    unresolved: &mut BTreeSet<String>,
+) -> (String, usize) {
+    let mut result = String::with_capacity(value.len());
+    let mut count = 0;
+    let mut rest = value;
+
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        if let Some(escaped) = after.strip_prefix("${") {
+            result.push_str("${");
+            rest = escaped;
+            continue;
+        }
+
+        let end = after.strip_prefix('{').and_then(|inner| inner.find('}'));
+        match end {
+            Some(end) => {
+                let name = after[1..end + 1].trim();
+                match params.get(name) {
+                    Some(param) => {
+                        result.push_str(param);
+                        count += 1;
+                    }
+                    None => {
+                        unresolved.insert(name.to_string());
+                        result.push_str(&rest[start..start + end + 3]);
+                    }
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                result.push('$');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+
+    (result, count)
 }
 
 fn write_string_to_file(
@@ -384,6 +640,309 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_plan_apply_unnest() -> std::result::Result<(), PlanError> {
+        let mut plan = generate_dummy_processed_plan()?;
+        let unnest_op: Operator = operator::Unnest {
+            attribute:             "items".to_string(),
+            alias:                 "items".to_string(),
+            iterator:              None,
+            reference_formulation: Default::default(),
+            fields:                vec![],
+            ordinality:            Some("items.#".to_string()),
+            unnest_type:           operator::UnnestType::Outer,
+        }
+        .into();
+
+        let plan = plan.apply(&unnest_op, "Unnest")?;
+        let graph = plan.graph.borrow();
+        let unnest_node = graph.node_weight(plan.current_cursor_idx.unwrap());
+
+        assert!(graph.node_count() == 4);
+        assert!(unnest_node.unwrap().operator == unnest_op);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_aggregate() -> std::result::Result<(), PlanError> {
+        let mut plan = generate_dummy_processed_plan()?;
+        let aggregate = operator::Aggregate::new(vec!["subject"])
+            .with_function(
+                "objects",
+                operator::AggregateFunction::CollectList {
+                    attribute: "object".to_string(),
+                },
+            )
+            .with_function(
+                "count",
+                operator::AggregateFunction::Count { attribute: None },
+            );
+
+        let plan = plan.aggregate(aggregate.clone())?;
+        let graph = plan.graph.borrow();
+        let aggregate_node =
+            graph.node_weight(plan.current_cursor_idx.unwrap()).unwrap();
+
+        assert!(graph.node_count() == 4);
+        assert!(aggregate_node.operator == aggregate.into());
+
+        let json = serde_json::to_value(&aggregate_node.operator).unwrap();
+        assert_eq!(json["type"], "AggregateOp");
+        assert_eq!(
+            json["config"]["aggregate_pairs"]["objects"]["type"],
+            "CollectList"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_sort_limit() -> std::result::Result<(), PlanError> {
+        let mut plan = generate_dummy_processed_plan()?;
+        let sort = operator::Sort {
+            keys: vec![
+                operator::SortKey::ascending("name"),
+                operator::SortKey::descending("age"),
+            ],
+        };
+
+        let plan = plan.sort(sort.clone())?.limit(operator::Limit {
+            limit:  100,
+            offset: 10,
+        })?;
+        let graph = plan.graph.borrow();
+        let limit_node =
+            graph.node_weight(plan.current_cursor_idx.unwrap()).unwrap();
+
+        assert!(graph.node_count() == 5);
+        assert!(
+            limit_node.operator
+                == operator::Limit {
+                    limit:  100,
+                    offset: 10,
+                }
+                .into()
+        );
+
+        let json = serde_json::to_value(&graph[NodeIndex::new(3)].operator)
+            .unwrap();
+        assert_eq!(json["type"], "SortOp");
+        assert_eq!(json["config"]["keys"][1]["direction"], "Descending");
+        assert_eq!(json["config"]["keys"][1]["nulls"], "Last");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_insert_before_serializers(
+    ) -> std::result::Result<(), PlanError> {
+        let mut plan = generate_dummy_processed_plan()?;
+        let serializer = operator::Serializer {
+            template: "?x ?y ?z .".to_string(),
+            options:  None,
+            format:   operator::formats::DataFormat::NTriples,
+        };
+        let rename_idx = plan.current_cursor_idx.unwrap();
+        let mut serialized_plan = plan.serialize(serializer.clone())?;
+        let serializer_idx = serialized_plan.current_cursor_idx.unwrap();
+
+        let limit: Operator = operator::Limit::new(100).into();
+        let inserted =
+            serialized_plan.insert_before_serializers(&limit, "Limit")?;
+        assert_eq!(inserted, 1);
+
+        let graph = serialized_plan.graph.borrow();
+        let limit_idx = NodeIndex::new(graph.node_count() - 1);
+        assert!(graph[limit_idx].operator == limit);
+        assert!(graph.find_edge(rename_idx, serializer_idx).is_none());
+        assert!(graph.find_edge(rename_idx, limit_idx).is_some());
+        assert!(graph.find_edge(limit_idx, serializer_idx).is_some());
+        drop(graph);
+
+        let serializer_op = Operator::SerializerOp { config: serializer };
+        assert!(serialized_plan
+            .insert_before_serializers(&serializer_op, "Serializer")
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_resolve_parameters() -> std::result::Result<(), PlanError> {
+        let mut plan = Plan::new();
+        let source = Source {
+            config:        HashMap::from([(
+                "path".to_string(),
+                "${data_dir}/people.csv".to_string(),
+            )]),
+            source_type:   operator::IOType::File,
+            root_iterator: Iterator::default(),
+        };
+        let target = operator::Target {
+            configuration: HashMap::from([
+                ("broker".to_string(), "${ broker }:9092".to_string()),
+                ("topic".to_string(), "$${verbatim} $5".to_string()),
+            ]),
+            target_type:   operator::IOType::Kafka,
+            data_format:   operator::formats::DataFormat::NTriples,
+        };
+        let serializer = operator::Serializer {
+            template: "?x ?y ?z .".to_string(),
+            options:  None,
+            format:   operator::formats::DataFormat::NTriples,
+        };
+        let mut sunk_plan =
+            plan.source(source).serialize(serializer)?.sink(&target)?;
+
+        let mut params =
+            HashMap::from([("data_dir".to_string(), "/data".to_string())]);
+        let err = sunk_plan.resolve_parameters(&params).unwrap_err();
+        assert!(matches!(
+            err,
+            PlanError::UnresolvedParameters(names) if names == ["broker"]
+        ));
+
+        params.insert("broker".to_string(), "localhost".to_string());
+        assert_eq!(sunk_plan.resolve_parameters(&params)?, 2);
+
+        let graph = sunk_plan.graph.borrow();
+        for node in graph.node_weights() {
+            match &node.operator {
+                Operator::SourceOp { config } => {
+                    assert_eq!(config.config["path"], "/data/people.csv");
+                }
+                Operator::TargetOp { config } => {
+                    assert_eq!(
+                        config.configuration["broker"],
+                        "localhost:9092"
+                    );
+                    assert_eq!(
+                        config.configuration["topic"],
+                        "${verbatim} $5"
+                    );
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_lookup() -> std::result::Result<(), PlanError> {
+        let mut plan = generate_dummy_processed_plan()?;
+        let table = Source {
+            config:        HashMap::from([(
+                "path".to_string(),
+                "country_codes.csv".to_string(),
+            )]),
+            source_type:   operator::IOType::File,
+            root_iterator: Iterator {
+                reference_formulation:
+                    operator::formats::ReferenceFormulation::CSVRows,
+                ..Default::default()
+            },
+        };
+        let lookup = operator::Lookup {
+            attribute:     "country_code".to_string(),
+            alias:         "country_name".to_string(),
+            table,
+            key_field:     "code".to_string(),
+            value_field:   "name".to_string(),
+            default_value: None,
+            miss_policy:   operator::LookupMissPolicy::Drop,
+        };
+
+        let plan = plan.lookup(lookup.clone())?;
+        let graph = plan.graph.borrow();
+        let lookup_node =
+            graph.node_weight(plan.current_cursor_idx.unwrap()).unwrap();
+        assert!(lookup_node.operator == lookup.into());
+
+        let json = serde_json::to_string(&lookup_node.operator).unwrap();
+        let parsed: Operator = serde_json::from_str(&json).unwrap();
+        assert!(parsed == lookup_node.operator);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_left_join() -> std::result::Result<(), PlanError> {
+        let mut plan = Plan::new();
+        let source = Source {
+            config:        HashMap::new(),
+            source_type:   operator::IOType::File,
+            root_iterator: Iterator::default(),
+        };
+        let left_plan = Rc::new(RefCell::new(plan.source(source.clone())));
+        let right_plan = Rc::new(RefCell::new(plan.source(source)));
+
+        let joined_plan = states::join::join(left_plan, right_plan)?
+            .alias("right")?
+            .where_by(vec!["id"])?
+            .equal_to_with_join_type(
+                vec!["right.id"],
+                operator::JoinType::LeftJoin,
+            )?;
+        let graph = joined_plan.graph.borrow();
+        let join_node = graph
+            .node_weight(joined_plan.current_cursor_idx.unwrap())
+            .unwrap();
+
+        let Operator::JoinOp { config } = &join_node.operator else {
+            panic!("Expected a join operator but got {:?}", join_node);
+        };
+        assert_eq!(config.join_type, operator::JoinType::LeftJoin);
+        assert_eq!(
+            config.left_right_attr_pairs,
+            vec![("id".to_string(), "right.id".to_string())]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_mermaid() -> Result<(), PlanError> {
+        let plan = generate_dummy_processed_plan()?;
+        let mermaid = plan.to_mermaid_string().map_err(|err| {
+            PlanError::GenericError(format!("{:?}", err))
+        })?;
+        let mut lines = mermaid.lines();
+
+        assert_eq!(lines.next(), Some("flowchart TD"));
+        assert!(mermaid.contains("Source Operator"));
+        assert!(mermaid.contains("<br/>Rename Operator\"]"));
+        assert_eq!(mermaid.matches("-->|\"default\"|").count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_diff() -> Result<(), PlanError> {
+        let previous = generate_dummy_processed_plan()?;
+        let plan = generate_dummy_processed_plan()?;
+        assert!(plan.diff(&previous).is_empty());
+
+        let limit_op = Operator::LimitOp {
+            config: operator::Limit {
+                limit:  10,
+                offset: 0,
+            },
+        };
+        let mut plan = plan;
+        let plan = plan.apply(&limit_op, "Limit")?;
+        let diff = plan.diff(&previous);
+        assert_eq!(diff.added, vec![limit_op.clone()]);
+        assert!(diff.removed.is_empty());
+
+        let reverse_diff = previous.diff(&plan);
+        assert_eq!(reverse_diff.removed, vec![limit_op]);
+        let pretty = reverse_diff.pretty_string().map_err(|err| {
+            PlanError::GenericError(format!("{:?}", err))
+        })?;
+        assert!(pretty.lines().all(|line| line.starts_with("- ")));
+        Ok(())
+    }
+
     #[test]
     fn test_plan_serialization() -> Result<(), PlanError> {
         let plan = generate_dummy_processed_plan()?;
@@ -3,7 +3,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use anyhow::Result;
-use operator::{Fragmenter, Join, Operator};
+use operator::{Fragmenter, Join, JoinType, Operator};
 
 use crate::data_type::{EdgeDirection, PlanEdge, PlanNode, RcRefCellPlan};
 use crate::error::PlanError;
@@ -258,6 +258,23 @@ impl WhereByPlan<Processed> {
         &mut self,
         attributes: Vec<A>,
     ) -> Result<Plan<Processed>, PlanError>
+    where
+        A: Into<String>,
+    {
+        self.equal_to_with_join_type(attributes, JoinType::InnerJoin)
+    }
+
+    /// Add the attributes from the **right plan** which will be used to check during
+    /// the join operation and apply an equi-join operator of the given
+    /// [JoinType] to the plan at the end.
+    ///
+    /// A [JoinType::LeftJoin] keeps the mapping tuples of the **left plan**
+    /// without matching mapping tuples in the **right plan**.
+    pub fn equal_to_with_join_type<A>(
+        &mut self,
+        attributes: Vec<A>,
+        join_type: JoinType,
+    ) -> Result<Plan<Processed>, PlanError>
     where
         A: Into<String>,
     {
@@ -273,11 +290,11 @@ impl WhereByPlan<Processed> {
             .zip(right_attributes.clone())
             .collect();
 
-        // TODO: Enable specification of join type and predicate type  <12-03-24, yourname> //
+        // TODO: Enable specification of predicate type  <12-03-24, yourname> //
         let join_op = Operator::JoinOp {
             config: Join {
                 left_right_attr_pairs,
-                join_type: operator::JoinType::InnerJoin,
+                join_type,
                 predicate_type: operator::PredicateType::Equal,
             },
         };
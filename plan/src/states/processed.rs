@@ -1,4 +1,6 @@
-use operator::{Fragmenter, Operator, Serializer};
+use operator::{
+    Aggregate, Fragmenter, Limit, Lookup, Operator, Serializer, Sort,
+};
 
 use super::Processed;
 use crate::data_type::{EdgeDirection, PlanEdge, PlanNode, RcRefCellPlan};
@@ -116,6 +118,55 @@ impl Plan<Processed> {
         self.apply_to_fragment(operator, node_id_prefix, fragment_str)
     }
 
+    /// Groups the mapping tuples with the given [Aggregate] config for the
+    /// current fragment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying [Plan::apply] method call fails.
+    pub fn aggregate(
+        &mut self,
+        aggregate: Aggregate,
+    ) -> Result<Plan<Processed>, PlanError> {
+        self.apply(&aggregate.into(), "Aggregate")
+    }
+
+    /// Sorts the mapping tuples with the given [Sort] config for the current
+    /// fragment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying [Plan::apply] method call fails.
+    pub fn sort(&mut self, sort: Sort) -> Result<Plan<Processed>, PlanError> {
+        self.apply(&sort.into(), "Sort")
+    }
+
+    /// Limits the mapping tuples with the given [Limit] config for the
+    /// current fragment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying [Plan::apply] method call fails.
+    pub fn limit(
+        &mut self,
+        limit: Limit,
+    ) -> Result<Plan<Processed>, PlanError> {
+        self.apply(&limit.into(), "Limit")
+    }
+
+    /// Enriches the mapping tuples with the given [Lookup] config for the
+    /// current fragment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying [Plan::apply] method call fails.
+    pub fn lookup(
+        &mut self,
+        lookup: Lookup,
+    ) -> Result<Plan<Processed>, PlanError> {
+        self.apply(&lookup.into(), "Lookup")
+    }
+
     pub fn fragment(
         &mut self,
         fragmenter: Fragmenter,
@@ -1,18 +1,54 @@
+use std::any::Any;
 use std::error::Error;
-use std::path::PathBuf;
+use std::fmt::Display;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
 
-use log::{debug, error};
+use log::{debug, error, info};
+use plan::error::PlanError;
+use plan::states::Init;
+use plan::Plan;
+use serde_json::{json, Value};
+use translator::detect::{self, MappingLanguage};
+use translator::error::{
+    LanguageErrorKind, TranslationError, TranslationErrorKind,
+};
+pub use translator::options::TranslationOptions;
+use translator::project::MappingProject;
+use translator::rml::parser::extractors::io::with_default_base;
+use translator::shexml::converter;
+use translator::shexml::error::ShExMLTranslationError;
+use translator::shexml::parcombi;
+use translator::shexml::parcombi::printer;
+use walkdir::WalkDir;
 
 use crate::handler::{FileTranslatorHandler, StringTranslatorHandler};
+use crate::output::{OutputLocation, PlanOutput};
+use crate::report::{FileReport, FileStatus};
 use crate::rml::{RMLFileHandler, RMLStringHandler};
 use crate::shexml::{ShExMLFileHandler, ShExMLStringHandler};
-use crate::util::{pretty_print_err, serialize_and_log_msg};
+use crate::util::{
+    glob_match, insert_sample_limit, pretty_print_err, resolve_parameters,
+    serialize_and_log_msg,
+};
 
+/// Translates the mapping document at `file_path` and writes the mapping
+/// plan to files with the given `output_prefix`.
+///
+/// Returns the report of the translation, the errors are logged as well.
 pub fn process_one_file(
     file_path: PathBuf,
     output_prefix: Option<String>,
-    json_only_flag: bool,
-) {
+    plan_output: &PlanOutput,
+    options: &TranslationOptions,
+) -> FileReport {
+    let started = Instant::now();
+    let report = FileReport::new(file_path.clone());
     let handlers: Vec<Box<dyn FileTranslatorHandler>> =
         vec![Box::new(RMLFileHandler), Box::new(ShExMLFileHandler)];
 
@@ -24,75 +60,725 @@ pub fn process_one_file(
             "Skipped processing file {} since it is not supported",
             file_path.to_string_lossy()
         );
-        return; 
+        return report;
     }
 
-    let (generated_plans, generated_errors_res): (Vec<_>, Vec<_>) = handlers
-        .iter()
-        .map(|handler| handler.handle_file(&file_path.to_string_lossy()))
-        .partition(|plan| plan.is_ok());
-    if generated_plans.is_empty() {
-        if !generated_errors_res.is_empty() {
+    // The extension only selects the supported files, the mapping language
+    // is detected from the content like for any other mapping document
+    let is_turtle = RMLFileHandler.can_handle(&file_path.to_string_lossy())
+        && options.language != Some(MappingLanguage::ShExML);
+    let plan_res = fs::read_to_string(&file_path)
+        .map(|mapping| {
+            if is_turtle {
+                with_default_base(mapping)
+            } else {
+                mapping
+            }
+        })
+        .map_err(|err| {
+            let language =
+                options.language.map_or("unknown".to_string(), |language| {
+                    language.to_string()
+                });
+            TranslationFailure::from(LanguageFailure::new(language, &err))
+        })
+        .and_then(|mapping| translate_to_plan(&mapping, options));
+
+    let (mut plan, language) = match plan_res {
+        Ok((plan, language, _)) => (plan, language),
+        Err(failure) => {
             error!(
                 "Errored while translating: {}",
                 file_path.to_string_lossy()
             );
+            pretty_print_err(&failure);
+            return report.failed(failure.errors, started);
         }
-        generated_errors_res
-            .into_iter()
-            .flat_map(|pe| pe.err())
-            .enumerate()
-            .for_each(|(id, err)| {
-                error!("Handler is: {:?} ", handlers[id]);
-                pretty_print_err(&err);
-            });
-    } else {
-        for mut plan in generated_plans.into_iter().flat_map(|p_res| p_res.ok())
-        {
-            if let Err(err) = serialize_and_log_msg(
-                output_prefix.clone().unwrap(),
+    };
+
+    let mut report = FileReport {
+        status:   FileStatus::Translated,
+        language: Some(language),
+        ..report
+    };
+    let outputs_res = resolve_parameters(&mut plan, &options.params)
+        .and_then(|_| insert_sample_limit(&mut plan, options.sample_limit))
+        .and_then(|_| {
+            serialize_and_log_msg(
+                output_prefix.unwrap(),
                 &mut plan,
                 file_path.to_string_lossy(),
-                json_only_flag,
-            ) {
-                error!(
-                    "Errored while serializing mapping plan for: {}",
-                    file_path.to_string_lossy()
-                );
-                pretty_print_err(&err);
+                plan_output,
+            )
+        });
+
+    match outputs_res {
+        Ok(outputs) => report.outputs = outputs,
+        Err(err) => {
+            error!(
+                "Errored while serializing mapping plan for: {}",
+                file_path.to_string_lossy()
+            );
+            pretty_print_err(&err);
+            report.status = FileStatus::Failed;
+            report
+                .errors
+                .push(LanguageFailure::new(language.to_string(), &err));
+        }
+    }
+    report.duration = started.elapsed();
+    report
+}
+
+/// Options to select and translate the mapping documents of a folder.
+#[derive(Debug, Clone)]
+pub struct FolderOptions {
+    /// Number of files translated in parallel
+    pub workers:   usize,
+    /// Maximum depth of the translated files below the folder
+    pub max_depth: usize,
+    /// Glob patterns of which a file has to match at least one to be
+    /// translated, every file is translated if empty
+    pub include:   Vec<String>,
+    /// Glob patterns of files which are not translated
+    pub exclude:   Vec<String>,
+}
+
+impl Default for FolderOptions {
+    fn default() -> Self {
+        Self {
+            workers:   thread::available_parallelism().map_or(1, usize::from),
+            max_depth: 4,
+            include:   Vec::new(),
+            exclude:   Vec::new(),
+        }
+    }
+}
+
+/// Translates the mapping documents in the folder at `folder_path` in
+/// parallel, writing the mapping plan of every document to the
+/// `plan_output`. An output directory mirrors the subfolders of the
+/// documents.
+///
+/// The glob patterns of the `folder_options` match the path of a file
+/// relative to the folder, or only its file name if the pattern has no
+/// `/`. Returns the reports of the selected files in the order in which
+/// they are found.
+pub fn process_folder(
+    folder_path: &Path,
+    folder_options: &FolderOptions,
+    plan_output: &PlanOutput,
+    options: &TranslationOptions,
+) -> Vec<FileReport> {
+    let include = &folder_options.include;
+    let exclude = &folder_options.exclude;
+    let files: Vec<PathBuf> = WalkDir::new(folder_path)
+        .max_depth(folder_options.max_depth)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|dentry| dentry.file_type().is_file())
+        .map(|dentry| dentry.into_path())
+        .filter(|path| {
+            let relative = path.strip_prefix(folder_path).unwrap_or(path);
+            (include.is_empty() || matches_any(include, relative))
+                && !matches_any(exclude, relative)
+        })
+        .collect();
+
+    let next_file = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::with_capacity(files.len()));
+    let workers = folder_options.workers.clamp(1, files.len().max(1));
+    debug!("Translating {} files with {} workers", files.len(), workers);
+
+    // Plans are not thread-safe, every file is translated and written by
+    // one worker.
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let idx = next_file.fetch_add(1, Ordering::Relaxed);
+                    let Some(input_path) = files.get(idx) else {
+                        break;
+                    };
+                    debug!(
+                        "Attempting to translate: {}",
+                        input_path.to_string_lossy()
+                    );
+
+                    let output_dir = input_path.parent().map_or(
+                        "".to_string(),
+                        |p| p.to_string_lossy().to_string(),
+                    );
+                    let output_prefix = output_dir
+                        + "/"
+                        + &input_path.file_stem().unwrap().to_string_lossy();
+
+                    // Keep the plans of documents with the same name in
+                    // different subfolders apart in an output directory
+                    let subfolder = input_path
+                        .parent()
+                        .and_then(|p| p.strip_prefix(folder_path).ok())
+                        .unwrap_or(Path::new(""));
+                    let file_output = plan_output.in_subdirectory(subfolder);
+
+                    let started = Instant::now();
+                    let report = panic::catch_unwind(|| {
+                        process_one_file(
+                            input_path.clone(),
+                            Some(output_prefix),
+                            &file_output,
+                            options,
+                        )
+                    })
+                    .unwrap_or_else(|panic| {
+                        error!(
+                            "Panicked while translating: {}",
+                            input_path.to_string_lossy()
+                        );
+                        FileReport::new(input_path.clone())
+                            .failed(vec![panic_failure(panic)], started)
+                    });
+                    reports.lock().unwrap().push((idx, report));
+                }
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|(idx, _)| *idx);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+/// Converts the payload of a panic of a translator to a failure, so that
+/// a folder is still translated, or the service still responds, if one of
+/// its documents panics.
+pub fn panic_failure(panic: Box<dyn Any + Send>) -> LanguageFailure {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+
+    LanguageFailure {
+        language: "unknown".to_string(),
+        location: None,
+        messages: vec![format!("translator panicked: {}", message)],
+    }
+}
+
+fn matches_any(globs: &[String], relative_path: &Path) -> bool {
+    let path = relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let file_name = relative_path
+        .file_name()
+        .map_or(String::new(), |name| name.to_string_lossy().to_string());
+
+    globs.iter().any(|glob| {
+        if glob.contains('/') {
+            glob_match(glob, &path)
+        } else {
+            glob_match(glob, &file_name)
+        }
+    })
+}
+
+/// Mapping plan translated from a mapping document.
+#[derive(Debug, Clone)]
+pub struct TranslationOutput {
+    /// The mapping language of the translated document
+    pub language:   MappingLanguage,
+    /// Confidence of the detection of the mapping language, between 0 and 1
+    pub confidence: f32,
+    /// The mapping plan serialized as JSON
+    pub plan:       String,
+}
+
+/// Failure to translate a mapping document with any of the supported
+/// mapping languages, with the error of every attempted language.
+#[derive(Debug, Clone)]
+pub struct TranslationFailure {
+    pub errors: Vec<LanguageFailure>,
+}
+
+/// Error of the translation of a mapping document with one mapping language.
+#[derive(Debug, Clone)]
+pub struct LanguageFailure {
+    pub language: String,
+    /// Location of the error in the mapping document if it is known
+    pub location: Option<ErrorLocation>,
+    /// Messages of the error chain, starting with the outermost error
+    pub messages: Vec<String>,
+}
+
+/// Location in a mapping document, lines and columns start from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorLocation {
+    pub line:   usize,
+    pub column: usize,
+}
+
+impl LanguageFailure {
+    pub(crate) fn new(language: String, err: &(dyn Error + 'static)) -> Self {
+        let messages = std::iter::successors(Some(err), |&err| err.source())
+            .map(|err| err.to_string())
+            .collect();
+
+        LanguageFailure {
+            language,
+            location: None,
+            messages,
+        }
+    }
+
+    fn from_translation_error(
+        language: String,
+        err: &TranslationError,
+    ) -> Self {
+        LanguageFailure {
+            location: error_location(err),
+            ..LanguageFailure::new(language, err)
+        }
+    }
+
+    /// Serializes the failure as JSON object with its language, location
+    /// and messages.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "language": self.language,
+            "location": self.location.map(|location| json!({
+                "line":   location.line,
+                "column": location.column,
+            })),
+            "messages": self.messages,
+        })
+    }
+}
+
+impl Display for ErrorLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+impl Display for LanguageFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.language)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        write!(f, ": {}", self.messages.join(": "))
+    }
+}
+
+impl Display for TranslationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to translate the mapping document")?;
+        self.errors
+            .iter()
+            .try_for_each(|failure| write!(f, "\n  - {}", failure))
+    }
+}
+
+impl Error for TranslationFailure {}
+
+impl From<LanguageFailure> for TranslationFailure {
+    fn from(failure: LanguageFailure) -> Self {
+        TranslationFailure {
+            errors: vec![failure],
+        }
+    }
+}
+
+/// Gets the location of the error in the mapping document, which is only
+/// known for ShExML lexer and parser errors.
+fn error_location(err: &TranslationError) -> Option<ErrorLocation> {
+    let lang_err_kind = match &err.kind {
+        TranslationErrorKind::LanguageError(lang_err_kind)
+        | TranslationErrorKind::FileLanguageError { lang_err_kind, .. } => {
+            lang_err_kind
+        }
+        _ => return None,
+    };
+
+    match lang_err_kind {
+        LanguageErrorKind::ShExMLTranslationError(shexml_err) => {
+            match shexml_err.as_ref() {
+                ShExMLTranslationError::ParseCombiError(parse_err) => {
+                    parse_err.diagnostics.first().map(|diagnostic| {
+                        ErrorLocation {
+                            line:   diagnostic.line,
+                            column: diagnostic.column,
+                        }
+                    })
+                }
+                _ => None,
             }
         }
+        _ => None,
+    }
+}
+
+/// Translates the `mapping` document into a mapping plan serialized as JSON.
+///
+/// The mapping language is detected from the document unless it is forced
+/// with [TranslationOptions::language]. The [TranslationFailure] contains
+/// the errors of every supported language if it cannot be detected.
+///
+/// The `${name}` placeholders in the sources and targets of the plan are
+/// resolved with [TranslationOptions::params], any placeholder without a
+/// parameter fails the translation.
+pub fn translate(
+    mapping: &str,
+    options: &TranslationOptions,
+) -> Result<TranslationOutput, TranslationFailure> {
+    let translated = translate_mapping(mapping, options)?;
+    serialize_output(translated)
+}
+
+/// Serializes the `translated` mapping plan as JSON, together with its
+/// mapping language and the confidence of the detection.
+fn serialize_output(
+    (plan, language, confidence): (Plan<Init>, MappingLanguage, f32),
+) -> Result<TranslationOutput, TranslationFailure> {
+    let serialized = plan
+        .to_json_string()
+        .map_err(|err| PlanError::GenericError(format!("{:?}", err)));
+
+    match serialized {
+        Ok(plan) => {
+            Ok(TranslationOutput {
+                language,
+                confidence,
+                plan,
+            })
+        }
+        Err(err) => {
+            Err(LanguageFailure::new(language.to_string(), &err).into())
+        }
+    }
+}
+
+/// Translates the `mapping` document like [translate] and writes the
+/// mapping plan to the `plan_output`, named after the given
+/// `output_prefix`. Returns the paths of the written files.
+pub fn translate_to_output(
+    mapping: &str,
+    output_prefix: &str,
+    plan_output: &PlanOutput,
+    options: &TranslationOptions,
+) -> Result<Vec<String>, TranslationFailure> {
+    let (plan, language, _) = translate_mapping(mapping, options)?;
+
+    plan_output
+        .write(&plan, output_prefix)
+        .map_err(|err| LanguageFailure::new(language.to_string(), &err).into())
+}
+
+/// Translates the `mapping` document like [translate] into a mapping plan
+/// with its parameters resolved and the sample limit of the `options`
+/// inserted, together with the language and confidence of the detection.
+pub(crate) fn translate_mapping(
+    mapping: &str,
+    options: &TranslationOptions,
+) -> Result<(Plan<Init>, MappingLanguage, f32), TranslationFailure> {
+    let translated = translate_to_plan(mapping, options)?;
+    resolve_plan(translated, options)
+}
+
+/// Resolves the parameters of the `translated` mapping plan and inserts the
+/// sample limit of the `options`.
+fn resolve_plan(
+    (mut plan, language, confidence): (Plan<Init>, MappingLanguage, f32),
+    options: &TranslationOptions,
+) -> Result<(Plan<Init>, MappingLanguage, f32), TranslationFailure> {
+    resolve_parameters(&mut plan, &options.params)
+        .and_then(|_| insert_sample_limit(&mut plan, options.sample_limit))
+        .map_err(|err| LanguageFailure::new(language.to_string(), &err))?;
+
+    Ok((plan, language, confidence))
+}
+
+/// Translates the mapping project of the RML documents at the given
+/// `paths`, and the documents they import, into one combined mapping plan
+/// serialized as JSON.
+///
+/// See [MappingProject::load] for how the documents are merged.
+pub fn translate_project(
+    paths: &[PathBuf],
+    options: &TranslationOptions,
+) -> Result<TranslationOutput, TranslationFailure> {
+    let project = load_project(paths)?;
+    let translated = translate_project_to_plan(project, options)?;
+    serialize_output(resolve_plan(translated, options)?)
+}
+
+/// Translates the loaded mapping `project` like [translate_mapping].
+pub(crate) fn translate_project_mapping(
+    project: MappingProject,
+    options: &TranslationOptions,
+) -> Result<(Plan<Init>, MappingLanguage, f32), TranslationFailure> {
+    let translated = translate_project_to_plan(project, options)?;
+    resolve_plan(translated, options)
+}
+
+pub(crate) fn load_project(
+    paths: &[PathBuf],
+) -> Result<MappingProject, TranslationFailure> {
+    MappingProject::load(paths).map_err(|err| {
+        LanguageFailure::from_translation_error("RML".to_string(), &err)
+            .into()
+    })
+}
+
+/// Translates the mapping project of the RML documents at `file_paths`
+/// into one combined mapping plan, which is written to the `plan_output`
+/// named after the given `output_prefix`.
+///
+/// Returns the paths of the written files, the errors are logged as well.
+pub fn process_project(
+    file_paths: &[PathBuf],
+    output_prefix: String,
+    plan_output: &PlanOutput,
+    options: &TranslationOptions,
+) -> Result<Vec<String>, TranslationFailure> {
+    let plan_res = load_project(file_paths).and_then(|project| {
+        let files = project.files.clone();
+        translate_project_to_plan(project, options)
+            .map(|(plan, language, _)| (plan, language, files))
+    });
+    let (mut plan, language, files) = match plan_res {
+        Ok(translated) => translated,
+        Err(failure) => {
+            error!("Errored while translating the mapping project");
+            pretty_print_err(&failure);
+            return Err(failure);
+        }
     };
+
+    let files = files
+        .iter()
+        .map(|file| file.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(", ");
+    resolve_parameters(&mut plan, &options.params)
+        .and_then(|_| insert_sample_limit(&mut plan, options.sample_limit))
+        .and_then(|_| {
+            serialize_and_log_msg(
+                output_prefix,
+                &mut plan,
+                &files,
+                plan_output,
+            )
+        })
+        .map_err(|err| {
+            error!("Errored while serializing mapping plan for: {}", files);
+            pretty_print_err(&err);
+            LanguageFailure::new(language.to_string(), &err).into()
+        })
 }
 
-pub fn process_one_str(mapping: &str) -> String {
+/// Translates the `mapping` document with the forced language of the
+/// `options` or the detected one, together with the confidence of the
+/// detection.
+fn translate_to_plan(
+    mapping: &str,
+    options: &TranslationOptions,
+) -> Result<(Plan<Init>, MappingLanguage, f32), TranslationFailure> {
+    let (parsed, confidence) = match options.language {
+        Some(language) => {
+            let parsed = detect::parse(mapping, language).map_err(|err| {
+                LanguageFailure::from_translation_error(
+                    language.to_string(),
+                    &err,
+                )
+            })?;
+            (parsed, 1.0)
+        }
+        None => {
+            match detect::detect(mapping) {
+                Some(detected) => (detected.parsed, detected.confidence),
+                None => return Err(undetected_failure(mapping, options)),
+            }
+        }
+    };
+
+    let language = parsed.language();
+    info!(
+        "Translating {} document detected with confidence {:.2}",
+        language, confidence
+    );
+    match detect::translate_parsed(mapping, parsed, options) {
+        Ok(plan) => Ok((plan, language, confidence)),
+        Err(err) => {
+            Err(LanguageFailure::from_translation_error(
+                language.to_string(),
+                &err,
+            )
+            .into())
+        }
+    }
+}
+
+/// Translates the merged graph of the mapping `project` with the forced
+/// language of the `options` or the detected RML version, together with
+/// the confidence of the detection.
+fn translate_project_to_plan(
+    project: MappingProject,
+    options: &TranslationOptions,
+) -> Result<(Plan<Init>, MappingLanguage, f32), TranslationFailure> {
+    let base_iri = project.base_iri.clone();
+    let detected = project.detect(options.language).map_err(|err| {
+        let language = options
+            .language
+            .map_or("RML".to_string(), |lang| lang.to_string());
+        LanguageFailure::from_translation_error(language, &err)
+    })?;
+
+    let language = detected.parsed.language();
+    info!(
+        "Translating {} mapping project detected with confidence {:.2}",
+        language, detected.confidence
+    );
+    detect::translate_parsed_with_base(detected.parsed, base_iri, options)
+        .map(|plan| (plan, language, detected.confidence))
+        .map_err(|err| {
+            LanguageFailure::from_translation_error(
+                language.to_string(),
+                &err,
+            )
+            .into()
+        })
+}
+
+/// Collects the errors of every supported mapping language for a `mapping`
+/// document whose language cannot be detected.
+fn undetected_failure(
+    mapping: &str,
+    options: &TranslationOptions,
+) -> TranslationFailure {
     let handlers: Vec<Box<dyn StringTranslatorHandler>> =
         vec![Box::new(RMLStringHandler), Box::new(ShExMLStringHandler)];
 
-    let (generated_plans, generated_errors_res): (Vec<_>, Vec<_>) = handlers
+    let errors = handlers
         .iter()
-        .map(|handler| handler.translate(mapping))
-        .partition(|plan| plan.is_ok());
+        .filter_map(|handler| {
+            handler.translate(mapping, options).err().map(|err| {
+                LanguageFailure::from_translation_error(
+                    handler.language(),
+                    &err,
+                )
+            })
+        })
+        .collect();
+
+    TranslationFailure { errors }
+}
 
-    if generated_plans.is_empty() {
-        if !generated_errors_res.is_empty() {
+pub fn process_one_str(mapping: &str) -> String {
+    process_one_str_sampled(mapping, None)
+}
+
+/// Same as [process_one_str] but with a [Limit](operator::Limit) operator
+/// inserted before every serializer if a `sample_limit` is given.
+///
+/// # Panics
+///
+/// Panics if the mapping document cannot be translated, use [translate] to
+/// handle the translation errors instead.
+pub fn process_one_str_sampled(
+    mapping: &str,
+    sample_limit: Option<usize>,
+) -> String {
+    let options = TranslationOptions {
+        sample_limit,
+        ..Default::default()
+    };
+
+    match translate(mapping, &options) {
+        Ok(output) => output.plan,
+        Err(failure) => {
             error!("Errored while translating from stdin");
+            pretty_print_err(&failure);
+            panic!("Generated plan not serialized as string")
+        }
+    }
+}
+
+/// Formats the ShExML document at `file_path` into its canonical form.
+///
+/// The formatted document is written back to the file if `write_flag` is
+/// set, otherwise to the `output` or stdout by default. Comments in the
+/// document are not preserved. The returned parse or write error is logged
+/// as well.
+pub fn format_one_file(
+    file_path: PathBuf,
+    write_flag: bool,
+    output: Option<&OutputLocation>,
+) -> Result<(), ShExMLTranslationError> {
+    let shexml_document = match parcombi::parse_file(&file_path) {
+        Ok(shexml_document) => shexml_document,
+        Err(err) => {
+            error!("Errored while formatting: {}", file_path.to_string_lossy());
+            let err = ShExMLTranslationError::from(err);
+            pretty_print_err(&err);
+            return Err(err);
+        }
+    };
+    let formatted = printer::print(&shexml_document);
+
+    let output = match (write_flag, output) {
+        (true, _) => OutputLocation::File(file_path.clone()),
+        (false, Some(output)) => output.clone(),
+        (false, None) => OutputLocation::StdOut,
+    };
+    match output.write_document(&file_path, &formatted) {
+        Ok(path) => {
+            info!("Formatted file to: {}", path);
+            Ok(())
+        }
+        Err(err) => {
+            error!("Errored while writing: {}", file_path.to_string_lossy());
+            pretty_print_err(&err);
+            Err(ShExMLTranslationError::IOError(err))
+        }
+    }
+}
+
+/// Converts the ShExML document at `file_path` into an equivalent RML
+/// document in Turtle which is written to the `output`, or printed to
+/// stdout by default.
+///
+/// Nothing is written if the document contains constructs that cannot be
+/// converted, instead all of them are reported. The returned error is
+/// logged as well.
+pub fn convert_one_file_to_rml(
+    file_path: PathBuf,
+    output: Option<&OutputLocation>,
+) -> Result<(), ShExMLTranslationError> {
+    let converted = parcombi::parse_file(&file_path)
+        .map_err(ShExMLTranslationError::from)
+        .and_then(|document| converter::convert_to_rml(&document));
+
+    let rml_document = match converted {
+        Ok(rml_document) => rml_document,
+        Err(err) => {
+            error!("Errored while converting: {}", file_path.to_string_lossy());
+            pretty_print_err(&err);
+            return Err(err);
         }
-        generated_errors_res
-            .into_iter()
-            .flat_map(|pe| pe.err())
-            .enumerate()
-            .for_each(|(id, err)| {
-                error!("Handler is: {:?} ", handlers[id]);
-                pretty_print_err(&err);
-            });
-    } else if let Some(plan) = generated_plans
-        .into_iter()
-        .flat_map(|p_res| p_res.ok())
-        .next()
-    {
-        return plan.to_string().unwrap();
     };
 
-    panic!("Generated plan not serialized as string")
+    let output = output.cloned().unwrap_or(OutputLocation::StdOut);
+    let document_path = file_path.with_extension("ttl");
+    if let Err(err) = output.write_document(&document_path, &rml_document) {
+        error!("Errored while writing: {}", file_path.to_string_lossy());
+        pretty_print_err(&err);
+        return Err(ShExMLTranslationError::IOError(err));
+    }
+
+    Ok(())
 }
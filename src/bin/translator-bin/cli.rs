@@ -1,4 +1,12 @@
-use clap::{arg, Command};
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+use clap::{arg, value_parser, ArgAction, Command};
+use ltranslator::output::{OutputLocation, PlanFormat};
+use ltranslator::report::SummaryFormat;
+use translator::detect::MappingLanguage;
+use translator::options::{parse_param, Compression, SerializationFormat};
 
 pub const TRANSLATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -23,12 +31,79 @@ impl Cli {
             .subcommand(Command::new("folder")
                          .about("translate all mapping documents under the given folder")
                          .arg(arg!(<FOLDER> "the folder containing several mapping documents"))
+                         .arg(arg!(-w --workers <WORKERS> "The number of documents translated in parallel, defaults to the number of CPUs")
+                              .value_parser(value_parser!(NonZeroUsize)))
+                         .arg(arg!(--"max-depth" <DEPTH> "The maximum depth of the translated documents below the folder")
+                              .value_parser(value_parser!(usize))
+                              .default_value("4"))
+                         .arg(arg!(--include <GLOB> "Only translates the documents matching one of the glob patterns")
+                              .action(ArgAction::Append))
+                         .arg(arg!(--exclude <GLOB> "Skips the documents matching one of the glob patterns")
+                              .action(ArgAction::Append))
+                         .arg(arg!(--summary <SUMMARY> "Writes a summary of the translated documents to the given file, or - for stdout"))
+                         .arg(arg!(--"summary-format" <FORMAT> "The format of the summary")
+                              .value_parser(SummaryFormat::VALUES)
+                              .default_value("json"))
+                         .arg_required_else_help(true))
+            .subcommand(Command::new("project")
+                         .about("translate several RML documents, and the documents they import with owl:imports, into one combined plan")
+                         .arg(arg!(<DOCUMENTS> ... "the RML documents of the mapping project"))
+                         .arg(arg!(-p --prefix <PREFIX> "the prefix of the generated plan files, defaults to the name of the first document"))
+                         .arg_required_else_help(true))
+            .subcommand(Command::new("watch")
+                         .about("translate a mapping document, or all documents under a folder, again whenever they or the documents they import change")
+                         .arg(arg!(<PATH> "the mapping document or folder to be watched"))
+                         .arg(arg!(--interval <MILLIS> "The interval in milliseconds in which the documents are checked for changes")
+                              .value_parser(value_parser!(u64).range(1..))
+                              .default_value("500"))
+                         .arg(arg!(--"max-depth" <DEPTH> "The maximum depth of the watched documents below the folder")
+                              .value_parser(value_parser!(usize))
+                              .default_value("4"))
+                         .arg(arg!(--diff "Logs the operators added and removed by every change"))
                          .arg_required_else_help(true))
+            .subcommand(Command::new("serve")
+                         .about("serve an HTTP API which translates the posted mapping documents, with the given options as defaults")
+                         .arg(arg!(-a --address <ADDRESS> "The address the API listens on")
+                              .value_parser(value_parser!(SocketAddr))
+                              .default_value("127.0.0.1:8080")))
             .subcommand(Command::new("stdin")
-                         .about("translate all input from stdin"))
+                         .about("translate the mapping document read from stdin")
+                         .arg(arg!(-p --prefix <PREFIX> "the prefix of the generated plan files when writing to a directory")
+                              .default_value("stdin")))
+            .subcommand(Command::new("fmt")
+                         .about("format a ShExML document into its canonical form")
+                         .arg(arg!(<DOCUMENT> "the ShExML document to be formatted"))
+                         .arg(arg!(-w --write "Overwrites the document instead of printing the formatted document"))
+                         .arg_required_else_help(true))
+            .subcommand(Command::new("convert")
+                         .about("convert a ShExML document into another mapping language")
+                         .arg(arg!(<DOCUMENT> "the ShExML document to be converted"))
+                         .arg(arg!(--to <LANGUAGE> "the mapping language to convert to")
+                              .value_parser(["rml"])
+                              .required(true))
+                         .arg_required_else_help(true))
             .arg(arg!(-d --debug ...  "Turns on debugging and logging to file"))
             .arg(arg!(-j --json ... "Only generate dot files in JSON format"))
-            .arg(arg!(-o --outputFolderSuffix <OUTPUT_FOLDER_SUFFIX> "The output folder suffix"));
+            .arg(arg!(-o --output <OUTPUT> "The file or directory, ending with /, to write the output to, or - for stdout if there is a single plan. Plans are written next to the documents by default")
+                 .value_parser(OutputLocation::from_str))
+            .arg(arg!(--format <FORMAT> "The format of the generated plans, can be given several times")
+                 .action(ArgAction::Append)
+                 .value_parser(PlanFormat::VALUES))
+            .arg(arg!(-l --limit <LIMIT> "Inserts a limit operator before every serializer to sample the generated output")
+                 .value_parser(value_parser!(usize)))
+            .arg(arg!(--language <LANGUAGE> "Translates with the given mapping language instead of detecting it")
+                 .value_parser(MappingLanguage::VALUES))
+            .arg(arg!(--"base-iri" <BASE_IRI> "Overrides the base IRI declared in the mapping document"))
+            .arg(arg!(--serialization <SERIALIZATION> "The serialization format of output without a declared logical target")
+                 .value_parser(SerializationFormat::VALUES))
+            .arg(arg!(--target <TARGET> "The file path, or stdout, to write output without a declared logical target to"))
+            .arg(arg!(--compression <COMPRESSION> "The compression of output without a declared logical target")
+                 .value_parser(Compression::VALUES))
+            .arg(arg!(--"no-named-graphs" "Generates every triple in the default graph instead of named graphs"))
+            .arg(arg!(--param <PARAM> "Resolves the ${name} placeholders of sources and targets with the given name=value parameter")
+                 .action(ArgAction::Append)
+                 .value_parser(parse_param))
+            .arg(arg!(--"param-file" <PARAM_FILE> "Reads the parameters from a file with a name=value parameter on every line"));
 
         Self { cmd }
     }
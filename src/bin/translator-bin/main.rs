@@ -2,14 +2,28 @@
 mod cli;
 
 use std::io;
-use std::io::BufRead;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{fs, process};
 
-use log::debug;
-use ltranslator::api::{process_one_file, process_one_str};
+use clap::ArgMatches;
+use log::{debug, error};
+use ltranslator::api::{
+    convert_one_file_to_rml, format_one_file, process_folder,
+    process_one_file, process_project, translate_to_output, FolderOptions,
+    TranslationOptions,
+};
 use ltranslator::logger::init_logger;
+use ltranslator::output::{OutputLocation, PlanFormat, PlanOutput};
+use ltranslator::report::{FileStatus, SummaryFormat};
+use ltranslator::serve::Service;
+use ltranslator::util::pretty_print_err;
+use ltranslator::watch::{WatchOptions, Watcher};
 use plan::error::PlanError;
-use walkdir::WalkDir;
 
 pub fn main() -> Result<(), PlanError> {
     let cli = cli::Cli::new();
@@ -17,9 +31,31 @@ pub fn main() -> Result<(), PlanError> {
     let matches = cli.cmd.get_matches();
     let debug_flag_count = *matches.get_one::<u8>("debug").unwrap();
     let json_only = (*matches.get_one::<u8>("json").unwrap()) >= 1;
+    let output = matches.get_one::<OutputLocation>("output");
+    let mut options = TranslationOptions {
+        sample_limit:  matches.get_one::<usize>("limit").copied(),
+        language:      parse_arg(&matches, "language"),
+        base_iri:      matches.get_one::<String>("base-iri").cloned(),
+        serialization: parse_arg(&matches, "serialization")
+            .unwrap_or_default(),
+        target:        parse_arg(&matches, "target").unwrap_or_default(),
+        compression:   parse_arg(&matches, "compression"),
+        named_graphs:  !matches.get_flag("no-named-graphs"),
+        ..Default::default()
+    };
     init_logger(debug_flag_count >= 1)
         .map_err(|err| PlanError::GenericError(err.to_string()))?;
 
+    // Parameters given on the command line override the parameter file
+    if let Some(param_file) = matches.get_one::<String>("param-file") {
+        options
+            .set("param-file", param_file)
+            .map_err(PlanError::GenericError)?;
+    }
+    if let Some(params) = matches.get_many::<(String, String)>("param") {
+        options.params.extend(params.cloned());
+    }
+
     if let Some(file_matches) = matches.subcommand_matches("file") {
         let file_path_string: &String =
             file_matches.get_one("DOCUMENT").unwrap();
@@ -31,44 +67,226 @@ pub fn main() -> Result<(), PlanError> {
             let derived_string = derived_prefix.to_string_lossy();
             let _ = output_prefix.insert(derived_string.to_string());
         }
-        process_one_file(file_path, output_prefix, json_only);
+        let plan_output = build_plan_output(&matches, None, json_only, 1)?;
+        let report = process_one_file(
+            file_path,
+            output_prefix,
+            &plan_output,
+            &options,
+        );
+        match report.status {
+            FileStatus::Translated => {}
+            FileStatus::Failed => process::exit(1),
+            FileStatus::Skipped => {
+                error!(
+                    "Skipped {} since it is not a supported mapping document",
+                    file_path_string
+                );
+                process::exit(1);
+            }
+        }
     } else if let Some(folder_matches) = matches.subcommand_matches("folder") {
         let folder_path_string: &String =
             folder_matches.get_one("FOLDER").unwrap();
         let folder_path: PathBuf = folder_path_string.into();
-        let files = WalkDir::new(folder_path)
-            .max_depth(4)
-            .into_iter()
-            .filter_map(|entry| entry.ok())
-            .filter(|dentry| dentry.file_type().is_file());
-
-        for file in files {
-            debug!(
-                "Attempting to translate: {}",
-                file.path().to_string_lossy()
-            );
-            let input_path = file.path();
-
-            let output_dir = input_path
-                .parent()
-                .map_or("".to_string(), |p| p.to_string_lossy().to_string());
-            let output_prefix = output_dir
-                + "/"
-                + &input_path.file_stem().unwrap().to_string_lossy();
-
-            process_one_file(input_path.to_path_buf(), Some(output_prefix), json_only);
+        let mut folder_options = FolderOptions {
+            max_depth: *folder_matches.get_one::<usize>("max-depth").unwrap(),
+            include:   folder_matches
+                .get_many::<String>("include")
+                .map_or(Vec::new(), |globs| globs.cloned().collect()),
+            exclude:   folder_matches
+                .get_many::<String>("exclude")
+                .map_or(Vec::new(), |globs| globs.cloned().collect()),
+            ..Default::default()
+        };
+        if let Some(workers) = folder_matches.get_one::<NonZeroUsize>("workers")
+        {
+            folder_options.workers = workers.get();
         }
-    } else if let Some(_stdin_matches) = matches.subcommand_matches("stdin") {
-        let mut mapping = String::new();
-        let stdin = io::stdin();
-        for line in stdin.lock().lines() {
-            mapping = String::from(mapping + line.unwrap().clone().as_str());
+
+        // Validated as several plans since the folder may contain many
+        let plan_output = build_plan_output(&matches, None, json_only, 2)?;
+        let reports = process_folder(
+            &folder_path,
+            &folder_options,
+            &plan_output,
+            &options,
+        );
+        if let Some(summary_path) = folder_matches.get_one::<String>("summary")
+        {
+            let summary = parse_arg::<SummaryFormat>(
+                folder_matches,
+                "summary-format",
+            )
+            .unwrap_or_default()
+            .format(&reports);
+
+            if summary_path == "-" {
+                println!("{}", summary);
+            } else {
+                fs::write(summary_path, summary).map_err(|err| {
+                    PlanError::GenericError(format!(
+                        "cannot write summary to {}: {}",
+                        summary_path, err
+                    ))
+                })?;
+            }
         }
 
+        let failed = reports
+            .iter()
+            .filter(|report| report.status == FileStatus::Failed)
+            .count();
+        if failed > 0 {
+            error!("Failed to translate {} of {} files", failed, reports.len());
+            process::exit(1);
+        }
+    } else if let Some(project_matches) = matches.subcommand_matches("project")
+    {
+        let file_paths: Vec<PathBuf> = project_matches
+            .get_many::<String>("DOCUMENTS")
+            .unwrap()
+            .map(PathBuf::from)
+            .collect();
+        let output_prefix = project_matches
+            .get_one::<String>("prefix")
+            .cloned()
+            .or_else(|| {
+                let file_stem = file_paths[0].file_stem()?;
+                Some(file_stem.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| "output".to_string());
+
+        debug!("Attempting to translate the project: {:?}", file_paths);
+        let plan_output = build_plan_output(&matches, None, json_only, 1)?;
+        let processed =
+            process_project(&file_paths, output_prefix, &plan_output, &options);
+        if processed.is_err() {
+            process::exit(1);
+        }
+    } else if let Some(watch_matches) = matches.subcommand_matches("watch") {
+        let path: PathBuf =
+            watch_matches.get_one::<String>("PATH").unwrap().into();
+        let watch_options = WatchOptions {
+            interval:  Duration::from_millis(
+                *watch_matches.get_one::<u64>("interval").unwrap(),
+            ),
+            max_depth: *watch_matches.get_one::<usize>("max-depth").unwrap(),
+            diff:      watch_matches.get_flag("diff"),
+        };
+
+        let plan_count = if path.is_dir() { 2 } else { 1 };
+        let plan_output =
+            build_plan_output(&matches, None, json_only, plan_count)?;
+        Watcher::new(path, watch_options, &plan_output, &options).run();
+    } else if let Some(serve_matches) = matches.subcommand_matches("serve") {
+        let address = serve_matches.get_one::<SocketAddr>("address").unwrap();
+        let service = Service::bind(address, options).map_err(|err| {
+            PlanError::GenericError(format!(
+                "cannot listen on {}: {}",
+                address, err
+            ))
+        })?;
+        service.run();
+    } else if let Some(fmt_matches) = matches.subcommand_matches("fmt") {
+        let file_path_string: &String =
+            fmt_matches.get_one("DOCUMENT").unwrap();
+        let write_flag = fmt_matches.get_flag("write");
+
+        debug!("Attempting to format: {:?}", file_path_string);
+        let formatted =
+            format_one_file(file_path_string.into(), write_flag, output);
+        if formatted.is_err() {
+            process::exit(1);
+        }
+    } else if let Some(convert_matches) = matches.subcommand_matches("convert")
+    {
+        let file_path_string: &String =
+            convert_matches.get_one("DOCUMENT").unwrap();
+        let target_language: &String = convert_matches.get_one("to").unwrap();
+
+        debug!(
+            "Attempting to convert {:?} to {}",
+            file_path_string, target_language
+        );
+        if target_language == "rml" {
+            let converted =
+                convert_one_file_to_rml(file_path_string.into(), output);
+            if converted.is_err() {
+                process::exit(1);
+            }
+        }
+    } else if let Some(stdin_matches) = matches.subcommand_matches("stdin") {
+        let output_prefix: &String = stdin_matches.get_one("prefix").unwrap();
+        let plan_output = build_plan_output(
+            &matches,
+            Some(OutputLocation::StdOut),
+            json_only,
+            1,
+        )?;
+
+        let mut mapping = String::new();
+        io::stdin().read_to_string(&mut mapping).map_err(|err| {
+            PlanError::GenericError(format!("cannot read stdin: {}", err))
+        })?;
+
         debug!("Attempting to translate from stdin");
-        let out = process_one_str(mapping.as_str());
-        println!("{}", out);
+        let translated = translate_to_output(
+            &mapping,
+            output_prefix,
+            &plan_output,
+            &options,
+        );
+        if let Err(failure) = translated {
+            error!("Errored while translating from stdin");
+            pretty_print_err(&failure);
+            process::exit(1);
+        }
     }
 
     Ok(())
 }
+
+/// Builds the output of `plan_count` plans from the `--output` and
+/// `--format` arguments, using the `default_location` if no output is
+/// given.
+///
+/// Plans written to stdout are JSON unless formats are given, plans
+/// written to files are also written as dot graphs unless `json_only`.
+fn build_plan_output(
+    matches: &ArgMatches,
+    default_location: Option<OutputLocation>,
+    json_only: bool,
+    plan_count: usize,
+) -> Result<PlanOutput, PlanError> {
+    let location = matches
+        .get_one::<OutputLocation>("output")
+        .cloned()
+        .or(default_location);
+    let formats: Vec<PlanFormat> = matches
+        .get_many::<String>("format")
+        .map_or(Vec::new(), |formats| {
+            formats.filter_map(|format| format.parse().ok()).collect()
+        });
+
+    let formats = match (&location, formats.is_empty()) {
+        (_, false) => formats,
+        (Some(OutputLocation::StdOut), true)
+        | (Some(OutputLocation::File(_)), true) => vec![PlanFormat::Json],
+        _ => PlanOutput::next_to_input(json_only).formats,
+    };
+    let plan_output = PlanOutput { location, formats };
+    plan_output
+        .validate(plan_count)
+        .map_err(PlanError::GenericError)?;
+
+    Ok(plan_output)
+}
+
+/// Parses the value of the argument with the given `id`, which is already
+/// validated by the value parser of the argument.
+fn parse_arg<T: FromStr>(matches: &ArgMatches, id: &str) -> Option<T> {
+    matches
+        .get_one::<String>(id)
+        .and_then(|value| value.parse().ok())
+}
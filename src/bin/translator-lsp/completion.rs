@@ -0,0 +1,170 @@
+//!
+//! Completion of the vocabulary terms used in mapping documents, e.g.
+//! `rml:logicalSource` after typing `rml:log`.
+//!
+use std::collections::HashSet;
+
+use lsp_types::{CompletionItem, CompletionItemKind};
+use vocab::*;
+
+use crate::document::TextDocument;
+use crate::turtle::{is_name_char, TurtleDocument};
+
+/// Conventional prefix, terms and completion kind of the vocabularies.
+///
+/// The prefix is only used if the document does not declare the prefix
+/// itself, otherwise the terms are matched by the declared namespace.
+const VOCABULARIES: &[(&str, &[PAIR], CompletionItemKind)] = &[
+    (comp::PREFIX, comp::CLASS::TERMS, CompletionItemKind::CLASS),
+    (csvw::PREFIX, csvw::CLASS::TERMS, CompletionItemKind::CLASS),
+    (csvw::PREFIX, csvw::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (d2rq::PREFIX, d2rq::CLASS::TERMS, CompletionItemKind::CLASS),
+    (d2rq::PREFIX, d2rq::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (fnml::PREFIX, fnml::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (fno::PREFIX, fno::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (formats::PREFIX, formats::CLASS::TERMS, CompletionItemKind::CLASS),
+    (ldes::PREFIX, ldes::CLASS::TERMS, CompletionItemKind::CLASS),
+    (ldes::PREFIX, ldes::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (owl::PREFIX, owl::CLASS::TERMS, CompletionItemKind::CLASS),
+    (owl::PREFIX, owl::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (query::PREFIX, query::CLASS::TERMS, CompletionItemKind::CLASS),
+    (r2rml::PREFIX, r2rml::CLASS::TERMS, CompletionItemKind::CLASS),
+    (r2rml::PREFIX, r2rml::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (rdf::PREFIX, rdf::CLASS::TERMS, CompletionItemKind::CLASS),
+    (rdf::PREFIX, rdf::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (rml::PREFIX, rml::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (rml_cc::PREFIX, rml_cc::CLASS::TERMS, CompletionItemKind::CLASS),
+    (rml_cc::PREFIX, rml_cc::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (rml_core::PREFIX, rml_core::CLASS::TERMS, CompletionItemKind::CLASS),
+    (
+        rml_core::PREFIX,
+        rml_core::PROPERTY::TERMS,
+        CompletionItemKind::PROPERTY,
+    ),
+    (rml_fnml::PREFIX, rml_fnml::CLASS::TERMS, CompletionItemKind::CLASS),
+    (
+        rml_fnml::PREFIX,
+        rml_fnml::PROPERTY::TERMS,
+        CompletionItemKind::PROPERTY,
+    ),
+    (rml_io::PREFIX, rml_io::CLASS::TERMS, CompletionItemKind::CLASS),
+    (rml_io::PREFIX, rml_io::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (rml_lv::PREFIX, rml_lv::CLASS::TERMS, CompletionItemKind::CLASS),
+    (rml_lv::PREFIX, rml_lv::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    ("rmls", rmls::CLASS::TERMS, CompletionItemKind::CLASS),
+    ("rmls", rmls::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (rmlt::PREFIX, rmlt::CLASS::TERMS, CompletionItemKind::CLASS),
+    (rmlt::PREFIX, rmlt::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (tree::PREFIX, tree::CLASS::TERMS, CompletionItemKind::CLASS),
+    (tree::PREFIX, tree::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (void::PREFIX, void::CLASS::TERMS, CompletionItemKind::CLASS),
+    (void::PREFIX, void::PROPERTY::TERMS, CompletionItemKind::PROPERTY),
+    (xsd::PREFIX, xsd::TYPE::TERMS, CompletionItemKind::CLASS),
+];
+
+/// Vocabulary terms completing the prefixed name which ends at the
+/// character `offset` of the document.
+pub fn completions(
+    document: &TextDocument,
+    offset: usize,
+) -> Vec<CompletionItem> {
+    let chars = document.chars();
+    let offset = offset.min(chars.len());
+    let start = chars[..offset]
+        .iter()
+        .rposition(|&ch| !is_name_char(ch))
+        .map_or(0, |idx| idx + 1);
+    let typed: String = chars[start..offset].iter().collect();
+    let Some((prefix, partial)) = typed.split_once(':') else {
+        return Vec::new();
+    };
+
+    // ShExML declares its prefixes like SPARQL, which the Turtle scanner
+    // recognizes as well
+    let turtle = TurtleDocument::scan(chars);
+    let namespace = turtle.prefixes.get(prefix);
+
+    let mut seen = HashSet::new();
+    VOCABULARIES
+        .iter()
+        .filter(|(vocab_prefix, _, _)| {
+            namespace.is_some() || *vocab_prefix == prefix
+        })
+        .flat_map(|(_, terms, kind)| {
+            terms.iter().map(move |term| (term, kind))
+        })
+        .filter(|((iri, local), _)| {
+            namespace.is_none_or(|namespace| namespace == iri)
+                && local.starts_with(partial)
+        })
+        .filter(|(term, _)| seen.insert(**term))
+        .map(|((iri, local), kind)| {
+            CompletionItem {
+                label: local.to_string(),
+                kind: Some(*kind),
+                detail: Some(format!("{}{}", iri, local)),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+
+    use super::*;
+
+    fn complete(text: &str) -> Vec<CompletionItem> {
+        let uri = Url::parse("file:///mapping.ttl").unwrap();
+        let document = TextDocument::new(&uri, text.to_string());
+        completions(&document, document.chars().len())
+    }
+
+    #[test]
+    fn declared_prefix_test() {
+        let items = complete(
+            "@prefix rml: <http://w3id.org/rml/> .\n<#Mapping> rml:logi",
+        );
+
+        assert!(items.iter().any(|item| item.label == "logicalSource"));
+        assert!(items.iter().all(|item| {
+            item.label.starts_with("logi")
+                && item
+                    .detail
+                    .as_ref()
+                    .is_some_and(|iri| iri.starts_with("http://w3id.org/rml/"))
+        }));
+    }
+
+    #[test]
+    fn declared_namespace_test() {
+        // The rml prefix of RML v1.1.2 completes the terms of its namespace
+        let items = complete(
+            "@prefix rml: <http://semweb.mmlab.be/ns/rml#> .\n<#Mapping> rml:",
+        );
+
+        assert!(items.iter().any(|item| item.label == "logicalSource"));
+        assert!(items.iter().all(|item| {
+            item.detail.as_ref().is_some_and(|iri| {
+                iri.starts_with("http://semweb.mmlab.be/ns/rml#")
+            })
+        }));
+    }
+
+    #[test]
+    fn conventional_prefix_test() {
+        let items = complete("<#Mapping> rr:parent");
+
+        assert!(items.iter().any(|item| {
+            item.label == "parentTriplesMap"
+                && item.kind == Some(CompletionItemKind::PROPERTY)
+        }));
+    }
+
+    #[test]
+    fn no_prefixed_name_test() {
+        assert!(complete("<#Mapping> parent").is_empty());
+        assert!(complete("").is_empty());
+    }
+}
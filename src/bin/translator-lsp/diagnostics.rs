@@ -0,0 +1,107 @@
+//!
+//! Diagnostics of the errors of the extractors and translators.
+//!
+use std::panic;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use ltranslator::api::{
+    panic_failure, translate, LanguageFailure, TranslationOptions,
+};
+
+use crate::document::TextDocument;
+
+/// Translates the document and returns a diagnostic for the error of every
+/// mapping language the document failed to translate with.
+pub fn diagnostics(document: &TextDocument) -> Vec<Diagnostic> {
+    let options = TranslationOptions {
+        language: document.language,
+        ..Default::default()
+    };
+
+    // A panicking translator is reported like a failed translation
+    let translated =
+        panic::catch_unwind(|| translate(&document.text, &options))
+            .unwrap_or_else(|panic| Err(panic_failure(panic).into()));
+
+    match translated {
+        Ok(_) => Vec::new(),
+        Err(failure) => {
+            failure
+                .errors
+                .iter()
+                .map(|error| diagnostic(document, error))
+                .collect()
+        }
+    }
+}
+
+fn diagnostic(document: &TextDocument, error: &LanguageFailure) -> Diagnostic {
+    // Errors without a location are shown on the first line
+    let range = match error.location {
+        Some(location) => {
+            let line = location.line.saturating_sub(1) as u32;
+            let start =
+                Position::new(line, location.column.saturating_sub(1) as u32);
+            Range::new(start, document.line_range(location.line).end)
+        }
+        None => document.line_range(1),
+    };
+
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("translator".to_string()),
+        message: format!("{}: {}", error.language, error.messages.join(": ")),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+
+    use super::*;
+
+    fn document(uri: &str, text: &str) -> TextDocument {
+        TextDocument::new(&Url::parse(uri).unwrap(), text.to_string())
+    }
+
+    #[test]
+    fn shexml_error_test() {
+        let text = "PREFIX : <http://example.com/>\nSOURCE films <films.csv\n";
+        let diagnostics =
+            diagnostics(&document("file:///mapping.shexml", text));
+
+        // The unclosed IRI is reported at the end of the input
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.range.start, Position::new(2, 0));
+        assert_eq!(diagnostic.range.end, Position::new(2, 0));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+        assert!(diagnostic.message.starts_with("ShExML: "));
+    }
+
+    #[test]
+    fn undetected_language_test() {
+        let diagnostics =
+            diagnostics(&document("file:///mapping.ttl", "not a mapping"));
+
+        // Every language reports its error on the first line
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.range.start == Position::new(0, 0)));
+    }
+
+    #[test]
+    fn translated_test() {
+        let text = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/translator/resources/test/rml-core-tests/RMLTC0009a-JSON.ttl"
+        ))
+        .unwrap();
+
+        assert!(diagnostics(&document("file:///mapping.ttl", &text))
+            .is_empty());
+    }
+}
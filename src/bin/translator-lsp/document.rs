@@ -0,0 +1,141 @@
+use lsp_types::{Position, Range, Url};
+use translator::detect::MappingLanguage;
+
+/// Mapping document opened in the editor.
+///
+/// Offsets in the document count characters, like the spans of the ShExML
+/// lexer, while the positions of the protocol count UTF-16 code units.
+#[derive(Debug, Clone)]
+pub struct TextDocument {
+    pub text:     String,
+    /// Mapping language derived from the file extension, the language of
+    /// Turtle documents is detected since they can be any RML version
+    pub language: Option<MappingLanguage>,
+    chars:        Vec<char>,
+}
+
+impl TextDocument {
+    pub fn new(uri: &Url, text: String) -> Self {
+        let language = match uri.path().rsplit('.').next() {
+            Some("shexml") => Some(MappingLanguage::ShExML),
+            _ => None,
+        };
+
+        TextDocument {
+            chars: text.chars().collect(),
+            text,
+            language,
+        }
+    }
+
+    pub fn is_shexml(&self) -> bool {
+        self.language == Some(MappingLanguage::ShExML)
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    /// Character offset of the `position`, clamped to the end of its line.
+    pub fn offset(&self, position: Position) -> usize {
+        let mut line = 0;
+        let mut column = 0;
+        for (offset, ch) in self.chars.iter().enumerate() {
+            if line == position.line && column >= position.character {
+                return offset;
+            }
+            if *ch == '\n' {
+                if line == position.line {
+                    return offset;
+                }
+                line += 1;
+                column = 0;
+            } else if line == position.line {
+                column += ch.len_utf16() as u32;
+            }
+        }
+
+        self.chars.len()
+    }
+
+    /// Position of the character `offset`.
+    pub fn position(&self, offset: usize) -> Position {
+        let mut position = Position::new(0, 0);
+        for ch in self.chars.iter().take(offset) {
+            if *ch == '\n' {
+                position.line += 1;
+                position.character = 0;
+            } else {
+                position.character += ch.len_utf16() as u32;
+            }
+        }
+
+        position
+    }
+
+    pub fn range(&self, span: &std::ops::Range<usize>) -> Range {
+        Range::new(self.position(span.start), self.position(span.end))
+    }
+
+    /// Range of the line with the 1-based `line` number.
+    pub fn line_range(&self, line: usize) -> Range {
+        let line = line.saturating_sub(1) as u32;
+        let length = self
+            .text
+            .lines()
+            .nth(line as usize)
+            .map_or(0, |content| content.encode_utf16().count());
+
+        Range::new(Position::new(line, 0), Position::new(line, length as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(uri: &str, text: &str) -> TextDocument {
+        TextDocument::new(&Url::parse(uri).unwrap(), text.to_string())
+    }
+
+    #[test]
+    fn language_test() {
+        let text = "";
+        assert!(document("file:///mapping.shexml", text).is_shexml());
+        assert!(!document("file:///mapping.ttl", text).is_shexml());
+        assert_eq!(document("file:///mapping.ttl", text).language, None);
+    }
+
+    #[test]
+    fn offset_and_position_test() {
+        // The emoji is one character but two UTF-16 code units
+        let document = document("file:///mapping.ttl", "ab\n😀x\ny");
+
+        assert_eq!(document.offset(Position::new(1, 2)), 4);
+        assert_eq!(document.position(4), Position::new(1, 2));
+        assert_eq!(document.offset(Position::new(2, 0)), 6);
+        assert_eq!(document.position(6), Position::new(2, 0));
+
+        // Positions past the end of a line or the document are clamped
+        assert_eq!(document.offset(Position::new(0, 10)), 2);
+        assert_eq!(document.offset(Position::new(5, 0)), 7);
+    }
+
+    #[test]
+    fn range_test() {
+        let document = document("file:///mapping.ttl", "ab\n😀x\ny");
+
+        assert_eq!(
+            document.range(&(3..5)),
+            Range::new(Position::new(1, 0), Position::new(1, 3))
+        );
+        assert_eq!(
+            document.line_range(2),
+            Range::new(Position::new(1, 0), Position::new(1, 3))
+        );
+        assert_eq!(
+            document.line_range(9),
+            Range::new(Position::new(8, 0), Position::new(8, 0))
+        );
+    }
+}
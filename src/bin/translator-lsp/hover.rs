@@ -0,0 +1,120 @@
+//!
+//! Hover of the triples maps in RML documents, which lists the operators
+//! of the mapping plan the triples map is translated into.
+//!
+use std::panic;
+
+use log::error;
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind};
+use operator::display::PrettyDisplay;
+use translator::detect;
+use translator::options::TranslationOptions;
+
+use crate::document::TextDocument;
+use crate::turtle::TurtleDocument;
+
+/// Operators of the triples map at the character `offset`, or [None] if
+/// there is no triples map at the offset.
+pub fn hover(document: &TextDocument, offset: usize) -> Option<Hover> {
+    if document.is_shexml() {
+        return None;
+    }
+
+    let turtle = TurtleDocument::scan(document.chars());
+    let (token, span) = turtle.token_at(offset)?;
+    let term = turtle.resolve(token)?;
+
+    let parsed = detect::detect(&document.text)?.parsed;
+
+    // Identifiers of IRIs are either bare or enclosed in angle brackets
+    let options = TranslationOptions::default();
+    let translated = panic::catch_unwind(|| {
+        [term.clone(), format!("<{}>", term)]
+            .iter()
+            .find_map(|identifier| {
+                detect::translate_triples_map(
+                    &document.text,
+                    &parsed,
+                    identifier,
+                    &options,
+                )
+            })
+    });
+    let plan = match translated {
+        Ok(plan) => plan?.ok()?,
+        Err(_) => {
+            error!("Panicked while translating the triples map {}", term);
+            return None;
+        }
+    };
+
+    let graph = plan.graph.borrow();
+    let mut markdown = format!("**{}** translates into:\n\n", term);
+    for node in graph.node_weights() {
+        let operator_string = node.operator.pretty_string().ok()?;
+        let title = operator_string.lines().next().unwrap_or_default();
+        markdown += &format!("- `{}`: {}\n", node.id, title);
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind:  MarkupKind::Markdown,
+            value: markdown,
+        }),
+        range:    Some(document.range(span)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+
+    use super::*;
+
+    fn hover_markdown(file: &str, subject: &str) -> Option<String> {
+        let path = format!(
+            "{}/translator/resources/test/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            file
+        );
+        let text = std::fs::read_to_string(path).unwrap();
+        let byte = text.find(subject).unwrap();
+        let offset = text[..byte].chars().count() + 1;
+
+        let uri = Url::parse("file:///mapping.ttl").unwrap();
+        let hover = hover(&TextDocument::new(&uri, text), offset)?;
+        match hover.contents {
+            HoverContents::Markup(markup) => Some(markup.value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn rml_hover_test() {
+        let markdown =
+            hover_markdown("rml/multiple_tm_join.ttl", "<#AirportMapping>")
+                .unwrap();
+
+        assert!(markdown
+            .starts_with("**http://example.com/ns#AirportMapping**"));
+        assert!(markdown.contains("- `"));
+    }
+
+    #[test]
+    fn new_rml_hover_test() {
+        let markdown = hover_markdown(
+            "rml-core-tests/RMLTC0009a-JSON.ttl",
+            "<http://example.com/base/TriplesMap2>",
+        )
+        .unwrap();
+
+        assert!(markdown
+            .starts_with("**http://example.com/base/TriplesMap2**"));
+        assert!(markdown.contains("- `"));
+    }
+
+    #[test]
+    fn no_triples_map_hover_test() {
+        assert!(hover_markdown("rml/multiple_tm_join.ttl", "@base").is_none());
+    }
+}
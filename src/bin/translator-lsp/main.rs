@@ -0,0 +1,228 @@
+//!
+//! Language server for RML and ShExML mapping documents, which talks the
+//! language server protocol over stdin and stdout.
+//!
+//! It offers diagnostics of the translation errors, go-to-definition of
+//! triples maps, logical sources and ShExML identifiers, completion of the
+//! vocabulary terms and a hover with the operators of a triples map.
+//!
+mod completion;
+mod diagnostics;
+mod document;
+mod hover;
+mod shexml;
+mod turtle;
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use document::TextDocument;
+use log::{debug, info};
+use lsp_server::{
+    Connection, ErrorCode, Message, Notification, Request, Response,
+};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, GotoDefinition, HoverRequest, Request as _,
+};
+use lsp_types::{
+    CompletionOptions, CompletionResponse, GotoDefinitionResponse,
+    HoverProviderCapability, Location, OneOf, PublishDiagnosticsParams,
+    ServerCapabilities, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use ltranslator::logger::init_logger;
+use serde_json::Value;
+use turtle::TurtleDocument;
+
+pub fn main() -> Result<()> {
+    // The logs are written to stderr, stdout is used by the protocol
+    init_logger(false)?;
+
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::FULL,
+        )),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+    info!("Initialized the mapping language server");
+
+    // The connection is dropped by the server, which ends the IO threads
+    Server::default().run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Mapping documents opened in the editor.
+#[derive(Debug, Default)]
+struct Server {
+    documents: HashMap<Url, TextDocument>,
+}
+
+impl Server {
+    fn run(&mut self, connection: Connection) -> Result<()> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.respond(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    if let Some(uri) = self.update(notification) {
+                        self.publish_diagnostics(&connection, uri)?;
+                    }
+                }
+                Message::Response(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn respond(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let method = request.method.clone();
+        let supported = [
+            GotoDefinition::METHOD,
+            Completion::METHOD,
+            HoverRequest::METHOD,
+        ];
+        if !supported.contains(&method.as_str()) {
+            return Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request {}", method),
+            );
+        }
+
+        let result = self.position(request).and_then(|position| {
+            let Some((document, uri, offset)) = position else {
+                return Ok(Value::Null);
+            };
+            let result = match method.as_str() {
+                GotoDefinition::METHOD => {
+                    let span = if document.is_shexml() {
+                        shexml::definition(&document.text, offset)
+                    } else {
+                        TurtleDocument::scan(document.chars())
+                            .definition(offset)
+                    };
+                    serde_json::to_value(span.map(|span| {
+                        GotoDefinitionResponse::Scalar(Location::new(
+                            uri,
+                            document.range(&span),
+                        ))
+                    }))
+                }
+                Completion::METHOD => {
+                    let items = completion::completions(document, offset);
+                    serde_json::to_value(CompletionResponse::Array(items))
+                }
+                _ => serde_json::to_value(hover::hover(document, offset)),
+            };
+            Ok(result?)
+        });
+
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(err) => {
+                Response::new_err(
+                    id,
+                    ErrorCode::InvalidParams as i32,
+                    err.to_string(),
+                )
+            }
+        }
+    }
+
+    /// Opened document, its URI and the character offset of the position
+    /// a request is about, [None] if the document is not opened.
+    fn position(
+        &self,
+        request: Request,
+    ) -> Result<Option<(&TextDocument, Url, usize)>> {
+        let params: TextDocumentPositionParams =
+            serde_json::from_value(request.params)?;
+        let uri = params.text_document.uri;
+        Ok(self.documents.get(&uri).map(|document| {
+            let offset = document.offset(params.position);
+            (document, uri, offset)
+        }))
+    }
+
+    /// Updates the opened documents and returns the document to diagnose
+    /// again.
+    fn update(&mut self, notification: Notification) -> Option<Url> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = extract::<DidOpenTextDocument>(notification)?;
+                let document = params.text_document;
+                self.open(document.uri, document.text)
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params =
+                    extract::<DidChangeTextDocument>(notification)?;
+                // The whole document is synced on every change
+                let text = params.content_changes.pop()?.text;
+                self.open(params.text_document.uri, text)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = extract::<DidCloseTextDocument>(notification)?;
+                self.documents.remove(&params.text_document.uri);
+                None
+            }
+            method => {
+                debug!("Ignored notification {}", method);
+                None
+            }
+        }
+    }
+
+    fn open(&mut self, uri: Url, text: String) -> Option<Url> {
+        let document = TextDocument::new(&uri, text);
+        self.documents.insert(uri.clone(), document);
+        Some(uri)
+    }
+
+    fn publish_diagnostics(
+        &self,
+        connection: &Connection,
+        uri: Url,
+    ) -> Result<()> {
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(());
+        };
+
+        let params = PublishDiagnosticsParams::new(
+            uri,
+            diagnostics::diagnostics(document),
+            None,
+        );
+        let notification =
+            Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        connection
+            .sender
+            .send(Message::Notification(notification))?;
+        Ok(())
+    }
+}
+
+fn extract<N: lsp_types::notification::Notification>(
+    notification: Notification,
+) -> Option<N::Params> {
+    notification.extract(N::METHOD).ok()
+}
@@ -0,0 +1,265 @@
+//!
+//! Definitions of the identifiers and shapes referenced in ShExML
+//! documents, e.g. the expression `films` of `[films.name]`.
+//!
+use translator::shexml::parcombi::{self, ShExMLToken, Span, SpannedToken};
+
+/// Span of the declaration of the identifier or shape at the character
+/// `offset` of the ShExML document.
+pub fn definition(text: &str, offset: usize) -> Option<Span> {
+    let tokens = parcombi::tokenize(text).ok()?;
+    let idx = tokens
+        .iter()
+        .position(|(_, span)| span.start <= offset && offset <= span.end)?;
+
+    match &tokens[idx].0 {
+        ShExMLToken::Ident(name) => ident_definition(&tokens, idx, name),
+        ShExMLToken::ShapeNode { prefix, local } => {
+            shape_definition(&tokens, prefix, local)
+        }
+        _ => None,
+    }
+}
+
+/// Whether the identifier at `idx` is declared by the token before it.
+fn is_declaration(tokens: &[SpannedToken], idx: usize) -> bool {
+    idx > 0
+        && matches!(
+            tokens[idx - 1].0,
+            ShExMLToken::Source
+                | ShExMLToken::Query
+                | ShExMLToken::Iterator
+                | ShExMLToken::Matcher
+                | ShExMLToken::Expression
+                | ShExMLToken::AutoIncrement
+                | ShExMLToken::Function
+                | ShExMLToken::Field
+                | ShExMLToken::PushField
+                | ShExMLToken::PopField
+        )
+}
+
+fn is_field(tokens: &[SpannedToken], idx: usize) -> bool {
+    matches!(
+        tokens[idx - 1].0,
+        ShExMLToken::Field | ShExMLToken::PushField | ShExMLToken::PopField
+    )
+}
+
+/// Indexes of the declarations of identifiers with the given `name`.
+fn declarations<'a>(
+    tokens: &'a [SpannedToken],
+    name: &'a str,
+) -> impl Iterator<Item = usize> + 'a {
+    (0..tokens.len()).filter(move |&idx| {
+        tokens[idx].0 == ShExMLToken::Ident(name.to_string())
+            && is_declaration(tokens, idx)
+    })
+}
+
+fn ident_definition(
+    tokens: &[SpannedToken],
+    idx: usize,
+    name: &str,
+) -> Option<Span> {
+    if is_declaration(tokens, idx) {
+        return Some(tokens[idx].1.clone());
+    }
+
+    // Identifiers after a dot are iterators or fields of the identifier
+    // before the dot, e.g. `films_xml_file.film_xml.name`
+    let qualifier = match idx.checked_sub(2).map(|start| &tokens[start..idx]) {
+        Some([(ShExMLToken::Ident(qualifier), _), (ShExMLToken::Dot, _)]) => {
+            Some(qualifier)
+        }
+        _ => None,
+    };
+
+    let found = match qualifier {
+        Some(qualifier) => {
+            declarations(tokens, qualifier)
+                .filter(|&decl| tokens[decl - 1].0 == ShExMLToken::Iterator)
+                .find_map(|iterator| field_of(tokens, iterator, name))
+                .or_else(|| {
+                    declarations(tokens, name).find(|&decl| {
+                        is_field(tokens, decl)
+                            || tokens[decl - 1].0 == ShExMLToken::Iterator
+                    })
+                })
+        }
+        None => {
+            declarations(tokens, name).find(|&decl| !is_field(tokens, decl))
+        }
+    };
+
+    found
+        .or_else(|| declarations(tokens, name).next())
+        .map(|decl| tokens[decl].1.clone())
+}
+
+/// Index of the declaration of the field or nested iterator with the given
+/// `name` in the body of the iterator declared at `iterator`.
+fn field_of(
+    tokens: &[SpannedToken],
+    iterator: usize,
+    name: &str,
+) -> Option<usize> {
+    let body_start = (iterator..tokens.len())
+        .find(|&idx| tokens[idx].0 == ShExMLToken::BrackStart)?;
+
+    let mut depth = 0;
+    for idx in body_start..tokens.len() {
+        match &tokens[idx].0 {
+            ShExMLToken::BrackStart => depth += 1,
+            ShExMLToken::BrackEnd => {
+                depth -= 1;
+                if depth == 0 {
+                    return None;
+                }
+            }
+            ShExMLToken::Ident(ident)
+                if ident == name && is_declaration(tokens, idx) =>
+            {
+                return Some(idx);
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Span of the shape with the given name, which is referenced with `@` or
+/// used as the name of a graph.
+fn shape_definition(
+    tokens: &[SpannedToken],
+    prefix: &str,
+    local: &str,
+) -> Option<Span> {
+    (0..tokens.len())
+        .find(|&idx| {
+            let is_shape = matches!(
+                &tokens[idx].0,
+                ShExMLToken::ShapeNode { prefix: p, local: l }
+                    if p == prefix && l == local
+            );
+            let is_reference =
+                idx > 0 && tokens[idx - 1].0 == ShExMLToken::AtSymb;
+            let is_graph = tokens
+                .get(idx + 1)
+                .is_some_and(|(token, _)| *token == ShExMLToken::SqBrackStart);
+
+            is_shape && !is_reference && !is_graph
+        })
+        .map(|idx| tokens[idx].1.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPING: &str = r#"PREFIX : <http://example.com/>
+PREFIX graphs: <http://example.com/graphs/>
+SOURCE films_xml_file <data/films.xml>
+SOURCE films_json_file <data/films.json>
+ITERATOR film_xml <xpath: //film> {
+    FIELD id <@id>
+    FIELD name <name>
+    FIELD year <year>
+    FIELD country <country>
+}
+ITERATOR film_json <jsonpath: $.films[*]> {
+    FIELD id <id>
+    FIELD name <name>
+    FIELD year <year>
+    FIELD country <country>
+}
+EXPRESSION films <films_xml_file.film_xml UNION films_json_file.film_json>
+EXPRESSION film_labels <films_xml_file.film_xml.name + "-" + films_xml_file.film_xml.year>
+
+graphs:Films [[
+    :Films :[films.id] {
+        :name [films.name] ;
+        :country @:Countries ;
+    }
+]]
+
+:Countries :[films.country] {
+    :name [films.country] ;
+}
+
+:Labels :[films.id] {
+    :label [film_labels] ;
+}
+"#;
+
+    fn offset_of(needle: &str, nth: usize) -> usize {
+        let byte = MAPPING.match_indices(needle).nth(nth).unwrap().0;
+        MAPPING[..byte].chars().count()
+    }
+
+    /// Start of the definition of the identifier at `offset` characters
+    /// into the `nth` occurrence of the `needle`.
+    fn definition_of(needle: &str, nth: usize, offset: usize) -> Option<usize> {
+        definition(MAPPING, offset_of(needle, nth) + offset)
+            .map(|span| span.start)
+    }
+
+    #[test]
+    fn expression_definition_test() {
+        assert_eq!(
+            definition_of("films.id", 0, 1),
+            Some(offset_of("films <", 0))
+        );
+        assert_eq!(
+            definition_of("film_labels]", 0, 1),
+            Some(offset_of("film_labels <", 0))
+        );
+    }
+
+    #[test]
+    fn source_and_iterator_definition_test() {
+        assert_eq!(
+            definition_of("films_xml_file.film_xml", 0, 1),
+            Some(offset_of("films_xml_file <", 0))
+        );
+        assert_eq!(
+            definition_of("film_xml UNION", 0, 1),
+            Some(offset_of("film_xml <", 0))
+        );
+    }
+
+    #[test]
+    fn field_definition_test() {
+        // The fields with the same name in both iterators are told apart by
+        // the iterator before the dot
+        let field = "film_xml.".len() + 1;
+        assert_eq!(
+            definition_of("film_xml.name", 0, field),
+            Some(offset_of("name <name>", 0))
+        );
+        assert_eq!(
+            definition_of("film_xml.year", 0, field),
+            Some(offset_of("year <year>", 0))
+        );
+    }
+
+    #[test]
+    fn shape_definition_test() {
+        assert_eq!(
+            definition_of("@:Countries", 0, 3),
+            Some(offset_of(":Countries :[", 0))
+        );
+        assert_eq!(definition_of("graphs:Films", 0, 1), None);
+    }
+
+    #[test]
+    fn declaration_definition_test() {
+        let declaration = offset_of("films <", 0);
+        assert_eq!(
+            definition(MAPPING, declaration + 1).map(|span| span.start),
+            Some(declaration)
+        );
+        assert_eq!(definition("not ShExML", 1), None);
+    }
+}
@@ -0,0 +1,417 @@
+//!
+//! Lenient scanner of the Turtle syntax of RML documents, which finds the
+//! terms in documents that cannot be parsed yet while they are edited.
+//!
+use std::collections::HashMap;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TurtleToken {
+    /// Content of an IRI between angle brackets
+    Iri(String),
+    PrefixedName {
+        prefix: String,
+        local:  String,
+    },
+    BlankNode(String),
+    Literal,
+    /// Directives, `a`, booleans, numbers and language tags
+    Keyword(String),
+    /// One of `.`, `;`, `,`, `[`, `]`, `(`, `)` or `^`
+    Punctuation(char),
+}
+
+pub type SpannedToken = (TurtleToken, Range<usize>);
+
+/// Scanned Turtle document with its prefixes, base IRI and the subjects of
+/// its statements.
+#[derive(Debug, Clone, Default)]
+pub struct TurtleDocument {
+    pub tokens:   Vec<SpannedToken>,
+    pub prefixes: HashMap<String, String>,
+    pub base_iri: Option<String>,
+    /// Resolved IRIs or blank node labels of the subjects of the
+    /// statements, with the span of their tokens
+    pub subjects: Vec<(String, Range<usize>)>,
+}
+
+impl TurtleDocument {
+    pub fn scan(chars: &[char]) -> Self {
+        let mut document = TurtleDocument {
+            tokens: tokenize(chars),
+            ..Default::default()
+        };
+        document.scan_statements();
+        document
+    }
+
+    fn scan_statements(&mut self) {
+        let mut expects_subject = true;
+        let mut depth = 0usize;
+        let mut idx = 0;
+
+        while let Some((token, span)) = self.tokens.get(idx) {
+            match token {
+                TurtleToken::Keyword(keyword)
+                    if depth == 0 && is_prefix_directive(keyword) =>
+                {
+                    if let Some((prefix, namespace)) =
+                        self.prefix_declaration(idx + 1)
+                    {
+                        self.prefixes.insert(prefix, namespace);
+                    }
+                    idx += 2;
+                    expects_subject = true;
+                }
+                TurtleToken::Keyword(keyword)
+                    if depth == 0 && is_base_directive(keyword) =>
+                {
+                    if let Some((TurtleToken::Iri(iri), _)) =
+                        self.tokens.get(idx + 1)
+                    {
+                        self.base_iri = Some(self.resolve_iri(iri));
+                    }
+                    idx += 1;
+                    expects_subject = true;
+                }
+                TurtleToken::Punctuation('.') if depth == 0 => {
+                    expects_subject = true;
+                }
+                TurtleToken::Punctuation('[' | '(') => {
+                    depth += 1;
+                    expects_subject = false;
+                }
+                TurtleToken::Punctuation(']' | ')') => {
+                    depth = depth.saturating_sub(1);
+                }
+                TurtleToken::Iri(_)
+                | TurtleToken::PrefixedName { .. }
+                | TurtleToken::BlankNode(_)
+                    if expects_subject && depth == 0 =>
+                {
+                    if let Some(subject) = self.resolve(token) {
+                        self.subjects.push((subject, span.clone()));
+                    }
+                    expects_subject = false;
+                }
+                _ => expects_subject = false,
+            }
+            idx += 1;
+        }
+    }
+
+    fn prefix_declaration(&self, idx: usize) -> Option<(String, String)> {
+        match (self.tokens.get(idx), self.tokens.get(idx + 1)) {
+            (
+                Some((TurtleToken::PrefixedName { prefix, local }, _)),
+                Some((TurtleToken::Iri(namespace), _)),
+            ) if local.is_empty() => {
+                Some((prefix.clone(), self.resolve_iri(namespace)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolves the IRI of a `token`, or the label of a blank node.
+    pub fn resolve(&self, token: &TurtleToken) -> Option<String> {
+        match token {
+            TurtleToken::Iri(iri) => Some(self.resolve_iri(iri)),
+            TurtleToken::PrefixedName { prefix, local } => {
+                let namespace = self.prefixes.get(prefix)?;
+                Some(format!("{}{}", namespace, local.replace('\\', "")))
+            }
+            TurtleToken::BlankNode(label) => Some(format!("_:{}", label)),
+            _ => None,
+        }
+    }
+
+    /// Resolves a relative `iri` against the base IRI of the document.
+    fn resolve_iri(&self, iri: &str) -> String {
+        let is_absolute = iri
+            .find(':')
+            .is_some_and(|colon| !iri[..colon].contains(['/', '#', '?']));
+        let Some(base_iri) = self.base_iri.as_deref().filter(|_| !is_absolute)
+        else {
+            return iri.to_string();
+        };
+
+        if iri.is_empty() || iri.starts_with('#') {
+            let without_fragment =
+                base_iri.split('#').next().unwrap_or(base_iri);
+            format!("{}{}", without_fragment, iri)
+        } else if iri.starts_with('/') {
+            let authority_end = base_iri
+                .find("://")
+                .and_then(|scheme_end| {
+                    base_iri[scheme_end + 3..]
+                        .find('/')
+                        .map(|path| scheme_end + 3 + path)
+                })
+                .unwrap_or(base_iri.len());
+            format!("{}{}", &base_iri[..authority_end], iri)
+        } else {
+            let directory_end = base_iri.rfind('/').map_or(0, |idx| idx + 1);
+            format!("{}{}", &base_iri[..directory_end], iri)
+        }
+    }
+
+    /// Token at the character `offset`, including the offset right after
+    /// the token.
+    pub fn token_at(&self, offset: usize) -> Option<&SpannedToken> {
+        self.tokens
+            .iter()
+            .find(|(_, span)| span.start <= offset && offset <= span.end)
+    }
+
+    /// Span of the subject of the statement which defines the term at the
+    /// character `offset`, e.g. the triples map of a
+    /// `rml:parentTriplesMap` or a logical source of a triples map.
+    pub fn definition(&self, offset: usize) -> Option<Range<usize>> {
+        let (token, _) = self.token_at(offset)?;
+        let term = self.resolve(token)?;
+
+        self.subjects
+            .iter()
+            .find(|(subject, _)| *subject == term)
+            .map(|(_, span)| span.clone())
+    }
+}
+
+/// Scans the Turtle document into tokens paired with the span of their
+/// characters.
+///
+/// Malformed input never fails the scan, the characters are skipped or
+/// split into tokens instead.
+pub fn tokenize(chars: &[char]) -> Vec<SpannedToken> {
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+
+    while let Some(&ch) = chars.get(idx) {
+        let start = idx;
+        let token = match ch {
+            _ if ch.is_whitespace() => {
+                idx += 1;
+                continue;
+            }
+            '#' => {
+                while chars.get(idx).is_some_and(|&ch| ch != '\n') {
+                    idx += 1;
+                }
+                continue;
+            }
+            '<' => {
+                let end = chars[idx + 1..]
+                    .iter()
+                    .position(|&ch| ch == '>' || ch.is_whitespace())
+                    .map(|len| idx + 1 + len)
+                    .filter(|&end| chars[end] == '>');
+                match end {
+                    Some(end) => {
+                        idx = end + 1;
+                        TurtleToken::Iri(chars[start + 1..end].iter().collect())
+                    }
+                    None => {
+                        idx += 1;
+                        TurtleToken::Punctuation('<')
+                    }
+                }
+            }
+            '"' | '\'' => {
+                idx = skip_string(chars, idx);
+                TurtleToken::Literal
+            }
+            '.' | ';' | ',' | '[' | ']' | '(' | ')' | '^' => {
+                idx += 1;
+                TurtleToken::Punctuation(ch)
+            }
+            _ => {
+                while chars.get(idx).is_some_and(|&ch| is_name_char(ch)) {
+                    // A dot at the end of a name ends the statement
+                    if chars[idx] == '.'
+                        && !chars.get(idx + 1).is_some_and(|&c| is_name_char(c))
+                    {
+                        break;
+                    }
+                    idx += 1;
+                }
+                if idx == start {
+                    idx += 1;
+                    continue;
+                }
+                name_token(chars[start..idx].iter().collect())
+            }
+        };
+
+        tokens.push((token, start..idx));
+    }
+
+    tokens
+}
+
+fn is_prefix_directive(keyword: &str) -> bool {
+    keyword == "@prefix" || keyword.eq_ignore_ascii_case("prefix")
+}
+
+fn is_base_directive(keyword: &str) -> bool {
+    keyword == "@base" || keyword.eq_ignore_ascii_case("base")
+}
+
+fn name_token(name: String) -> TurtleToken {
+    if let Some(label) = name.strip_prefix("_:") {
+        return TurtleToken::BlankNode(label.to_string());
+    }
+
+    match name.split_once(':') {
+        Some((prefix, local)) if !name.starts_with('@') => {
+            TurtleToken::PrefixedName {
+                prefix: prefix.to_string(),
+                local:  local.to_string(),
+            }
+        }
+        _ => TurtleToken::Keyword(name),
+    }
+}
+
+/// Whether the character can be part of a prefixed name, keyword or
+/// number.
+pub fn is_name_char(ch: char) -> bool {
+    !ch.is_whitespace() && !"<>\"'#;,()[]{}^".contains(ch)
+}
+
+/// Returns the index after the string literal starting at `start`.
+fn skip_string(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let is_long = chars.get(start + 1) == Some(&quote)
+        && chars.get(start + 2) == Some(&quote);
+    let mut idx = if is_long { start + 3 } else { start + 1 };
+
+    while let Some(&ch) = chars.get(idx) {
+        if ch == '\\' {
+            idx += 2;
+            continue;
+        }
+        if ch == quote {
+            if !is_long {
+                return idx + 1;
+            }
+            if chars.get(idx + 1) == Some(&quote)
+                && chars.get(idx + 2) == Some(&quote)
+            {
+                return idx + 3;
+            }
+        } else if ch == '\n' && !is_long {
+            // Unterminated strings end at the end of their line
+            return idx;
+        }
+        idx += 1;
+    }
+
+    chars.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPING: &str = r#"@prefix rr: <http://www.w3.org/ns/r2rml#> .
+@prefix rml: <http://semweb.mmlab.be/ns/rml#> .
+@base <http://example.com/base/> .
+<#AirportMapping> a rr:TriplesMap ;
+  rml:logicalSource _:airports ;
+  rr:predicateObjectMap [
+    rr:objectMap [ rr:parentTriplesMap <#PositionMapping> ]
+  ] .
+<#PositionMapping> a rr:TriplesMap ;
+  rml:logicalSource [ rml:source "Positions.csv" ] .
+_:airports rml:source "Airport.csv" .
+"#;
+
+    fn scan(text: &str) -> TurtleDocument {
+        TurtleDocument::scan(&text.chars().collect::<Vec<_>>())
+    }
+
+    fn offset_of(text: &str, needle: &str, nth: usize) -> usize {
+        let byte = text.match_indices(needle).nth(nth).unwrap().0;
+        text[..byte].chars().count()
+    }
+
+    #[test]
+    fn scan_subjects_test() {
+        let document = scan(MAPPING);
+        let subjects: Vec<_> = document
+            .subjects
+            .iter()
+            .map(|(subject, _)| subject.as_str())
+            .collect();
+
+        assert_eq!(subjects, [
+            "http://example.com/base/#AirportMapping",
+            "http://example.com/base/#PositionMapping",
+            "_:airports",
+        ]);
+        assert_eq!(
+            document.prefixes.get("rr").map(String::as_str),
+            Some("http://www.w3.org/ns/r2rml#")
+        );
+        assert_eq!(
+            document.base_iri.as_deref(),
+            Some("http://example.com/base/")
+        );
+    }
+
+    #[test]
+    fn resolve_test() {
+        let document = scan(MAPPING);
+        let resolve = |token: TurtleToken| document.resolve(&token);
+
+        assert_eq!(
+            resolve(TurtleToken::PrefixedName {
+                prefix: "rml".to_string(),
+                local:  "source".to_string(),
+            })
+            .as_deref(),
+            Some("http://semweb.mmlab.be/ns/rml#source")
+        );
+        assert_eq!(
+            resolve(TurtleToken::Iri("/absolute".to_string())).as_deref(),
+            Some("http://example.com/absolute")
+        );
+        assert_eq!(
+            resolve(TurtleToken::Iri("http://other.org/".to_string()))
+                .as_deref(),
+            Some("http://other.org/")
+        );
+        assert!(resolve(TurtleToken::PrefixedName {
+            prefix: "ex".to_string(),
+            local:  "undeclared".to_string(),
+        })
+        .is_none());
+    }
+
+    #[test]
+    fn definition_test() {
+        let document = scan(MAPPING);
+
+        // The parent triples map and the logical source of a triples map
+        let parent = offset_of(MAPPING, "<#PositionMapping>", 0) + 2;
+        assert_eq!(
+            document.definition(parent).map(|span| span.start),
+            Some(offset_of(MAPPING, "<#PositionMapping>", 1))
+        );
+        let source = offset_of(MAPPING, "_:airports", 0);
+        assert_eq!(
+            document.definition(source).map(|span| span.start),
+            Some(offset_of(MAPPING, "_:airports", 1))
+        );
+        assert!(document.definition(offset_of(MAPPING, "rr:", 2)).is_none());
+    }
+
+    #[test]
+    fn tokenize_malformed_test() {
+        let chars: Vec<_> = "<#Unclosed a \"string".chars().collect();
+        let tokens = tokenize(&chars);
+
+        assert!(!tokens.is_empty());
+        assert!(tokens.iter().all(|(_, span)| span.end <= chars.len()));
+    }
+}
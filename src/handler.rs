@@ -5,8 +5,11 @@ use plan::error::PlanError;
 use plan::states::Init;
 use plan::Plan;
 use translator::error::{TranslationError, TranslationErrorKind};
+use translator::options::TranslationOptions;
 
 pub trait FileTranslatorHandler: Debug {
+    /// Name of the mapping language handled by the translator.
+    fn language(&self) -> String;
     fn supported_extension(&self) -> String;
     fn can_handle(&self, file_path: &dyn AsRef<str>) -> bool {
         let pbuf: PathBuf = file_path.as_ref().into();
@@ -22,11 +25,13 @@ pub trait FileTranslatorHandler: Debug {
     fn translate(
         &self,
         file_path: &dyn AsRef<str>,
+        options: &TranslationOptions,
     ) -> Result<Plan<Init>, TranslationError>;
 
     fn handle_file(
         &self,
         file_path: &dyn AsRef<str>,
+        options: &TranslationOptions,
     ) -> Result<Plan<Init>, TranslationError> {
         if !self.can_handle(file_path) {
             return Err(TranslationError {
@@ -36,12 +41,18 @@ pub trait FileTranslatorHandler: Debug {
                 },
             });
         }
-        self.translate(file_path)
+        self.translate(file_path, options)
     }
 }
 
 pub trait StringTranslatorHandler: Debug {
-    fn translate(&self, mapping: &str) -> Result<Plan<Init>, TranslationError>;
+    /// Name of the mapping language handled by the translator.
+    fn language(&self) -> String;
+    fn translate(
+        &self,
+        mapping: &str,
+        options: &TranslationOptions,
+    ) -> Result<Plan<Init>, TranslationError>;
 }
 
 pub trait TranslatorHandler:
@@ -1,8 +1,17 @@
 use jni::JNIEnv;
-use jni::objects::{JClass, JString};
-use crate::api::process_one_str;
+use jni::objects::{
+    JClass, JObject, JObjectArray, JString, JThrowable, JValue,
+};
+use crate::api::{
+    translate, LanguageFailure, TranslationFailure, TranslationOptions,
+};
 use catch_panic::catch_panic;
 
+const TRANSLATION_EXCEPTION: &str =
+    "be/ugent/idlab/knows/mappingLoom/TranslationException";
+const LANGUAGE_FAILURE: &str =
+    "be/ugent/idlab/knows/mappingLoom/LanguageFailure";
+
 #[no_mangle]
 #[catch_panic]
 pub extern "system" fn Java_be_ugent_idlab_knows_mappingLoom_Translator_translate<'local>(
@@ -15,10 +24,146 @@ pub extern "system" fn Java_be_ugent_idlab_knows_mappingLoom_Translator_translat
         .expect("Failed to retrieve mapping as string from Java")
         .into();
 
-    let translated = process_one_str(mapping.clone().as_str());
+    translate_to_java(&mut env, &mapping, &TranslationOptions::default())
+}
+
+#[no_mangle]
+#[catch_panic]
+pub extern "system" fn Java_be_ugent_idlab_knows_mappingLoom_Translator_translateWithOptions<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    input: JString<'local>,
+    options: JObjectArray<'local>,
+) -> JString<'local> {
+    let mapping: String = env
+        .get_string(&input)
+        .expect("Failed to retrieve mapping as string from Java")
+        .into();
+
+    match translation_options(&mut env, &options) {
+        Ok(options) => translate_to_java(&mut env, &mapping, &options),
+        Err(msg) => throw_translation_exception(&mut env, msg),
+    }
+}
+
+/// Parses the translation options given as an array of alternating keys
+/// and values.
+fn translation_options(
+    env: &mut JNIEnv<'_>,
+    key_values: &JObjectArray<'_>,
+) -> Result<TranslationOptions, String> {
+    let length = env
+        .get_array_length(key_values)
+        .expect("Failed to retrieve length of options array from Java");
+    let key_values = (0..length)
+        .map(|idx| {
+            let element = env
+                .get_object_array_element(key_values, idx)
+                .expect("Failed to retrieve option from Java");
+            env.get_string(&JString::from(element))
+                .expect("Failed to retrieve option as string from Java")
+                .into()
+        })
+        .collect::<Vec<String>>();
+
+    let mut options = TranslationOptions::default();
+    for pair in key_values.chunks(2) {
+        match pair {
+            [key, value] => options.set(key, value)?,
+            _ => {
+                return Err(format!(
+                    "translation option {} has no value",
+                    pair[0]
+                ))
+            }
+        }
+    }
+    Ok(options)
+}
+
+fn translate_to_java<'local>(
+    env: &mut JNIEnv<'local>,
+    mapping: &str,
+    options: &TranslationOptions,
+) -> JString<'local> {
+    match translate(mapping, options) {
+        Ok(output) => {
+            env.new_string(output.plan)
+                .expect("Couldn't create translated mapping as Java string!")
+        }
+        Err(failure) => throw_translation_failure(env, &failure),
+    }
+}
 
-    let output = env
-        .new_string(translated)
-        .expect("Couldn't create translated mapping as Java string!");
-    output
+/// Throws a `TranslationException` with the failures of every attempted
+/// mapping language as `LanguageFailure` objects.
+fn throw_translation_failure<'local>(
+    env: &mut JNIEnv<'local>,
+    failure: &TranslationFailure,
+) -> JString<'local> {
+    let message = env
+        .new_string(failure.to_string())
+        .expect("Couldn't create translation failure as Java string!");
+    let failures = language_failures(env, &failure.errors)
+        .expect("Couldn't create LanguageFailure objects for Java!");
+    let exception = env
+        .new_object(
+            TRANSLATION_EXCEPTION,
+            format!("(Ljava/lang/String;[L{};)V", LANGUAGE_FAILURE),
+            &[JValue::Object(&message), JValue::Object(&failures)],
+        )
+        .expect("Couldn't create TranslationException for Java!");
+    env.throw(JThrowable::from(exception))
+        .expect("Couldn't throw TranslationException to Java!");
+    JObject::null().into()
+}
+
+/// Converts the failures to an array of `LanguageFailure` objects, with 0
+/// as line and column if the location of the error is unknown.
+fn language_failures<'local>(
+    env: &mut JNIEnv<'local>,
+    failures: &[LanguageFailure],
+) -> jni::errors::Result<JObjectArray<'local>> {
+    let java_failures = env.new_object_array(
+        failures.len() as i32,
+        LANGUAGE_FAILURE,
+        JObject::null(),
+    )?;
+    for (idx, failure) in failures.iter().enumerate() {
+        let language = env.new_string(&failure.language)?;
+        let messages = env.new_object_array(
+            failure.messages.len() as i32,
+            "java/lang/String",
+            JObject::null(),
+        )?;
+        for (msg_idx, message) in failure.messages.iter().enumerate() {
+            let message = env.new_string(message)?;
+            env.set_object_array_element(&messages, msg_idx as i32, message)?;
+        }
+        let (line, column) = failure.location.map_or((0, 0), |location| {
+            (location.line as i32, location.column as i32)
+        });
+
+        let java_failure = env.new_object(
+            LANGUAGE_FAILURE,
+            "(Ljava/lang/String;II[Ljava/lang/String;)V",
+            &[
+                JValue::Object(&language),
+                JValue::Int(line),
+                JValue::Int(column),
+                JValue::Object(&messages),
+            ],
+        )?;
+        env.set_object_array_element(&java_failures, idx as i32, java_failure)?;
+    }
+    Ok(java_failures)
+}
+
+fn throw_translation_exception<'local>(
+    env: &mut JNIEnv<'local>,
+    msg: String,
+) -> JString<'local> {
+    env.throw_new(TRANSLATION_EXCEPTION, msg)
+        .expect("Couldn't throw TranslationException to Java!");
+    JObject::null().into()
 }
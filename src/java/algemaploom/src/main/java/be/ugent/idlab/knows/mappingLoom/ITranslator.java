@@ -1,14 +1,60 @@
 package be.ugent.idlab.knows.mappingLoom;
 
+import java.util.Map;
+
 public interface ITranslator {
 	/**
 	 * Translate a mapping into a MappingLoom document.
 	 *
 	 * @param document Mapping to translate in RML or ShExML format.
      * @return The MappingLoom plan
+     * @throws TranslationException If the mapping cannot be translated.
 	 */
 	String translate_to_document(String document);
 
+	/**
+	 * Translate a mapping of the given mapping language into a MappingLoom
+	 * document, instead of detecting the mapping language.
+	 *
+	 * @param document Mapping to translate.
+	 * @param language Mapping language of the mapping, one of "rml-v1",
+	 *                 "rml-v2" or "shexml".
+	 * @return The MappingLoom plan
+	 * @throws TranslationException If the mapping cannot be translated.
+	 */
+	String translate_to_document(String document, String language);
+
+	/**
+	 * Translate a mapping into a MappingLoom document with the given
+	 * translation options.
+	 *
+	 * @param document Mapping to translate.
+	 * @param options  Translation options by key, one of "sample-limit",
+	 *                 "language", "base-iri", "serialization", "target",
+	 *                 "compression", "named-graphs", "param" (as
+	 *                 "name=value") or "param-file".
+	 * @return The MappingLoom plan
+	 * @throws TranslationException If an option is invalid or the mapping
+	 *                              cannot be translated.
+	 */
+	String translate_to_document(String document, Map<String, String> options);
+
+	/**
+	 * Translate a mapping into a MappingLoom document with the given
+	 * translation options, resolving the ${name} placeholders in the
+	 * sources and targets of the mapping with the given parameters.
+	 *
+	 * @param document Mapping to translate.
+	 * @param options  Translation options by key, see
+	 *                 {@link #translate_to_document(String, Map)}.
+	 * @param params   Values of the placeholders by name.
+	 * @return The MappingLoom plan
+	 * @throws TranslationException If an option is invalid, a placeholder
+	 *                              has no parameter or the mapping cannot
+	 *                              be translated.
+	 */
+	String translate_to_document(String document, Map<String, String> options, Map<String, String> params);
+
 	/**
 	 * A default instance of the translator.
      *
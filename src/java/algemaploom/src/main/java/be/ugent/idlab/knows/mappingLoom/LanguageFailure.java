@@ -0,0 +1,59 @@
+package be.ugent.idlab.knows.mappingLoom;
+
+import java.util.List;
+import java.util.OptionalInt;
+
+/**
+ * Error of the translation of a mapping document with one mapping language.
+ */
+public class LanguageFailure {
+	private final String language;
+	private final int line;
+	private final int column;
+	private final List<String> messages;
+
+	/**
+	 * @param language The attempted mapping language
+	 * @param line     The line of the error in the mapping document starting
+	 *                 from 1, or 0 if it is unknown
+	 * @param column   The column of the error in the mapping document
+	 *                 starting from 1, or 0 if it is unknown
+	 * @param messages The messages of the error chain, starting with the
+	 *                 outermost error
+	 */
+	public LanguageFailure(String language, int line, int column, String[] messages) {
+		this.language = language;
+		this.line = line;
+		this.column = column;
+		this.messages = List.of(messages);
+	}
+
+	/**
+	 * @return The attempted mapping language
+	 */
+	public String getLanguage() {
+		return language;
+	}
+
+	/**
+	 * @return The line of the error in the mapping document, if it is known
+	 */
+	public OptionalInt getLine() {
+		return line > 0 ? OptionalInt.of(line) : OptionalInt.empty();
+	}
+
+	/**
+	 * @return The column of the error in the mapping document, if it is known
+	 */
+	public OptionalInt getColumn() {
+		return column > 0 ? OptionalInt.of(column) : OptionalInt.empty();
+	}
+
+	/**
+	 * @return The messages of the error chain, starting with the outermost
+	 *         error
+	 */
+	public List<String> getMessages() {
+		return messages;
+	}
+}
@@ -0,0 +1,35 @@
+package be.ugent.idlab.knows.mappingLoom;
+
+import java.util.List;
+
+/**
+ * Thrown when a mapping document cannot be translated with any of the
+ * supported mapping languages.
+ */
+public class TranslationException extends RuntimeException {
+	private final List<LanguageFailure> failures;
+
+	/**
+	 * @param message The errors of every attempted mapping language
+	 */
+	public TranslationException(String message) {
+		this(message, new LanguageFailure[0]);
+	}
+
+	/**
+	 * @param message  The errors of every attempted mapping language
+	 * @param failures The failures of every attempted mapping language
+	 */
+	public TranslationException(String message, LanguageFailure[] failures) {
+		super(message);
+		this.failures = List.of(failures);
+	}
+
+	/**
+	 * @return The failures of every attempted mapping language, which is
+	 *         empty if the translation options are invalid
+	 */
+	public List<LanguageFailure> getFailures() {
+		return failures;
+	}
+}
@@ -1,10 +1,14 @@
 package be.ugent.idlab.knows.mappingLoom;
 
 import java.io.IOException;
+import java.util.Map;
+import java.util.stream.Stream;
 
 class Translator implements ITranslator {
 	private static native String translate(String input);
 
+	private static native String translateWithOptions(String input, String[] options);
+
 	static {
         try {
 			String os = System.getProperty("os.name");
@@ -30,4 +34,25 @@ class Translator implements ITranslator {
 	public String translate_to_document(String document) {
 		return translate(document);
 	}
+
+	@Override
+	public String translate_to_document(String document, String language) {
+		return translate_to_document(document, Map.of("language", language));
+	}
+
+	@Override
+	public String translate_to_document(String document, Map<String, String> options) {
+		return translate_to_document(document, options, Map.of());
+	}
+
+	@Override
+	public String translate_to_document(String document, Map<String, String> options, Map<String, String> params) {
+		Stream<String> optionKeyValues = options.entrySet().stream()
+				.flatMap(option -> Stream.of(option.getKey(), option.getValue()));
+		Stream<String> paramKeyValues = params.entrySet().stream()
+				.flatMap(param -> Stream.of("param", param.getKey() + "=" + param.getValue()));
+		String[] keyValues = Stream.concat(optionKeyValues, paramKeyValues)
+				.toArray(String[]::new);
+		return translateWithOptions(document, keyValues);
+	}
 }
@@ -1,7 +1,13 @@
 package be.ugent.idlab.knows.mappingLoom;
 
+import static org.junit.Assert.assertEquals;
+import static org.junit.Assert.assertFalse;
+import static org.junit.Assert.assertThrows;
 import static org.junit.Assert.assertTrue;
 
+import java.util.Map;
+import java.util.OptionalInt;
+
 import org.junit.Test;
 
 /**
@@ -94,4 +100,63 @@ public class TranslatorTest
         assertTrue(out.contains("Serialize_3"));
         assertTrue(out.contains("Sink_4"));
     }
+
+    @Test
+    public void testTranslateInvalidMapping()
+    {
+        Translator translator = new Translator();
+        TranslationException exception = assertThrows(
+                TranslationException.class,
+                () -> translator.translate_to_document("PREFIX ex= <http://example.com/>"));
+
+        assertTrue(exception.getMessage().contains("ShExML at line 1, column 10"));
+
+        LanguageFailure shexml = exception.getFailures().stream()
+                .filter(failure -> failure.getLanguage().equals("ShExML"))
+                .findFirst()
+                .orElseThrow();
+        assertEquals(OptionalInt.of(1), shexml.getLine());
+        assertEquals(OptionalInt.of(10), shexml.getColumn());
+        assertFalse(shexml.getMessages().isEmpty());
+    }
+
+    @Test
+    public void testTranslateWithLanguage()
+    {
+        Translator translator = new Translator();
+        String out = translator.translate_to_document(SHEXML_EXAMPLE, "shexml");
+        assertTrue(out.contains("<http://example.com/name>"));
+
+        assertThrows(
+                TranslationException.class,
+                () -> translator.translate_to_document(SHEXML_EXAMPLE, "rml-v1"));
+    }
+
+    @Test
+    public void testTranslateWithOptions()
+    {
+        Translator translator = new Translator();
+        String out = translator.translate_to_document(RML_EXAMPLE, Map.of(
+                "serialization", "ntriples",
+                "target", "students.nt"));
+        assertTrue(out.contains("NTriples"));
+        assertTrue(out.contains("students.nt"));
+
+        assertThrows(
+                TranslationException.class,
+                () -> translator.translate_to_document(RML_EXAMPLE, Map.of("serialization", "xml")));
+    }
+
+    @Test
+    public void testTranslateWithParams()
+    {
+        Translator translator = new Translator();
+        String mapping = RML_EXAMPLE.replace("\"student.csv\"", "\"${data_dir}/student.csv\"");
+        String out = translator.translate_to_document(mapping, Map.of(), Map.of("data_dir", "/data"));
+        assertTrue(out.contains("/data/student.csv"));
+
+        assertThrows(
+                TranslationException.class,
+                () -> translator.translate_to_document(mapping, Map.of()));
+    }
 }
@@ -3,7 +3,11 @@ pub mod rml;
 pub mod shexml;
 pub mod logger;
 pub mod api;
+pub mod output;
+pub mod report;
+pub mod serve;
 pub mod util;
+pub mod watch;
 
 #[cfg(feature = "jni")]
 mod java;
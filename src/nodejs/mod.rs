@@ -1,11 +1,118 @@
 use neon::prelude::*;
-use crate::api::process_one_str;
+use crate::api::{
+    translate as translate_mapping, TranslationFailure, TranslationOptions,
+};
 
+/// Translates the mapping given as first argument, with the translation
+/// options given as optional second argument. The options are either an
+/// object of options by key, e.g. `{ "base-iri": "http://example.com/" }`,
+/// or only the mapping language ("rml-v1", "rml-v2" or "shexml") to use
+/// instead of detecting it. The values of the `${name}` placeholders in the
+/// sources and targets are given as an object under the `params` key.
+///
+/// Throws an error with the error of every attempted mapping language in
+/// its `failures` property, see [translation_error].
 fn translate(mut cx: FunctionContext) -> JsResult<JsString> {
     let mapping = cx.argument::<JsString>(0)?.value(&mut cx);
-    let translated = process_one_str(mapping.as_str());
+    let mut options = TranslationOptions::default();
+    if let Some(arg) = cx.argument_opt(1) {
+        if let Err(msg) = set_options(&mut cx, &mut options, arg)? {
+            return cx.throw_error(msg);
+        }
+    }
 
-    Ok(cx.string(translated))
+    match translate_mapping(mapping.as_str(), &options) {
+        Ok(output) => Ok(cx.string(output.plan)),
+        Err(failure) => {
+            let err = translation_error(&mut cx, &failure)?;
+            cx.throw(err)
+        }
+    }
+}
+
+/// Creates the error of the `failure`, with a `failures` array of objects
+/// with the `language`, the `location` (`{ line, column }` or `null` if
+/// unknown) and the `messages` of the error of every attempted language.
+fn translation_error<'a>(
+    cx: &mut FunctionContext<'a>,
+    failure: &TranslationFailure,
+) -> JsResult<'a, JsError> {
+    let err = cx.error(failure.to_string())?;
+    let failures = cx.empty_array();
+    for (idx, lang_failure) in failure.errors.iter().enumerate() {
+        let js_failure = cx.empty_object();
+        let language = cx.string(&lang_failure.language);
+        js_failure.set(cx, "language", language)?;
+
+        let location: Handle<JsValue> = match lang_failure.location {
+            Some(location) => {
+                let js_location = cx.empty_object();
+                let line = cx.number(location.line as f64);
+                js_location.set(cx, "line", line)?;
+                let column = cx.number(location.column as f64);
+                js_location.set(cx, "column", column)?;
+                js_location.upcast()
+            }
+            None => cx.null().upcast(),
+        };
+        js_failure.set(cx, "location", location)?;
+
+        let messages = cx.empty_array();
+        for (msg_idx, message) in lang_failure.messages.iter().enumerate() {
+            let message = cx.string(message);
+            messages.set(cx, msg_idx as u32, message)?;
+        }
+        js_failure.set(cx, "messages", messages)?;
+        failures.set(cx, idx as u32, js_failure)?;
+    }
+    err.set(cx, "failures", failures)?;
+    Ok(err)
+}
+
+fn set_options<'a>(
+    cx: &mut FunctionContext<'a>,
+    options: &mut TranslationOptions,
+    arg: Handle<'a, JsValue>,
+) -> NeonResult<Result<(), String>> {
+    if arg.is_a::<JsUndefined, _>(cx) {
+        return Ok(Ok(()));
+    }
+    if let Ok(language) = arg.downcast::<JsString, _>(cx) {
+        let language = language.value(cx);
+        return Ok(options.set("language", &language));
+    }
+
+    let object = arg.downcast_or_throw::<JsObject, _>(cx)?;
+    let keys = object.get_own_property_names(cx)?.to_vec(cx)?;
+    for key in keys {
+        let key = key.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+        let value: Handle<JsValue> = object.get(cx, key.as_str())?;
+        if key == "params" {
+            set_params(cx, options, value)?;
+            continue;
+        }
+        let value = value.to_string(cx)?.value(cx);
+        if let Err(msg) = options.set(&key, &value) {
+            return Ok(Err(msg));
+        }
+    }
+    Ok(Ok(()))
+}
+
+fn set_params<'a>(
+    cx: &mut FunctionContext<'a>,
+    options: &mut TranslationOptions,
+    params: Handle<'a, JsValue>,
+) -> NeonResult<()> {
+    let params = params.downcast_or_throw::<JsObject, _>(cx)?;
+    let names = params.get_own_property_names(cx)?.to_vec(cx)?;
+    for name in names {
+        let name = name.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+        let value: Handle<JsValue> = params.get(cx, name.as_str())?;
+        let value = value.to_string(cx)?.value(cx);
+        options.params.insert(name, value);
+    }
+    Ok(())
 }
 
 #[neon::main]
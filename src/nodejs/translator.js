@@ -2,4 +2,21 @@ const fs = require('fs')
 const translator = require('.')
 
 var mapping = fs.readFileSync(0);
-console.log(translator.translate(mapping.toString()))
+// Translation options are given as key=value arguments, parameters as
+// param=name=value arguments
+var options = { params: {} };
+process.argv.slice(2).forEach((arg) => {
+    const [key, ...value] = arg.split('=')
+    if (key === 'param') {
+        const [name, ...param] = value
+        options.params[name] = param.join('=')
+    } else {
+        options[key] = value.join('=')
+    }
+});
+try {
+    console.log(translator.translate(mapping.toString(), options))
+} catch (err) {
+    console.error(err.message)
+    process.exit(1)
+}
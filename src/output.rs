@@ -0,0 +1,368 @@
+//!
+//! Output of the translated mapping plans in the selected formats, either
+//! to files or to stdout.
+//!
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use colored::Colorize;
+use log::info;
+use plan::error::PlanError;
+use plan::states::Init;
+use plan::Plan;
+
+/// Formats in which a mapping plan can be written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlanFormat {
+    Json,
+    PrettyJson,
+    /// [Dot](https://graphviz.org/doc/info/lang.html) graph with the
+    /// operators serialized as JSON
+    Dot,
+    /// Dot graph with the operators in a human-readable form
+    PrettyDot,
+    /// [Mermaid](https://mermaid.js.org/) flowchart
+    Mermaid,
+}
+
+impl PlanFormat {
+    pub const VALUES: [&'static str; 5] =
+        ["json", "pretty-json", "dot", "pretty-dot", "mermaid"];
+
+    /// Suffix appended to the output prefix of the written file.
+    pub fn file_suffix(self) -> &'static str {
+        match self {
+            PlanFormat::Json => ".json",
+            PlanFormat::PrettyJson => "_pretty.json",
+            PlanFormat::Dot => ".dot",
+            PlanFormat::PrettyDot => "_pretty.dot",
+            PlanFormat::Mermaid => ".mmd",
+        }
+    }
+
+    /// Serializes the mapping plan in this format.
+    pub fn render(
+        self,
+        mapping_plan: &Plan<Init>,
+    ) -> Result<String, PlanError> {
+        let rendered = match self {
+            PlanFormat::Json => mapping_plan.to_json_string(),
+            PlanFormat::PrettyJson => mapping_plan.to_json_pretty_string(),
+            PlanFormat::Dot => Ok(mapping_plan.to_dot_string(false)),
+            PlanFormat::PrettyDot => Ok(mapping_plan.to_dot_string(true)),
+            PlanFormat::Mermaid => mapping_plan.to_mermaid_string(),
+        };
+
+        rendered.map_err(|err| PlanError::GenericError(format!("{:?}", err)))
+    }
+}
+
+impl Display for PlanFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            PlanFormat::Json => "json",
+            PlanFormat::PrettyJson => "pretty-json",
+            PlanFormat::Dot => "dot",
+            PlanFormat::PrettyDot => "pretty-dot",
+            PlanFormat::Mermaid => "mermaid",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for PlanFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(PlanFormat::Json),
+            "pretty-json" => Ok(PlanFormat::PrettyJson),
+            "dot" => Ok(PlanFormat::Dot),
+            "pretty-dot" => Ok(PlanFormat::PrettyDot),
+            "mermaid" => Ok(PlanFormat::Mermaid),
+            _ => {
+                Err(format!(
+                    "unknown plan format {}, expected one of: {}",
+                    s,
+                    PlanFormat::VALUES.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+/// Location to which the mapping plans are written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OutputLocation {
+    StdOut,
+    /// Directory in which the files of every plan are written, named after
+    /// the translated mapping document
+    Directory(PathBuf),
+    /// File to which the plan is written, only possible for one plan in
+    /// one format
+    File(PathBuf),
+}
+
+impl FromStr for OutputLocation {
+    type Err = String;
+
+    /// Parses `-` as [OutputLocation::StdOut], an existing directory or a
+    /// path ending with `/` as [OutputLocation::Directory] and anything else
+    /// as [OutputLocation::File].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("output cannot be empty".to_string()),
+            "-" => Ok(OutputLocation::StdOut),
+            path if path.ends_with('/') || Path::new(path).is_dir() => {
+                Ok(OutputLocation::Directory(path.into()))
+            }
+            path => Ok(OutputLocation::File(path.into())),
+        }
+    }
+}
+
+impl OutputLocation {
+    /// Writes the `content` of a document to this location, named after
+    /// the file name of the `document_path` in a directory. Returns the
+    /// path of the written file, or `-` for stdout.
+    pub fn write_document(
+        &self,
+        document_path: &Path,
+        content: &str,
+    ) -> io::Result<String> {
+        let path = match self {
+            OutputLocation::StdOut => {
+                print!("{}", content);
+                return Ok("-".to_string());
+            }
+            OutputLocation::File(path) => path.clone(),
+            OutputLocation::Directory(dir) => {
+                fs::create_dir_all(dir)?;
+                dir.join(document_path.file_name().unwrap_or_default())
+            }
+        };
+
+        fs::write(&path, content)?;
+        Ok(path.to_string_lossy().to_string())
+    }
+}
+
+/// Output of the translated mapping plans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanOutput {
+    /// The plans are written next to the mapping documents if [None]
+    pub location: Option<OutputLocation>,
+    pub formats:  Vec<PlanFormat>,
+}
+
+impl PlanOutput {
+    /// Output of the plans next to the mapping documents in JSON, and in
+    /// the dot formats unless `json_only`.
+    pub fn next_to_input(json_only: bool) -> Self {
+        let formats = if json_only {
+            vec![PlanFormat::Json]
+        } else {
+            vec![PlanFormat::Dot, PlanFormat::PrettyDot, PlanFormat::Json]
+        };
+
+        PlanOutput {
+            location: None,
+            formats,
+        }
+    }
+
+    /// Output of the plans in the `subdirectory` of the output directory,
+    /// any other output is unchanged.
+    pub fn in_subdirectory(&self, subdirectory: &Path) -> Self {
+        let location = match &self.location {
+            Some(OutputLocation::Directory(dir)) => {
+                Some(OutputLocation::Directory(dir.join(subdirectory)))
+            }
+            location => location.clone(),
+        };
+
+        PlanOutput {
+            location,
+            formats: self.formats.clone(),
+        }
+    }
+
+    /// Checks if the output can be used for `plan_count` plans.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if several plans or formats would be
+    /// written to the same file, or several plans to stdout where they
+    /// could not be told apart.
+    pub fn validate(&self, plan_count: usize) -> Result<(), String> {
+        match &self.location {
+            Some(OutputLocation::File(path)) if plan_count > 1 => {
+                Err(format!(
+                    "cannot write several plans to the file {}, use a \
+                     directory as output instead",
+                    path.to_string_lossy()
+                ))
+            }
+            Some(OutputLocation::File(path)) if self.formats.len() > 1 => {
+                Err(format!(
+                    "cannot write several formats to the file {}, use a \
+                     directory or - as output instead",
+                    path.to_string_lossy()
+                ))
+            }
+            Some(OutputLocation::StdOut) if plan_count > 1 => {
+                Err("cannot write several plans to stdout, use a directory \
+                     as output instead"
+                    .to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes the `mapping_plan` in every format and returns the paths of
+    /// the written files, or `-` for stdout.
+    ///
+    /// The files are written with the given `output_prefix` if there is no
+    /// output location, or named after it in the output directory.
+    pub fn write(
+        &self,
+        mapping_plan: &Plan<Init>,
+        output_prefix: &str,
+    ) -> Result<Vec<String>, PlanError> {
+        let mut outputs = Vec::new();
+
+        for format in &self.formats {
+            let rendered = format.render(mapping_plan)?;
+            let path = match &self.location {
+                Some(OutputLocation::StdOut) => {
+                    println!("{}", rendered);
+                    outputs.push("-".to_string());
+                    continue;
+                }
+                Some(OutputLocation::File(path)) => path.clone(),
+                Some(OutputLocation::Directory(dir)) => {
+                    let file_name = Path::new(output_prefix)
+                        .file_name()
+                        .map_or("output".into(), |name| name.to_string_lossy());
+                    dir.join(file_name.to_string() + format.file_suffix())
+                }
+                None => {
+                    let file_name = output_prefix.to_string();
+                    PathBuf::from(file_name + format.file_suffix())
+                }
+            };
+
+            if let Some(parent) = path.parent() {
+                if !parent.as_os_str().is_empty() {
+                    fs::create_dir_all(parent).map_err(|err| {
+                        PlanError::GenericError(err.to_string())
+                    })?;
+                }
+            }
+            fs::write(&path, rendered)
+                .map_err(|err| PlanError::GenericError(err.to_string()))?;
+
+            let path = path.to_string_lossy().to_string();
+            info!("Generated {} file: {}", format, path.yellow());
+            outputs.push(path);
+        }
+
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn plan_format_test() {
+        for value in PlanFormat::VALUES {
+            let format: PlanFormat = value.parse().unwrap();
+            assert_eq!(format.to_string(), value);
+        }
+        assert_eq!("JSON".parse(), Ok(PlanFormat::Json));
+        assert!("svg".parse::<PlanFormat>().is_err());
+    }
+
+    #[test]
+    fn output_location_test() {
+        assert_eq!("-".parse(), Ok(OutputLocation::StdOut));
+        assert_eq!(
+            "plans/".parse(),
+            Ok(OutputLocation::Directory("plans/".into()))
+        );
+        assert_eq!(
+            "plan.json".parse(),
+            Ok(OutputLocation::File("plan.json".into()))
+        );
+        assert!("".parse::<OutputLocation>().is_err());
+    }
+
+    #[test]
+    fn validate_test() {
+        let output = |location, formats| {
+            PlanOutput {
+                location: Some(location),
+                formats,
+            }
+        };
+        let file = OutputLocation::File("plan.json".into());
+        let json = vec![PlanFormat::Json];
+        let json_dot = vec![PlanFormat::Json, PlanFormat::Dot];
+
+        assert!(output(file.clone(), json.clone()).validate(1).is_ok());
+        assert!(output(file.clone(), json.clone()).validate(2).is_err());
+        assert!(output(file, json_dot.clone()).validate(1).is_err());
+        assert!(output(OutputLocation::StdOut, json_dot)
+            .validate(1)
+            .is_ok());
+        assert!(output(OutputLocation::StdOut, json).validate(2).is_err());
+        assert!(PlanOutput::next_to_input(false).validate(2).is_ok());
+    }
+
+    #[test]
+    fn in_subdirectory_test() {
+        let output = PlanOutput {
+            location: Some(OutputLocation::Directory("plans".into())),
+            formats:  vec![PlanFormat::Json],
+        };
+        assert_eq!(
+            output.in_subdirectory(Path::new("rml")).location,
+            Some(OutputLocation::Directory(Path::new("plans").join("rml")))
+        );
+
+        let output = PlanOutput::next_to_input(true);
+        assert_eq!(output.in_subdirectory(Path::new("rml")), output);
+    }
+
+    #[test]
+    fn write_test() {
+        let dir = env::temp_dir()
+            .join(format!("plan_output_test_{}", process::id()));
+        let output = PlanOutput {
+            location: Some(OutputLocation::Directory(dir.clone())),
+            formats:  vec![PlanFormat::Json, PlanFormat::Mermaid],
+        };
+
+        let written = output.write(&Plan::new(), "mappings/airports").unwrap();
+        let json_path = dir.join("airports.json");
+        assert_eq!(
+            written,
+            [
+                json_path.to_string_lossy().to_string(),
+                dir.join("airports.mmd").to_string_lossy().to_string(),
+            ]
+        );
+        let json = fs::read_to_string(&json_path).unwrap();
+        assert_eq!(json, Plan::new().to_json_string().unwrap());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
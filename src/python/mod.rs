@@ -1,15 +1,95 @@
+use std::collections::HashMap;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
 use pyo3::prelude::*;
-use crate::api::process_one_str;
+use pyo3::types::PyDict;
+use crate::api::{
+    self, translate as translate_mapping, TranslationFailure,
+    TranslationOptions,
+};
+
+create_exception!(ltranslator, TranslationError, PyException);
+
+/// Error of the translation with one mapping language, in the `failures`
+/// attribute of a `TranslationError`. The line and column are `None` if the
+/// location of the error is unknown.
+#[pyclass(module = "ltranslator", frozen, get_all)]
+#[derive(Debug, Clone)]
+struct LanguageFailure {
+    language: String,
+    line:     Option<usize>,
+    column:   Option<usize>,
+    messages: Vec<String>,
+}
+
+impl From<&api::LanguageFailure> for LanguageFailure {
+    fn from(failure: &api::LanguageFailure) -> Self {
+        LanguageFailure {
+            language: failure.language.clone(),
+            line:     failure.location.map(|location| location.line),
+            column:   failure.location.map(|location| location.column),
+            messages: failure.messages.clone(),
+        }
+    }
+}
+
+fn translation_error(py: Python<'_>, failure: &TranslationFailure) -> PyErr {
+    let err = TranslationError::new_err(failure.to_string());
+    let failures: Vec<_> =
+        failure.errors.iter().map(LanguageFailure::from).collect();
+    match err.value_bound(py).setattr("failures", failures.into_py(py)) {
+        Ok(()) => err,
+        Err(attr_err) => attr_err,
+    }
+}
 
+/// Translates the mapping, with the given mapping language ("rml-v1",
+/// "rml-v2" or "shexml") if any instead of detecting it.
+///
+/// The other translation options are given as keyword arguments, e.g.
+/// `base_iri="http://example.com/"` or `named_graphs=False`. The values of
+/// the `${name}` placeholders in the sources and targets are given as a
+/// dict of `params`.
+///
+/// Raises a `TranslationError` with the error of every attempted mapping
+/// language as `LanguageFailure` in its `failures` attribute.
 #[pyfunction]
-fn translate(mapping: String) -> PyResult<String> {
-    let translated = process_one_str(mapping.as_str());
+#[pyo3(signature = (mapping, language=None, params=None, **options))]
+fn translate(
+    py: Python<'_>,
+    mapping: String,
+    language: Option<String>,
+    params: Option<HashMap<String, String>>,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<String> {
+    let mut translation_options = TranslationOptions::default();
+    if let Some(language) = language {
+        translation_options
+            .set("language", &language)
+            .map_err(PyValueError::new_err)?;
+    }
+    if let Some(options) = options {
+        for (key, value) in options.iter() {
+            let key: String = key.extract()?;
+            translation_options
+                .set(&key, &value.str()?.to_string())
+                .map_err(PyValueError::new_err)?;
+        }
+    }
+    if let Some(params) = params {
+        translation_options.params.extend(params);
+    }
 
-    Ok(translated)
+    translate_mapping(mapping.as_str(), &translation_options)
+        .map(|output| output.plan)
+        .map_err(|failure| translation_error(py, &failure))
 }
 
 #[pymodule]
 fn ltranslator(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(translate, m)?)?;
+    m.add("TranslationError", m.py().get_type_bound::<TranslationError>())?;
+    m.add_class::<LanguageFailure>()?;
     Ok(())
 }
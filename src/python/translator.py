@@ -3,5 +3,14 @@ import ltranslator
 
 if __name__ == '__main__':
     mapping = sys.stdin.read()
-    translated = ltranslator.translate(mapping)
+    try:
+        # Translation options are given as key=value arguments, parameters
+        # as param=name=value arguments
+        options = [arg.split('=', 1) for arg in sys.argv[1:]]
+        params = dict(value.split('=', 1)
+                      for key, value in options if key == 'param')
+        options = {key: value for key, value in options if key != 'param'}
+        translated = ltranslator.translate(mapping, params=params, **options)
+    except (ltranslator.TranslationError, ValueError) as err:
+        sys.exit(str(err))
     print(translated)
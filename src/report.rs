@@ -0,0 +1,302 @@
+//!
+//! Reports of the translation of mapping document files, which can be
+//! summarized in a machine-readable format when translating a folder.
+//!
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use translator::detect::MappingLanguage;
+
+use crate::api::LanguageFailure;
+
+/// Status of the translation of a mapping document file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Translated,
+    Failed,
+    /// The file is not a supported mapping document
+    Skipped,
+}
+
+impl Display for FileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileStatus::Translated => write!(f, "translated"),
+            FileStatus::Failed => write!(f, "failed"),
+            FileStatus::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+/// Report of the translation of a mapping document file.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub file:      PathBuf,
+    pub status:    FileStatus,
+    /// Detected, or forced, mapping language and version of the document
+    pub language:  Option<MappingLanguage>,
+    /// Errors of every attempted mapping language if the translation failed
+    pub errors:    Vec<LanguageFailure>,
+    /// Paths of the written mapping plan files
+    pub outputs:   Vec<String>,
+    pub duration:  Duration,
+}
+
+impl FileReport {
+    pub(crate) fn new(file: PathBuf) -> Self {
+        FileReport {
+            file,
+            status:    FileStatus::Skipped,
+            language:  None,
+            errors:    Vec::new(),
+            outputs:   Vec::new(),
+            duration:  Duration::ZERO,
+        }
+    }
+
+    pub(crate) fn failed(
+        mut self,
+        errors: Vec<LanguageFailure>,
+        started: Instant,
+    ) -> Self {
+        self.status = FileStatus::Failed;
+        self.errors = errors;
+        self.duration = started.elapsed();
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let errors: Vec<_> =
+            self.errors.iter().map(LanguageFailure::to_json).collect();
+
+        json!({
+            "file":        self.file.to_string_lossy(),
+            "status":      self.status.to_string(),
+            "language":    self.language.map(|lang| lang.to_string()),
+            "errors":      errors,
+            "outputs":     self.outputs,
+            "duration_ms": self.duration.as_millis() as u64,
+        })
+    }
+}
+
+/// Machine-readable formats of the summary of the translated files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SummaryFormat {
+    #[default]
+    Json,
+    /// [JUnit XML](https://github.com/testmoapp/junitxml) with a test case
+    /// for every file
+    JUnit,
+}
+
+impl SummaryFormat {
+    pub const VALUES: [&'static str; 2] = ["json", "junit"];
+
+    /// Formats the summary of the given file `reports`.
+    pub fn format(self, reports: &[FileReport]) -> String {
+        match self {
+            SummaryFormat::Json => json_summary(reports),
+            SummaryFormat::JUnit => junit_summary(reports),
+        }
+    }
+}
+
+impl FromStr for SummaryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(SummaryFormat::Json),
+            "junit" => Ok(SummaryFormat::JUnit),
+            _ => {
+                Err(format!(
+                    "unknown summary format {}, expected one of: {}",
+                    s,
+                    SummaryFormat::VALUES.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+fn count(reports: &[FileReport], status: FileStatus) -> usize {
+    reports
+        .iter()
+        .filter(|report| report.status == status)
+        .count()
+}
+
+fn json_summary(reports: &[FileReport]) -> String {
+    let file_reports: Vec<_> =
+        reports.iter().map(FileReport::to_json).collect();
+    let summary = json!({
+        "files":      reports.len(),
+        "translated": count(reports, FileStatus::Translated),
+        "failed":     count(reports, FileStatus::Failed),
+        "skipped":    count(reports, FileStatus::Skipped),
+        "reports":    file_reports,
+    });
+
+    serde_json::to_string_pretty(&summary)
+        .expect("JSON values are always serializable")
+}
+
+fn junit_summary(reports: &[FileReport]) -> String {
+    let duration: Duration =
+        reports.iter().map(|report| report.duration).sum();
+    let counts = format!(
+        "tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\"",
+        reports.len(),
+        count(reports, FileStatus::Failed),
+        count(reports, FileStatus::Skipped),
+        duration.as_secs_f64()
+    );
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml += &format!("<testsuites {}>\n", counts);
+    xml += &format!("  <testsuite name=\"translator\" {}>\n", counts);
+    for report in reports {
+        let classname = report
+            .language
+            .map_or("unknown".to_string(), |lang| lang.to_string());
+        xml += &format!(
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">\n",
+            escape_xml(&report.file.to_string_lossy()),
+            escape_xml(&classname),
+            report.duration.as_secs_f64()
+        );
+
+        match report.status {
+            FileStatus::Translated => {
+                let outputs = report.outputs.join("\n");
+                xml += &format!(
+                    "      <system-out>{}</system-out>\n",
+                    escape_xml(&outputs)
+                );
+            }
+            FileStatus::Failed => {
+                for failure in &report.errors {
+                    xml += &format!(
+                        "      <failure message=\"{}\">{}</failure>\n",
+                        escape_xml(&failure.to_string()),
+                        escape_xml(&failure.messages.join("\n"))
+                    );
+                }
+            }
+            FileStatus::Skipped => {
+                xml += "      <skipped message=\"unsupported file\"/>\n";
+            }
+        }
+        xml += "    </testcase>\n";
+    }
+    xml += "  </testsuite>\n</testsuites>\n";
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ErrorLocation;
+
+    fn reports() -> Vec<FileReport> {
+        let translated = FileReport {
+            status:   FileStatus::Translated,
+            language: Some(MappingLanguage::RML),
+            outputs:  vec!["plans/mapping.json".to_string()],
+            duration: Duration::from_millis(1500),
+            ..FileReport::new(PathBuf::from("mapping.ttl"))
+        };
+        let failed = FileReport {
+            status:   FileStatus::Failed,
+            language: Some(MappingLanguage::ShExML),
+            errors:   vec![LanguageFailure {
+                language: "ShExML".to_string(),
+                location: Some(ErrorLocation {
+                    line:   3,
+                    column: 7,
+                }),
+                messages: vec!["expected '<' or \"&\"".to_string()],
+            }],
+            ..FileReport::new(PathBuf::from("a&b.shexml"))
+        };
+        let skipped = FileReport::new(PathBuf::from("data.csv"));
+
+        vec![translated, failed, skipped]
+    }
+
+    #[test]
+    fn json_summary_test() {
+        let summary: Value =
+            serde_json::from_str(&SummaryFormat::Json.format(&reports()))
+                .unwrap();
+
+        assert_eq!(summary["files"], 3);
+        assert_eq!(summary["translated"], 1);
+        assert_eq!(summary["failed"], 1);
+        assert_eq!(summary["skipped"], 1);
+
+        let translated = &summary["reports"][0];
+        assert_eq!(translated["file"], "mapping.ttl");
+        assert_eq!(translated["status"], "translated");
+        assert_eq!(translated["language"], "RML v1.1.2");
+        assert_eq!(translated["outputs"][0], "plans/mapping.json");
+        assert_eq!(translated["duration_ms"], 1500);
+
+        let failed = &summary["reports"][1];
+        assert_eq!(failed["status"], "failed");
+        assert_eq!(failed["errors"][0]["location"]["line"], 3);
+        assert_eq!(summary["reports"][2]["language"], Value::Null);
+    }
+
+    #[test]
+    fn junit_summary_test() {
+        let summary = SummaryFormat::JUnit.format(&reports());
+
+        assert!(summary.starts_with("<?xml"));
+        assert!(summary.contains(
+            "<testsuites tests=\"3\" failures=\"1\" skipped=\"1\" \
+             time=\"1.500\">"
+        ));
+        assert!(summary.contains(
+            "<testcase name=\"mapping.ttl\" classname=\"RML v1.1.2\" \
+             time=\"1.500\">"
+        ));
+        assert!(summary
+            .contains("<system-out>plans/mapping.json</system-out>"));
+        assert!(summary.contains("name=\"a&amp;b.shexml\""));
+        assert!(summary.contains(
+            "<failure message=\"ShExML at line 3, column 7: expected \
+             &apos;&lt;&apos; or &quot;&amp;&quot;\">"
+        ));
+        assert!(summary.contains("classname=\"unknown\""));
+        assert!(summary.contains("<skipped message=\"unsupported file\"/>"));
+        assert!(summary.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn escape_xml_test() {
+        assert_eq!(
+            escape_xml("<a href='x'>\"&\"</a>"),
+            "&lt;a href=&apos;x&apos;&gt;&quot;&amp;&quot;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn summary_format_from_str_test() {
+        assert_eq!("json".parse(), Ok(SummaryFormat::Json));
+        assert_eq!("JUnit".parse(), Ok(SummaryFormat::JUnit));
+        assert!("xml".parse::<SummaryFormat>().is_err());
+    }
+}
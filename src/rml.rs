@@ -1,14 +1,12 @@
-use log::{error, info, warn};
+use std::fs;
+
+use log::info;
 use plan::states::Init;
 use plan::Plan;
-use translator::error::TranslationError;
-use translator::new_rml::error::NewRMLTranslationError;
-use translator::new_rml::translator::NewRMLDocumentTranslator;
-use translator::rml::parser::extractors::io::{
-    parse_file as old_parse_file, parse_str as old_parse_str,
-};
-use translator::rml::OptimizedRMLDocumentTranslator;
-use translator::LanguageTranslator;
+use translator::detect;
+use translator::error::{TranslationError, TranslationErrorKind};
+use translator::options::TranslationOptions;
+use translator::rml::parser::extractors::io::with_default_base;
 
 use crate::handler::{FileTranslatorHandler, StringTranslatorHandler};
 
@@ -19,23 +17,25 @@ pub struct RMLFileHandler;
 pub struct RMLStringHandler;
 
 impl FileTranslatorHandler for RMLFileHandler {
+    fn language(&self) -> String {
+        "RML".to_string()
+    }
+
     fn translate(
         &self,
         file_path: &dyn AsRef<str>,
+        options: &TranslationOptions,
     ) -> Result<Plan<Init>, TranslationError> {
-        info!("Trying to translate file {} with RML v1 (old) spec translator https://rml.io/specs/rml/", file_path.as_ref());
-        if let Ok(document) = old_parse_file(file_path.as_ref().into()) {
-            OptimizedRMLDocumentTranslator::translate_to_plan(document)
-        } else {
-            warn!("Failed extracting with RML v1 (old) spec translator");
-            info!("Trying again with the RML v2 (new) spec translator https://kg-construct.github.io/rml-resources/portal/");
-            let document = translator::new_rml::extractors::io::parse_file(
-                file_path.as_ref().into(),
-            )
-            .map_err::<NewRMLTranslationError, _>(|err| err.into())?;
+        let mapping = fs::read_to_string(file_path.as_ref()).map_err(|error| {
+            TranslationError {
+                kind: TranslationErrorKind::FileStdError {
+                    file: file_path.as_ref().into(),
+                    error,
+                },
+            }
+        })?;
 
-            NewRMLDocumentTranslator::translate_to_plan(document)
-        }
+        RMLStringHandler.translate(&with_default_base(mapping), options)
     }
 
     fn supported_extension(&self) -> String {
@@ -44,23 +44,22 @@ impl FileTranslatorHandler for RMLFileHandler {
 }
 
 impl StringTranslatorHandler for RMLStringHandler {
-    fn translate(&self, mapping: &str) -> Result<Plan<Init>, TranslationError> {
-        // TODO: Needs a better way to detect old vs new RML mapping document <16-04-25, Min Oo> //
-
-        if mapping.contains("<http://www.w3.org/ns/r2rml#>") {
-            // Old RML mapping document
+    fn language(&self) -> String {
+        "RML".to_string()
+    }
 
-            info!("Using translator for the Old RML spec https://rml.io/specs/rml/");
-            let document = old_parse_str(mapping)?;
-            OptimizedRMLDocumentTranslator::translate_to_plan(document)
-        } else {
-            // New RML mapping document shouldn't contain R2RML's prefix (BIIIGG ASSUMPTION)
-            info!("Using translator for the latest RML spec https://kg-construct.github.io/rml-resources/portal/");
-            let document =
-                translator::new_rml::extractors::io::parse_str(mapping)
-                    .map_err::<NewRMLTranslationError, _>(|err| err.into())?;
+    fn translate(
+        &self,
+        mapping: &str,
+        options: &TranslationOptions,
+    ) -> Result<Plan<Init>, TranslationError> {
+        let detected = detect::detect_rml(mapping)?;
+        info!(
+            "Detected {} document with confidence {:.2}",
+            detected.parsed.language(),
+            detected.confidence
+        );
 
-            NewRMLDocumentTranslator::translate_to_plan(document)
-        }
+        detect::translate_parsed(mapping, detected.parsed, options)
     }
 }
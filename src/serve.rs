@@ -0,0 +1,586 @@
+//!
+//! Service mode which translates the mapping documents posted to a small
+//! HTTP API, for services which cannot embed the language bindings.
+//!
+//! The API exchanges JSON and closes every connection after its response:
+//!
+//! - `GET /health` reports that the service is up.
+//! - `POST /translate` translates the `mapping` of a request like
+//!   `{"mapping": "...", "options": {"language": "rml-v1"}}` and responds
+//!   with the mapping plan, or with the errors of every attempted mapping
+//!   language. The options are the keys of [TranslationOptions::set].
+//! - `POST /validate` takes the same request and only reports whether the
+//!   mapping document translates.
+//!
+//! The size of the head and body of a request and the number of connections
+//! handled at the same time are limited, see [MAX_HEAD_SIZE],
+//! [MAX_BODY_SIZE] and [MAX_CONNECTIONS].
+//!
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, error, info};
+use serde_json::{json, Value};
+use translator::options::TranslationOptions;
+
+use crate::api::{
+    panic_failure, translate, LanguageFailure, TranslationFailure,
+    TranslationOutput,
+};
+
+/// Maximum size of the body of a request
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Maximum size of the request line and headers of a request
+const MAX_HEAD_SIZE: u64 = 64 * 1024;
+
+/// Maximum number of connections handled at the same time, the connections
+/// beyond it are responded to with 503 until others are closed
+const MAX_CONNECTIONS: usize = 64;
+
+/// Time after which a connection which stopped sending its request is
+/// closed
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP service translating mapping documents.
+#[derive(Debug)]
+pub struct Service {
+    listener:    TcpListener,
+    /// Defaults of the options of every request, which the options of a
+    /// request override
+    options:     Arc<TranslationOptions>,
+    /// Number of connections which are being handled
+    connections: Arc<AtomicUsize>,
+}
+
+impl Service {
+    /// Binds the service to the `address`, use port 0 to let the operating
+    /// system pick a free port.
+    pub fn bind<A: ToSocketAddrs>(
+        address: A,
+        options: TranslationOptions,
+    ) -> io::Result<Self> {
+        Ok(Service {
+            listener:    TcpListener::bind(address)?,
+            options:     Arc::new(options),
+            connections: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Responds to the requests of every connection on its own thread,
+    /// with at most [MAX_CONNECTIONS] threads at the same time, until the
+    /// process is stopped.
+    pub fn run(&self) -> ! {
+        if let Ok(address) = self.local_addr() {
+            info!("Serving the translation API on http://{}", address);
+        }
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => self.spawn_handler(stream),
+                Err(err) => {
+                    error!("Errored while accepting a connection: {}", err)
+                }
+            }
+        }
+    }
+
+    fn spawn_handler(&self, stream: TcpStream) {
+        let active = self.connections.fetch_add(1, Ordering::SeqCst);
+        let guard = ConnectionGuard(Arc::clone(&self.connections));
+        if active >= MAX_CONNECTIONS {
+            debug!("Refused a connection beyond {}", MAX_CONNECTIONS);
+            let response = Response::error(
+                503,
+                "too many connections, retry the request later",
+            );
+            if let Err(err) = write_response(stream, &response) {
+                debug!("Errored while writing a response: {}", err);
+            }
+            return;
+        }
+
+        let options = Arc::clone(&self.options);
+        thread::spawn(move || {
+            let _guard = guard;
+            handle_connection(stream, &options)
+        });
+    }
+}
+
+/// Counts a connection as handled until it is dropped.
+#[derive(Debug)]
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    /// Path of the request target without its query
+    path:   String,
+    body:   Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body:   Value,
+}
+
+impl Response {
+    fn new(status: u16, body: Value) -> Self {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response::new(status, json!({ "error": message.into() }))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Reasons why a mapping document of a request is not translated.
+#[derive(Debug)]
+enum RequestError {
+    /// The request itself is malformed
+    Invalid(String),
+    Failed(TranslationFailure),
+    /// A translator panicked, which is a bug of the translator
+    Panicked(TranslationFailure),
+}
+
+impl RequestError {
+    fn into_response(self) -> Response {
+        match self {
+            RequestError::Invalid(message) => Response::error(400, message),
+            RequestError::Failed(failure) => {
+                Response::new(422, failure_json(&failure))
+            }
+            RequestError::Panicked(failure) => {
+                Response::new(500, failure_json(&failure))
+            }
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, options: &TranslationOptions) {
+    let started = Instant::now();
+    if let Err(err) = stream.set_read_timeout(Some(READ_TIMEOUT)) {
+        debug!("Cannot set the read timeout of a connection: {}", err);
+    }
+
+    let response = match read_request(&stream) {
+        Ok(request) => {
+            let response = route(&request, options);
+            info!(
+                "{} {} responded {} in {} ms",
+                request.method,
+                request.path,
+                response.status,
+                started.elapsed().as_millis()
+            );
+            response
+        }
+        Err(response) => response,
+    };
+
+    if let Err(err) = write_response(stream, &response) {
+        debug!("Errored while writing a response: {}", err);
+    }
+}
+
+fn route(request: &Request, options: &TranslationOptions) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/health") => {
+            Response::new(
+                200,
+                json!({
+                    "status":  "ok",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }),
+            )
+        }
+        ("POST", "/translate") => {
+            match translate_request(&request.body, options) {
+                Ok(output) => {
+                    // The plan is embedded as JSON instead of a string
+                    let plan = serde_json::from_str(&output.plan)
+                        .unwrap_or(Value::String(output.plan));
+                    Response::new(
+                        200,
+                        json!({
+                            "language":   output.language.to_string(),
+                            "confidence": output.confidence,
+                            "plan":       plan,
+                        }),
+                    )
+                }
+                Err(err) => err.into_response(),
+            }
+        }
+        ("POST", "/validate") => {
+            match translate_request(&request.body, options) {
+                Ok(output) => {
+                    Response::new(
+                        200,
+                        json!({
+                            "valid":      true,
+                            "language":   output.language.to_string(),
+                            "confidence": output.confidence,
+                        }),
+                    )
+                }
+                Err(RequestError::Failed(failure)) => {
+                    let mut body = failure_json(&failure);
+                    body["valid"] = Value::Bool(false);
+                    Response::new(200, body)
+                }
+                Err(err) => err.into_response(),
+            }
+        }
+        (method, path @ ("/health" | "/translate" | "/validate")) => {
+            Response::error(
+                405,
+                format!("method {} is not allowed for {}", method, path),
+            )
+        }
+        (_, path) => Response::error(404, format!("no endpoint at {}", path)),
+    }
+}
+
+/// Translates the mapping document of the JSON `body` of a request, with
+/// the options of the request applied to the default `options`.
+fn translate_request(
+    body: &[u8],
+    options: &TranslationOptions,
+) -> Result<TranslationOutput, RequestError> {
+    let request: Value = serde_json::from_slice(body).map_err(|err| {
+        RequestError::Invalid(format!("invalid JSON request: {}", err))
+    })?;
+    let mapping = request["mapping"].as_str().ok_or_else(|| {
+        RequestError::Invalid(
+            "expected the mapping document as string in \"mapping\""
+                .to_string(),
+        )
+    })?;
+
+    let mut options = options.clone();
+    match &request["options"] {
+        Value::Null => (),
+        Value::Object(request_options) => {
+            for (key, value) in request_options {
+                set_option(&mut options, key, value)
+                    .map_err(RequestError::Invalid)?;
+            }
+        }
+        _ => {
+            return Err(RequestError::Invalid(
+                "expected the options as object in \"options\"".to_string(),
+            ))
+        }
+    }
+
+    panic::catch_unwind(|| translate(mapping, &options))
+        .map_err(|panic| RequestError::Panicked(panic_failure(panic).into()))?
+        .map_err(RequestError::Failed)
+}
+
+/// Sets the option with the given `key` from a JSON string, number or
+/// boolean, or from every element of an array, e.g. of several `param`s.
+fn set_option(
+    options: &mut TranslationOptions,
+    key: &str,
+    value: &Value,
+) -> Result<(), String> {
+    // Files of the machine running the service are not exposed
+    if key.replace('_', "-") == "param-file" {
+        return Err("the param-file option is not supported, give the \
+                    parameters with param instead"
+            .to_string());
+    }
+
+    match value {
+        Value::String(value) => options.set(key, value),
+        Value::Number(_) | Value::Bool(_) => {
+            options.set(key, &value.to_string())
+        }
+        Value::Array(values) => {
+            values
+                .iter()
+                .try_for_each(|value| set_option(options, key, value))
+        }
+        _ => {
+            Err(format!(
+                "invalid value {} for option {}, expected a string, number \
+                 or boolean",
+                value, key
+            ))
+        }
+    }
+}
+
+fn failure_json(failure: &TranslationFailure) -> Value {
+    let errors: Vec<_> =
+        failure.errors.iter().map(LanguageFailure::to_json).collect();
+    json!({ "errors": errors })
+}
+
+/// Reads an HTTP/1.1 request, or returns the response to a request which
+/// cannot be read.
+fn read_request(stream: &TcpStream) -> Result<Request, Response> {
+    let mut reader = BufReader::new(stream);
+    let mut head_remaining = MAX_HEAD_SIZE;
+
+    let request_line = read_head_line(&mut reader, &mut head_remaining)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "invalid request line"));
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut content_length = None;
+    loop {
+        let header = read_head_line(&mut reader, &mut head_remaining)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length =
+                    Some(value.trim().parse::<usize>().map_err(|_| {
+                        Response::error(400, "invalid Content-Length")
+                    })?);
+            }
+        }
+    }
+
+    let body = match content_length {
+        Some(length) if length > MAX_BODY_SIZE => {
+            return Err(Response::error(
+                413,
+                format!("request body exceeds {} bytes", MAX_BODY_SIZE),
+            ));
+        }
+        Some(length) => {
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).map_err(invalid_request)?;
+            body
+        }
+        None if method == "POST" => {
+            return Err(Response::error(411, "missing Content-Length"));
+        }
+        None => Vec::new(),
+    };
+
+    Ok(Request {
+        method: method.to_string(),
+        path,
+        body,
+    })
+}
+
+/// Reads the request line or a header, of which at most `remaining` bytes
+/// of the [MAX_HEAD_SIZE] are left.
+fn read_head_line<R: BufRead>(
+    reader: &mut R,
+    remaining: &mut u64,
+) -> Result<String, Response> {
+    let mut line = String::new();
+    let read = reader
+        .take(*remaining)
+        .read_line(&mut line)
+        .map_err(invalid_request)?;
+    *remaining -= read as u64;
+
+    if *remaining == 0 && !line.ends_with('\n') {
+        return Err(Response::error(
+            431,
+            format!("request line and headers exceed {} bytes", MAX_HEAD_SIZE),
+        ));
+    }
+    Ok(line)
+}
+
+fn invalid_request(err: io::Error) -> Response {
+    Response::error(400, format!("cannot read request: {}", err))
+}
+
+fn write_response(
+    mut stream: TcpStream,
+    response: &Response,
+) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPING: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/translator/resources/test/rml-core-tests/RMLTC0009a-JSON.ttl"
+    ));
+
+    fn start() -> SocketAddr {
+        let service =
+            Service::bind("127.0.0.1:0", TranslationOptions::default())
+                .unwrap();
+        let address = service.local_addr().unwrap();
+        thread::spawn(move || service.run());
+        address
+    }
+
+    fn send(address: SocketAddr, request: &[u8]) -> (u16, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap();
+        (status.parse().unwrap(), serde_json::from_str(body).unwrap())
+    }
+
+    fn post(address: SocketAddr, path: &str, body: &Value) -> (u16, Value) {
+        let body = body.to_string();
+        let request = format!(
+            "POST {} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            path,
+            body.len(),
+            body
+        );
+        send(address, request.as_bytes())
+    }
+
+    #[test]
+    fn health_test() {
+        let address = start();
+        let (status, body) = send(address, b"GET /health HTTP/1.1\r\n\r\n");
+
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "ok");
+    }
+
+    #[test]
+    fn translate_test() {
+        let address = start();
+        let (status, body) =
+            post(address, "/translate", &json!({ "mapping": MAPPING }));
+
+        assert_eq!(status, 200);
+        assert_eq!(body["language"], "RML v2.0");
+        assert!(body["plan"].is_object());
+    }
+
+    #[test]
+    fn translate_failure_test() {
+        let address = start();
+        let request = json!({
+            "mapping": "not a mapping document",
+            "options": { "language": "shexml" },
+        });
+        let (status, body) = post(address, "/translate", &request);
+
+        assert_eq!(status, 422);
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn validate_test() {
+        let address = start();
+        let (status, body) =
+            post(address, "/validate", &json!({ "mapping": MAPPING }));
+        assert_eq!(status, 200);
+        assert_eq!(body["valid"], true);
+
+        let request = json!({ "mapping": "not a mapping document" });
+        let (status, body) = post(address, "/validate", &request);
+        assert_eq!(status, 200);
+        assert_eq!(body["valid"], false);
+        assert!(!body["errors"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn param_file_test() {
+        let address = start();
+        let request = json!({
+            "mapping": MAPPING,
+            "options": { "param_file": "/etc/passwd" },
+        });
+        let (status, body) = post(address, "/translate", &request);
+
+        assert_eq!(status, 400);
+        assert!(body["error"].as_str().unwrap().contains("param-file"));
+    }
+
+    #[test]
+    fn invalid_request_test() {
+        let address = start();
+
+        let (status, _) = send(address, b"GET /plans HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 404);
+
+        let (status, _) = send(address, b"GET /translate HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 405);
+
+        let (status, _) = send(address, b"POST /translate HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 411);
+
+        let request = format!(
+            "POST /translate HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_SIZE + 1
+        );
+        let (status, _) = send(address, request.as_bytes());
+        assert_eq!(status, 413);
+    }
+
+    #[test]
+    fn head_size_test() {
+        let address = start();
+
+        // The head is exactly the maximum size without its final newline
+        let mut request = b"GET /health HTTP/1.1\r\nX-Padding: ".to_vec();
+        request.resize(MAX_HEAD_SIZE as usize, b'a');
+        let (status, _) = send(address, &request);
+
+        assert_eq!(status, 431);
+    }
+}
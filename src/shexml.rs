@@ -2,6 +2,7 @@ use plan::error::PlanError;
 use plan::states::Init;
 use plan::Plan;
 use translator::error::TranslationError;
+use translator::options::TranslationOptions;
 use translator::shexml::error::ShExMLTranslationError;
 use translator::shexml::{parcombi, ShExMLTranslator};
 use translator::LanguageTranslator;
@@ -15,15 +16,23 @@ pub struct ShExMLFileHandler;
 pub struct ShExMLStringHandler;
 
 impl FileTranslatorHandler for ShExMLFileHandler {
+    fn language(&self) -> String {
+        "ShExML".to_string()
+    }
+
     fn translate(
         &self,
         file_path: &dyn AsRef<str>,
+        options: &TranslationOptions,
     ) -> Result<Plan<Init>, TranslationError> {
         let shexml_document =
             parcombi::parse_file(file_path.as_ref())
                 .map_err::<ShExMLTranslationError, _>(|err| err.into())?;
 
-        ShExMLTranslator::translate_to_plan(shexml_document)
+        ShExMLTranslator::translate_to_plan_with_options(
+            shexml_document,
+            options,
+        )
     }
 
     fn supported_extension(&self) -> String {
@@ -32,11 +41,22 @@ impl FileTranslatorHandler for ShExMLFileHandler {
 }
 
 impl StringTranslatorHandler for ShExMLStringHandler {
-    fn translate(&self, mapping: &str) -> Result<Plan<Init>, TranslationError> {
+    fn language(&self) -> String {
+        "ShExML".to_string()
+    }
+
+    fn translate(
+        &self,
+        mapping: &str,
+        options: &TranslationOptions,
+    ) -> Result<Plan<Init>, TranslationError> {
         let shexml_document =
             parcombi::parse_string(mapping.to_string())
                 .map_err::<ShExMLTranslationError, _>(|err| err.into())?;
 
-        ShExMLTranslator::translate_to_plan(shexml_document)
+        ShExMLTranslator::translate_to_plan_with_options(
+            shexml_document,
+            options,
+        )
     }
 }
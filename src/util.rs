@@ -1,11 +1,15 @@
+use std::collections::HashMap;
 use std::error::Error;
 
 use colored::Colorize;
 use log::{error, info};
+use operator::Limit;
 use plan::error::PlanError;
 use plan::states::Init;
 use plan::Plan;
 
+use crate::output::PlanOutput;
+
 pub fn pretty_print_err(err: &dyn Error) {
     error!("Error: {:#}", err);
     if let Some(inner) = err.source() {
@@ -22,35 +26,123 @@ fn nested_pretty_print_err(mut idx: u32, err: &dyn Error) {
     }
 }
 
+/// Inserts a [Limit] operator with the given `sample_limit` before every
+/// serializer of the mapping plan to only generate a sample of the output.
+pub fn insert_sample_limit(
+    mapping_plan: &mut Plan<Init>,
+    sample_limit: Option<usize>,
+) -> Result<(), PlanError> {
+    if let Some(limit) = sample_limit {
+        let inserted = mapping_plan
+            .insert_before_serializers(&Limit::new(limit).into(), "Limit")?;
+        info!("Inserted {} sampling limit operator(s)", inserted);
+    }
+    Ok(())
+}
+
+/// Resolves the parameter placeholders in the sources and targets of the
+/// `mapping_plan` with the given `params`.
+pub fn resolve_parameters(
+    mapping_plan: &mut Plan<Init>,
+    params: &HashMap<String, String>,
+) -> Result<(), PlanError> {
+    let resolved = mapping_plan.resolve_parameters(params)?;
+    if resolved > 0 {
+        info!("Resolved {} parameter placeholder(s)", resolved);
+    }
+    Ok(())
+}
+
+/// Writes the mapping plan to the `plan_output`, named after the given
+/// `output_prefix`, and returns the paths of the written files.
 pub fn serialize_and_log_msg<F: AsRef<str>>(
     output_prefix: String,
     mapping_plan: &mut Plan<Init>,
     file: F,
-    json_only_flag: bool,
-) -> Result<(), PlanError> {
+    plan_output: &PlanOutput,
+) -> Result<Vec<String>, PlanError> {
     info!("Translated file: {}", file.as_ref().yellow(),);
+    plan_output.write(mapping_plan, &output_prefix)
+}
 
-    if !json_only_flag {
-        let full_path = output_prefix.clone() + ".dot";
-        mapping_plan
-            .write(full_path.clone().into())
-            .map_err(|err| PlanError::GenericError(format!("{:?}", err)))?;
-        info!("Generated dot file: {}", full_path.yellow());
-
-        let pretty_path = output_prefix.clone() + "_pretty.dot";
-        mapping_plan
-            .write_pretty(pretty_path.clone().into())
-            .map_err(|err| PlanError::GenericError(format!("{:?}", err)))?;
-        info!(
-            "The pretty dot file version for visualization is: {}",
-            pretty_path.yellow()
-        );
-    }
-
-    let json_path = output_prefix + ".json";
-    mapping_plan
-        .write_json(json_path.clone().into())
-        .map_err(|err| PlanError::GenericError(format!("{:?}", err)))?;
-    info!("Generated json file: {}", json_path.yellow());
-    Ok(())
+/// Matches the `text` against the glob `pattern`, where `**` matches any
+/// number of directories, `*` any characters but `/` and `?` one character
+/// but `/`.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    if let Some(rest) = pattern.strip_prefix("**/") {
+        return glob_match(rest, text)
+            || text
+                .split_once('/')
+                .is_some_and(|(_, tail)| glob_match(pattern, tail));
+    }
+    if let Some(rest) = pattern.strip_prefix("**") {
+        return text
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain([text.len()])
+            .any(|idx| glob_match(rest, &text[idx..]));
+    }
+
+    let mut pattern_chars = pattern.chars();
+    match pattern_chars.next() {
+        None => text.is_empty(),
+        Some('*') => {
+            let rest = pattern_chars.as_str();
+            let segment_end = text.find('/').unwrap_or(text.len());
+            text[..segment_end]
+                .char_indices()
+                .map(|(idx, _)| idx)
+                .chain([segment_end])
+                .any(|idx| glob_match(rest, &text[idx..]))
+        }
+        Some(expected) => {
+            let mut text_chars = text.chars();
+            match text_chars.next() {
+                Some('/') if expected == '?' => false,
+                Some(c) if expected == '?' || c == expected => {
+                    glob_match(pattern_chars.as_str(), text_chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_literal_test() {
+        assert!(glob_match("mapping.ttl", "mapping.ttl"));
+        assert!(!glob_match("mapping.ttl", "mapping.ttl.bak"));
+        assert!(!glob_match("mapping.ttl", "other/mapping.ttl"));
+    }
+
+    #[test]
+    fn glob_match_star_test() {
+        assert!(glob_match("*.ttl", "mapping.ttl"));
+        assert!(glob_match("*.ttl", ".ttl"));
+        assert!(!glob_match("*.ttl", "mapping.shexml"));
+        assert!(!glob_match("*.ttl", "nested/mapping.ttl"));
+        assert!(glob_match("nested/*.ttl", "nested/mapping.ttl"));
+    }
+
+    #[test]
+    fn glob_match_question_mark_test() {
+        assert!(glob_match("RMLTC000?a.ttl", "RMLTC0001a.ttl"));
+        assert!(!glob_match("RMLTC000?a.ttl", "RMLTC00010a.ttl"));
+        assert!(!glob_match("a?b", "a/b"));
+    }
+
+    #[test]
+    fn glob_match_double_star_test() {
+        assert!(glob_match("**/*.ttl", "mapping.ttl"));
+        assert!(glob_match("**/*.ttl", "a/b/mapping.ttl"));
+        assert!(glob_match("a/**/mapping.ttl", "a/mapping.ttl"));
+        assert!(glob_match("a/**/mapping.ttl", "a/b/c/mapping.ttl"));
+        assert!(glob_match("tests/**", "tests/a/b.ttl"));
+        assert!(!glob_match("tests/**", "other/a/b.ttl"));
+        assert!(!glob_match("**/*.ttl", "a/b/mapping.shexml"));
+    }
 }
@@ -0,0 +1,428 @@
+//!
+//! Watch mode which translates mapping documents again whenever they, or
+//! the documents they import, change.
+//!
+//! The modification times of the files are polled, which works the same on
+//! every platform and for editors which replace a file when saving it.
+//!
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use log::{error, info};
+use operator::display::PrettyDisplay;
+use plan::states::Init;
+use plan::Plan;
+use translator::options::TranslationOptions;
+use walkdir::WalkDir;
+
+use crate::api::{
+    load_project, translate_mapping, translate_project_mapping,
+    LanguageFailure, TranslationFailure,
+};
+use crate::handler::FileTranslatorHandler;
+use crate::output::PlanOutput;
+use crate::rml::RMLFileHandler;
+use crate::shexml::ShExMLFileHandler;
+use crate::util::pretty_print_err;
+
+/// Options of the watch mode.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Interval in which the files are checked for changes
+    pub interval:  Duration,
+    /// Maximum depth of the watched documents below a watched folder
+    pub max_depth: usize,
+    /// Logs the operators added and removed by every change of a document
+    pub diff:      bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            interval:  Duration::from_millis(500),
+            max_depth: 4,
+            diff:      false,
+        }
+    }
+}
+
+/// Mapping document which is translated again when one of its files
+/// changes.
+#[derive(Debug)]
+struct WatchedDocument {
+    /// Modification times of the document and the documents it imports,
+    /// [None] if a file does not exist
+    modified: HashMap<PathBuf, Option<SystemTime>>,
+    /// Plan of the last successful translation
+    previous: Option<Plan<Init>>,
+}
+
+impl WatchedDocument {
+    fn is_modified(&self) -> bool {
+        self.modified.is_empty()
+            || self
+                .modified
+                .iter()
+                .any(|(file, modified)| modified_time(file) != *modified)
+    }
+}
+
+/// Watches the mapping document at `path`, or all mapping documents under
+/// the folder at `path`.
+#[derive(Debug)]
+pub struct Watcher<'a> {
+    path:          PathBuf,
+    watch_options: WatchOptions,
+    plan_output:   &'a PlanOutput,
+    options:       &'a TranslationOptions,
+    documents:     BTreeMap<PathBuf, WatchedDocument>,
+}
+
+impl<'a> Watcher<'a> {
+    pub fn new(
+        path: PathBuf,
+        watch_options: WatchOptions,
+        plan_output: &'a PlanOutput,
+        options: &'a TranslationOptions,
+    ) -> Self {
+        Watcher {
+            path,
+            watch_options,
+            plan_output,
+            options,
+            documents: BTreeMap::new(),
+        }
+    }
+
+    /// Translates the documents which changed since the last poll, or
+    /// every document on the first poll, and returns the number of
+    /// translated documents.
+    pub fn poll(&mut self) -> usize {
+        let current = find_documents(&self.path, self.watch_options.max_depth);
+        self.documents.retain(|document, _| {
+            let exists = current.contains(document);
+            if !exists {
+                info!("Stopped watching: {}", document.to_string_lossy());
+            }
+            exists
+        });
+
+        let mut translated = 0;
+        for document in current {
+            let watched = self.documents.entry(document.clone()).or_insert(
+                WatchedDocument {
+                    modified: HashMap::new(),
+                    previous: None,
+                },
+            );
+
+            if watched.is_modified() {
+                translate_document(
+                    &self.path,
+                    &document,
+                    watched,
+                    &self.watch_options,
+                    self.plan_output,
+                    self.options,
+                );
+                translated += 1;
+            }
+        }
+
+        translated
+    }
+
+    /// Polls the documents in the interval of the watch options until the
+    /// process is stopped.
+    pub fn run(&mut self) -> ! {
+        info!(
+            "Watching {} for changes, stop with Ctrl+C",
+            self.path.to_string_lossy()
+        );
+        loop {
+            self.poll();
+            thread::sleep(self.watch_options.interval);
+        }
+    }
+}
+
+/// Translates the `document` of the `watched` path and writes its plan to
+/// the `plan_output`, the errors of a failed translation are logged.
+fn translate_document(
+    watched_path: &Path,
+    document: &Path,
+    watched: &mut WatchedDocument,
+    watch_options: &WatchOptions,
+    plan_output: &PlanOutput,
+    options: &TranslationOptions,
+) {
+    let started = Instant::now();
+    let (mut files, translated) = load_and_translate(document, options);
+    // Keep watching the imported documents if the failure is caused by
+    // one of them
+    if translated.is_err() {
+        files.extend(watched.modified.keys().cloned());
+    }
+    watched.modified = files
+        .into_iter()
+        .map(|file| {
+            let modified = modified_time(&file);
+            (file, modified)
+        })
+        .collect();
+
+    let plan = match translated {
+        Ok(plan) => plan,
+        Err(failure) => {
+            error!("Errored while translating: {}", document.to_string_lossy());
+            pretty_print_err(&failure);
+            return;
+        }
+    };
+
+    let (output_prefix, file_output) =
+        output_of(watched_path, document, plan_output);
+    match file_output.write(&plan, &output_prefix) {
+        Ok(outputs) => {
+            info!(
+                "Translated {} in {} ms to: {}",
+                document.to_string_lossy(),
+                started.elapsed().as_millis(),
+                outputs.join(", ")
+            );
+        }
+        Err(err) => {
+            error!(
+                "Errored while serializing mapping plan for: {}",
+                document.to_string_lossy()
+            );
+            pretty_print_err(&err);
+        }
+    }
+
+    if watch_options.diff {
+        if let Some(previous) = &watched.previous {
+            log_diff(document, &plan, previous);
+        }
+    }
+    watched.previous = Some(plan);
+}
+
+/// Translates the `document` and returns the files it consists of.
+///
+/// RML documents are translated as a mapping project, so that the
+/// documents they import are translated and watched as well.
+fn load_and_translate(
+    document: &Path,
+    options: &TranslationOptions,
+) -> (Vec<PathBuf>, Result<Plan<Init>, TranslationFailure>) {
+    let is_rml = RMLFileHandler.can_handle(&document.to_string_lossy());
+    if is_rml {
+        return match load_project(&[document.to_path_buf()]) {
+            Ok(project) => {
+                let files = project.files.clone();
+                let translated = translate_project_mapping(project, options)
+                    .map(|(plan, _, _)| plan);
+                (files, translated)
+            }
+            Err(failure) => (vec![document.to_path_buf()], Err(failure)),
+        };
+    }
+
+    let language = options
+        .language
+        .map_or(ShExMLFileHandler.language(), |lang| lang.to_string());
+    let translated = fs::read_to_string(document)
+        .map_err(|err| LanguageFailure::new(language, &err).into())
+        .and_then(|mapping| translate_mapping(&mapping, options))
+        .map(|(plan, _, _)| plan);
+    (vec![document.to_path_buf()], translated)
+}
+
+/// Output prefix and output of the plan of the `document`, named like the
+/// `file` subcommand for a watched document and like the `folder`
+/// subcommand for the documents of a watched folder.
+fn output_of(
+    watched_path: &Path,
+    document: &Path,
+    plan_output: &PlanOutput,
+) -> (String, PlanOutput) {
+    let file_stem = document
+        .file_stem()
+        .map_or("output".to_string(), |stem| {
+            stem.to_string_lossy().to_string()
+        });
+    if watched_path.is_file() {
+        return (file_stem, plan_output.clone());
+    }
+
+    let parent = document.parent().unwrap_or(Path::new(""));
+    let subfolder = parent.strip_prefix(watched_path).unwrap_or(parent);
+    let output_prefix = parent.join(file_stem).to_string_lossy().to_string();
+    (output_prefix, plan_output.in_subdirectory(subfolder))
+}
+
+fn log_diff(document: &Path, plan: &Plan<Init>, previous: &Plan<Init>) {
+    let diff = plan.diff(previous);
+    if diff.is_empty() {
+        info!("The plan of {} is unchanged", document.to_string_lossy());
+        return;
+    }
+
+    match diff.pretty_string() {
+        Ok(pretty) => {
+            info!(
+                "The plan of {} changed by {} added and {} removed \
+                 operators:\n{}",
+                document.to_string_lossy(),
+                diff.added.len(),
+                diff.removed.len(),
+                pretty
+            );
+        }
+        Err(err) => {
+            error!("Errored while displaying the plan changes: {}", err);
+        }
+    }
+}
+
+/// Finds the mapping documents at `path`, which is either a mapping
+/// document itself or a folder.
+fn find_documents(path: &Path, max_depth: usize) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+
+    let handlers: Vec<Box<dyn FileTranslatorHandler>> =
+        vec![Box::new(RMLFileHandler), Box::new(ShExMLFileHandler)];
+    WalkDir::new(path)
+        .max_depth(max_depth)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|dentry| dentry.file_type().is_file())
+        .map(|dentry| dentry.into_path())
+        .filter(|file| {
+            let file_path = file.to_string_lossy();
+            handlers.iter().any(|handler| handler.can_handle(&file_path))
+        })
+        .collect()
+}
+
+fn modified_time(file: &Path) -> Option<SystemTime> {
+    fs::metadata(file)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::File;
+    use std::process;
+
+    use super::*;
+    use crate::output::{OutputLocation, PlanFormat};
+
+    const TEST_CASES: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/translator/resources/test");
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir()
+            .join(format!("watch_{}_test_{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn copy(test_case: &str, dir: &Path) -> PathBuf {
+        let source = Path::new(TEST_CASES).join(test_case);
+        let path = dir.join(source.file_name().unwrap());
+        fs::copy(source, &path).unwrap();
+        path
+    }
+
+    /// Moves the modification time of the `file` forward, regardless of
+    /// the resolution of the file system.
+    fn touch(file: &Path) {
+        let modified = modified_time(file).unwrap();
+        File::options()
+            .write(true)
+            .open(file)
+            .unwrap()
+            .set_modified(modified + Duration::from_secs(2))
+            .unwrap();
+    }
+
+    fn json_output(dir: &Path) -> PlanOutput {
+        PlanOutput {
+            location: Some(OutputLocation::Directory(dir.to_path_buf())),
+            formats:  vec![PlanFormat::Json],
+        }
+    }
+
+    #[test]
+    fn poll_folder_test() {
+        let dir = temp_dir("folder");
+        let output_dir = temp_dir("folder_output");
+        let first = copy("rml-core-tests/RMLTC0009a-JSON.ttl", &dir);
+        let plan_output = json_output(&output_dir);
+        let options = TranslationOptions::default();
+        let mut watcher = Watcher::new(
+            dir.clone(),
+            WatchOptions::default(),
+            &plan_output,
+            &options,
+        );
+
+        assert_eq!(watcher.poll(), 1);
+        assert!(output_dir.join("RMLTC0009a-JSON.json").is_file());
+        assert_eq!(watcher.poll(), 0);
+
+        touch(&first);
+        assert_eq!(watcher.poll(), 1);
+
+        let second = copy("rml-core-tests/RMLTC0010a-JSON.ttl", &dir);
+        assert_eq!(watcher.poll(), 1);
+        assert_eq!(watcher.documents.len(), 2);
+
+        fs::remove_file(&second).unwrap();
+        assert_eq!(watcher.poll(), 0);
+        assert_eq!(watcher.documents.len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+        fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[test]
+    fn poll_imports_test() {
+        let dir = temp_dir("imports");
+        let output_dir = temp_dir("imports_output");
+        let document = copy("rml/project/airports.ttl", &dir);
+        let imported = copy("rml/project/positions.ttl", &dir);
+        let plan_output = json_output(&output_dir);
+        let options = TranslationOptions::default();
+        let mut watcher = Watcher::new(
+            document,
+            WatchOptions::default(),
+            &plan_output,
+            &options,
+        );
+
+        assert_eq!(watcher.poll(), 1);
+        assert_eq!(watcher.poll(), 0);
+
+        // The imported document is watched together with the document
+        touch(&imported);
+        assert_eq!(watcher.poll(), 1);
+        let plan =
+            fs::read_to_string(output_dir.join("airports.json")).unwrap();
+        assert!(plan.contains("Positions.csv"));
+
+        fs::remove_dir_all(dir).unwrap();
+        fs::remove_dir_all(output_dir).unwrap();
+    }
+}
@@ -0,0 +1,458 @@
+//!
+//! Detection of the mapping language (and version) of a mapping document
+//! from the vocabularies and keywords it uses.
+//!
+//! The document is only parsed once, the parsed document of the detected
+//! language is reused to translate it with [translate_parsed].
+//!
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use log::debug;
+use sophia_api::graph::Graph;
+use sophia_api::term::Term;
+use sophia_api::triple::Triple;
+use sophia_inmem::graph::FastGraph;
+use vocab::ToString;
+
+use crate::error::TranslationError;
+use crate::new_rml::error::NewRMLTranslationError;
+use crate::new_rml::translator::NewRMLDocumentTranslator;
+use crate::options::TranslationOptions;
+use crate::rml::error::RMLTranslationError;
+use crate::rml::parser::extractors::error::ParseError;
+use crate::rml::OptimizedRMLDocumentTranslator;
+use crate::shexml::error::ShExMLTranslationError;
+use crate::shexml::parcombi::{self, ShExMLDocument};
+use crate::shexml::ShExMLTranslator;
+use crate::{new_rml, rml, LanguageTranslateResult, LanguageTranslator};
+
+/// Namespaces of the vocabularies of [RML v1.1.2](https://rml.io/specs/rml/)
+const RML_NAMESPACES: [&str; 5] = [
+    vocab::r2rml::IRI,
+    vocab::rml::IRI,
+    vocab::query::IRI,
+    vocab::fnml::IRI,
+    vocab::rmlt::IRI,
+];
+
+/// Namespace of the vocabularies of
+/// [RML v2.0](https://kg-construct.github.io/rml-resources/portal/)
+const NEW_RML_NAMESPACE: &str = vocab::rml_core::IRI;
+
+/// Keywords of the declarations every complete ShExML document consists
+/// of, `PREFIX` is left out since Turtle documents may use it as well.
+const SHEXML_KEYWORDS: [&str; 4] =
+    ["SOURCE", "ITERATOR", "FIELD", "EXPRESSION"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MappingLanguage {
+    /// [RML v1.1.2](https://rml.io/specs/rml/)
+    RML,
+    /// [RML v2.0](https://kg-construct.github.io/rml-resources/portal/)
+    NewRML,
+    ShExML,
+}
+
+impl MappingLanguage {
+    pub const VALUES: [&'static str; 3] = ["rml-v1", "rml-v2", "shexml"];
+}
+
+impl Display for MappingLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MappingLanguage::RML => write!(f, "RML v1.1.2"),
+            MappingLanguage::NewRML => write!(f, "RML v2.0"),
+            MappingLanguage::ShExML => write!(f, "ShExML"),
+        }
+    }
+}
+
+impl FromStr for MappingLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rml-v1" => Ok(MappingLanguage::RML),
+            "rml-v2" => Ok(MappingLanguage::NewRML),
+            "shexml" => Ok(MappingLanguage::ShExML),
+            _ => {
+                Err(format!(
+                    "unknown mapping language {}, expected one of: {}",
+                    s,
+                    MappingLanguage::VALUES.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+/// A mapping document parsed with the parser of its mapping language.
+pub enum ParsedMapping {
+    RML(FastGraph),
+    NewRML(FastGraph),
+    ShExML(Box<ShExMLDocument>),
+}
+
+impl ParsedMapping {
+    pub fn language(&self) -> MappingLanguage {
+        match self {
+            ParsedMapping::RML(_) => MappingLanguage::RML,
+            ParsedMapping::NewRML(_) => MappingLanguage::NewRML,
+            ParsedMapping::ShExML(_) => MappingLanguage::ShExML,
+        }
+    }
+}
+
+pub struct DetectedMapping {
+    pub parsed:     ParsedMapping,
+    /// Share of the recognized vocabulary terms belonging to the detected
+    /// RML version, or of the declaration keywords used by a ShExML
+    /// document, between 0 and 1
+    pub confidence: f32,
+}
+
+/// Detects the mapping language of the `mapping` document.
+///
+/// RML documents are detected from the namespaces of the predicates and
+/// classes used in the document, ShExML documents from successfully parsing
+/// them and the declaration keywords they use. Returns [None] if the
+/// document is neither.
+pub fn detect(mapping: &str) -> Option<DetectedMapping> {
+    match detect_rml(mapping) {
+        Ok(detected) => Some(detected),
+        Err(rml_err) => {
+            debug!("Document is not an RML document: {:?}", rml_err);
+            let shexml_document =
+                parcombi::parse_string(mapping.to_string()).ok()?;
+
+            Some(DetectedMapping {
+                parsed:     ParsedMapping::ShExML(Box::new(shexml_document)),
+                confidence: shexml_confidence(mapping),
+            })
+        }
+    }
+}
+
+/// Share of the [SHEXML_KEYWORDS] used by the ShExML `mapping` document,
+/// outside of its comment lines.
+fn shexml_confidence(mapping: &str) -> f32 {
+    let words: HashSet<&str> = mapping
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(str::split_whitespace)
+        .collect();
+
+    let used = SHEXML_KEYWORDS
+        .iter()
+        .filter(|keyword| words.contains(*keyword))
+        .count();
+    used as f32 / SHEXML_KEYWORDS.len() as f32
+}
+
+/// Detects the version of the RML `mapping` document.
+///
+/// # Errors
+///
+/// Returns an error if the document is not valid Turtle or does not use any
+/// of the RML vocabularies.
+pub fn detect_rml(mapping: &str) -> Result<DetectedMapping, TranslationError> {
+    let graph = rml::parser::extractors::io::load_graph_str(mapping)?;
+    detect_rml_graph(graph)
+}
+
+/// Detects the version of the RML document already loaded into the
+/// `graph`, e.g. merged from several files.
+///
+/// # Errors
+///
+/// Returns an error if the graph does not use any of the RML vocabularies.
+pub fn detect_rml_graph(
+    graph: FastGraph,
+) -> Result<DetectedMapping, TranslationError> {
+    let (rml_count, new_rml_count) = count_rml_terms(&graph);
+    let total = rml_count + new_rml_count;
+    debug!(
+        "Found {} RML v1.1.2 and {} RML v2.0 terms",
+        rml_count, new_rml_count
+    );
+
+    if total == 0 {
+        return Err(RMLTranslationError::from(ParseError::GenericError(
+            "document does not use any RML vocabulary".to_string(),
+        ))
+        .into());
+    }
+
+    // Documents mixing both versions are rare, it is the version with the
+    // most terms which is detected
+    let (parsed, count) = if new_rml_count > rml_count {
+        (ParsedMapping::NewRML(graph), new_rml_count)
+    } else {
+        (ParsedMapping::RML(graph), rml_count)
+    };
+
+    Ok(DetectedMapping {
+        parsed,
+        confidence: count as f32 / total as f32,
+    })
+}
+
+/// Counts the predicates and classes of the `graph` from the vocabularies
+/// of RML v1.1.2 and RML v2.0 respectively.
+fn count_rml_terms(graph: &FastGraph) -> (usize, usize) {
+    let rdf_type = vocab::rdf::PROPERTY::TYPE.to_string();
+    let mut rml_count = 0;
+    let mut new_rml_count = 0;
+
+    for triple in graph.triples().filter_map(|triple| triple.ok()) {
+        let predicate = triple.p().iri().map(|iri| iri.as_str().to_string());
+        let class = match predicate.as_deref() {
+            Some(pred) if pred == rdf_type => {
+                triple.o().iri().map(|iri| iri.as_str().to_string())
+            }
+            _ => None,
+        };
+
+        for iri in predicate.iter().chain(class.iter()) {
+            if iri.starts_with(NEW_RML_NAMESPACE) {
+                new_rml_count += 1;
+            } else if RML_NAMESPACES.iter().any(|ns| iri.starts_with(ns)) {
+                rml_count += 1;
+            }
+        }
+    }
+
+    (rml_count, new_rml_count)
+}
+
+/// Parses the `mapping` document with the parser of the given `language`.
+pub fn parse(
+    mapping: &str,
+    language: MappingLanguage,
+) -> Result<ParsedMapping, TranslationError> {
+    match language {
+        MappingLanguage::RML => {
+            let graph = rml::parser::extractors::io::load_graph_str(mapping)?;
+            Ok(ParsedMapping::RML(graph))
+        }
+        MappingLanguage::NewRML => {
+            let graph = new_rml::extractors::io::load_graph_str(mapping)
+                .map_err(NewRMLTranslationError::from)?;
+            Ok(ParsedMapping::NewRML(graph))
+        }
+        MappingLanguage::ShExML => {
+            let document = parcombi::parse_string(mapping.to_string())
+                .map_err(ShExMLTranslationError::from)?;
+            Ok(ParsedMapping::ShExML(Box::new(document)))
+        }
+    }
+}
+
+/// Translates the `parsed` mapping document to a mapping plan with the
+/// given `options`.
+///
+/// The original `mapping` is needed to extract the base IRI of RML
+/// documents.
+pub fn translate_parsed(
+    mapping: &str,
+    parsed: ParsedMapping,
+    options: &TranslationOptions,
+) -> LanguageTranslateResult {
+    let base_iri = mapping
+        .lines()
+        .filter_map(rml::parser::extractors::io::extract_base_iri)
+        .next();
+    translate_parsed_with_base(parsed, base_iri, options)
+}
+
+/// Translates the `parsed` mapping document, whose RML graph has the given
+/// `base_iri`, to a mapping plan with the given `options`.
+pub fn translate_parsed_with_base(
+    parsed: ParsedMapping,
+    base_iri: Option<String>,
+    options: &TranslationOptions,
+) -> LanguageTranslateResult {
+    match parsed {
+        ParsedMapping::RML(graph) => {
+            let document = rml::parser::extractors::io::parse_graph_with_base(
+                &graph, base_iri,
+            )?;
+            OptimizedRMLDocumentTranslator::translate_to_plan_with_options(
+                document, options,
+            )
+        }
+        ParsedMapping::NewRML(graph) => {
+            let document =
+                new_rml::extractors::io::parse_graph_with_base(
+                    &graph, base_iri,
+                )
+                .map_err(NewRMLTranslationError::from)?;
+            NewRMLDocumentTranslator::translate_to_plan_with_options(
+                document, options,
+            )
+        }
+        ParsedMapping::ShExML(document) => {
+            ShExMLTranslator::translate_to_plan_with_options(*document, options)
+        }
+    }
+}
+
+/// Translates only the triples map with the given `identifier` of the
+/// parsed RML `mapping`, together with the parent triples maps it is
+/// joined with, e.g. to show the operators of one triples map.
+///
+/// Returns [None] for ShExML documents or if the document has no such
+/// triples map.
+pub fn translate_triples_map(
+    mapping: &str,
+    parsed: &ParsedMapping,
+    identifier: &str,
+    options: &TranslationOptions,
+) -> Option<LanguageTranslateResult> {
+    match parsed {
+        ParsedMapping::RML(graph) => {
+            let parsed_document =
+                rml::parser::extractors::io::parse_graph(mapping, graph);
+            let document = match parsed_document {
+                Ok(document) => document.restrict_to(identifier)?,
+                Err(err) => return Some(Err(err.into())),
+            };
+            Some(
+                OptimizedRMLDocumentTranslator::translate_to_plan_with_options(
+                    document, options,
+                ),
+            )
+        }
+        ParsedMapping::NewRML(graph) => {
+            let parsed_document =
+                new_rml::extractors::io::parse_graph(mapping, graph)
+                    .map_err(NewRMLTranslationError::from);
+            let document = match parsed_document {
+                Ok(document) => document.restrict_to(identifier)?,
+                Err(err) => return Some(Err(err.into())),
+            };
+            Some(NewRMLDocumentTranslator::translate_to_plan_with_options(
+                document, options,
+            ))
+        }
+        ParsedMapping::ShExML(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::test_case;
+
+    fn detect_file(path: &str) -> Option<DetectedMapping> {
+        detect(&fs::read_to_string(path).unwrap())
+    }
+
+    #[test]
+    fn detect_rml_test() {
+        let detected =
+            detect_file(test_case!("rml/sample_mapping.ttl")).unwrap();
+
+        assert_eq!(detected.parsed.language(), MappingLanguage::RML);
+        assert_eq!(detected.confidence, 1.0);
+    }
+
+    #[test]
+    fn detect_new_rml_test() {
+        let detected =
+            detect_file(test_case!("rml-core-tests/RMLTC0001a-JSON.ttl"))
+                .unwrap();
+
+        assert_eq!(detected.parsed.language(), MappingLanguage::NewRML);
+        assert_eq!(detected.confidence, 1.0);
+    }
+
+    #[test]
+    fn detect_mixed_vocabularies_test() {
+        let mapping = r#"
+            @prefix rr: <http://www.w3.org/ns/r2rml#> .
+            @prefix rml: <http://w3id.org/rml/> .
+            @prefix ex: <http://example.com/> .
+
+            ex:TriplesMap a rml:TriplesMap ;
+                rml:subjectMap [
+                    rml:template "http://example.com/{id}" ;
+                    rr:class ex:Person
+                ] .
+        "#;
+        let detected = detect(mapping).unwrap();
+
+        assert_eq!(detected.parsed.language(), MappingLanguage::NewRML);
+        assert_eq!(detected.confidence, 0.75);
+    }
+
+    #[test]
+    fn detect_shexml_test() {
+        let detected =
+            detect_file(test_case!("shexml/simple/input.shexml")).unwrap();
+
+        assert_eq!(detected.parsed.language(), MappingLanguage::ShExML);
+        assert_eq!(detected.confidence, 1.0);
+    }
+
+    #[test]
+    fn shexml_confidence_test() {
+        // Incomplete ShExML document without expressions and shapes
+        let mapping = r#"
+            PREFIX : <http://example.com/>
+            SOURCE films_csv_file <https://example.com/films.csv>
+            ITERATOR film_csv <csvperrow> {
+                FIELD id <id>
+            }
+        "#;
+
+        assert_eq!(shexml_confidence(mapping), 0.75);
+        assert!(detect(mapping).is_none());
+        assert_eq!(shexml_confidence("not a mapping document"), 0.0);
+    }
+
+    #[test]
+    fn detect_unknown_language_test() {
+        let mapping = "@prefix ex: <http://example.com/> .\nex:a ex:b ex:c .";
+
+        assert!(detect(mapping).is_none());
+        assert!(detect("not a mapping document").is_none());
+    }
+
+    #[test]
+    fn translate_new_rml_triples_map_test() {
+        let mapping =
+            fs::read_to_string(test_case!("rml-core-tests/RMLTC0009a-JSON.ttl"))
+                .unwrap();
+        let parsed = detect(&mapping).unwrap().parsed;
+        let options = TranslationOptions::default();
+        let translate = |name: &str| {
+            let identifier = format!("http://example.com/base/{}", name);
+            translate_triples_map(&mapping, &parsed, &identifier, &options)
+                .map(|plan| plan.unwrap().to_json_string().unwrap())
+        };
+
+        // The students are joined with the sports they practise
+        let students = translate("TriplesMap1").unwrap();
+        assert!(students.contains("student.json"));
+        assert!(students.contains("sport.json"));
+
+        let sports = translate("TriplesMap2").unwrap();
+        assert!(sports.contains("sport.json"));
+        assert!(!sports.contains("student.json"));
+
+        assert!(translate("TriplesMap3").is_none());
+    }
+
+    #[test]
+    fn mapping_language_from_str_test() {
+        assert_eq!("rml-v1".parse(), Ok(MappingLanguage::RML));
+        assert_eq!("RML-v2".parse(), Ok(MappingLanguage::NewRML));
+        assert_eq!("shexml".parse(), Ok(MappingLanguage::ShExML));
+        assert!("r2rml".parse::<MappingLanguage>().is_err());
+    }
+}
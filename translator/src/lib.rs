@@ -1,7 +1,11 @@
 use error::TranslationError;
+use options::TranslationOptions;
 use plan::states::Init;
 use plan::Plan;
 
+pub mod detect;
+pub mod options;
+pub mod project;
 pub mod rml;
 pub mod new_rml;
 pub mod shexml;
@@ -15,7 +19,18 @@ mod test_macro;
 pub type LanguageTranslateResult = Result<Plan<Init>, TranslationError>;
 
 pub trait LanguageTranslator<T> {
-    fn translate_to_plan(model: T) -> LanguageTranslateResult;
+    /// Translates the `model` with the default [TranslationOptions].
+    fn translate_to_plan(model: T) -> LanguageTranslateResult {
+        Self::translate_to_plan_with_options(
+            model,
+            &TranslationOptions::default(),
+        )
+    }
+
+    fn translate_to_plan_with_options(
+        model: T,
+        options: &TranslationOptions,
+    ) -> LanguageTranslateResult;
 }
 
 pub trait OperatorTranslator<Output> {
@@ -51,14 +51,32 @@ pub fn load_graph_str(input_str: &str) -> ExtractorResult<FastGraph> {
 
 pub fn parse_str(input_str: &str) -> ExtractorResult<Document> {
     let graph = load_graph_str(input_str)?;
-    let triples_maps = extract_triples_maps(&graph)?;
+    parse_graph(input_str, &graph)
+}
+
+/// Parses the already loaded `graph` of the `input_str` into the data model
+/// [Document].
+pub fn parse_graph(
+    input_str: &str,
+    graph: &FastGraph,
+) -> ExtractorResult<Document> {
+    let base_iri = input_str.split('\n').filter_map(extract_base_iri).next();
+    parse_graph_with_base(graph, base_iri)
+}
+
+/// Parses the already loaded `graph` of an RML document, e.g. merged from
+/// several files, with the given `base_iri` into the data model [Document].
+pub fn parse_graph_with_base(
+    graph: &FastGraph,
+    base_iri: Option<String>,
+) -> ExtractorResult<Document> {
+    let triples_maps = extract_triples_maps(graph)?;
     if triples_maps.is_empty() {
         return Err(ParseError::GenericError(
             "No Triples maps extracted for the input RML document".to_string(),
         )
         .into());
     }
-    let base_iri = input_str.split('\n').filter_map(extract_base_iri).next();
     Ok(Document::new(base_iri, triples_maps))
 }
 
@@ -1,17 +1,18 @@
 use log::debug;
-use sophia_api::term::Term;
+use sophia_api::term::{FromTerm, Term};
 use sophia_inmem::graph::FastGraph;
 use sophia_term::RcTerm;
 
-use super::store::get_subject;
+use super::store::{get_list_items, get_object, get_subject};
 use super::{ExtractorResult, TermMapExtractor};
 use crate::new_rml::extractors::error::ParseError;
 use crate::new_rml::extractors::store::get_object_with_ps;
 use crate::new_rml::extractors::{Extractor, FromVocab};
 use crate::new_rml::rml_model::v2::core::expression_map::term_map::{
-    CommonTermMapInfo, ObjectMap,
+    CommonTermMapInfo, GatherMap, ObjectMap,
 };
 use crate::new_rml::rml_model::v2::core::expression_map::ExpressionMapEnum;
+use crate::new_rml::rml_model::v2::io::target::LogicalTarget;
 use crate::new_rml::rml_model::v2::TermMapEnum;
 
 fn extract_sub_expr_maps<TS, TCP, TMP>(
@@ -51,6 +52,7 @@ impl TermMapExtractor<TermMapEnum> for ObjectMap {
             term_map_info: term_map,
             language_map:  None,
             datatype_map:  None,
+            gather_map:    None,
         }))
     }
 
@@ -94,10 +96,19 @@ impl TermMapExtractor<TermMapEnum> for ObjectMap {
                 ],
             );
 
-            let term_map_info = CommonTermMapInfo::extract_self(
-                subj_ref.borrow_term(),
-                graph_ref,
-            )?;
+            let gather_map =
+                extract_gather_map(subj_ref.borrow_term(), graph_ref)?;
+            let term_map_info = match &gather_map {
+                Some(_) => {
+                    gather_term_map_info(subj_ref.borrow_term(), graph_ref)?
+                }
+                None => {
+                    CommonTermMapInfo::extract_self(
+                        subj_ref.borrow_term(),
+                        graph_ref,
+                    )?
+                }
+            };
             debug!(
                 "Object map is extracting with term map info: {:?}",
                 term_map_info
@@ -106,6 +117,7 @@ impl TermMapExtractor<TermMapEnum> for ObjectMap {
                 term_map_info,
                 language_map,
                 datatype_map,
+                gather_map,
             }))
         }
     }
@@ -124,3 +136,109 @@ impl TermMapExtractor<TermMapEnum> for ObjectMap {
         ]
     }
 }
+
+/// Extracts the gather map of the object map `subj_ref` if it gathers the
+/// terms of the object maps in its `rml:gather` list.
+fn extract_gather_map<TS>(
+    subj_ref: TS,
+    graph_ref: &FastGraph,
+) -> ExtractorResult<Option<GatherMap>>
+where
+    TS: Term + Clone,
+{
+    let gather_pred = vocab::rml_cc::PROPERTY::GATHER.to_rcterm();
+    let Ok(gather_list) =
+        get_object(graph_ref, subj_ref.borrow_term(), &gather_pred)
+    else {
+        return Ok(None);
+    };
+
+    let gather_as = get_object(
+        graph_ref,
+        subj_ref.borrow_term(),
+        vocab::rml_cc::PROPERTY::GATHER_AS.to_rcterm(),
+    )
+    .map_err(|_| {
+        ParseError::GenericError(format!(
+            "Gather map {:?} does not have a rml:gatherAs type",
+            subj_ref
+        ))
+    })?;
+
+    let strategy = get_object(
+        graph_ref,
+        subj_ref.borrow_term(),
+        vocab::rml_cc::PROPERTY::STRATEGY.to_rcterm(),
+    )
+    .unwrap_or_else(|_| vocab::rml_cc::PROPERTY::APPEND.to_rcterm());
+
+    let allow_empty = get_object(
+        graph_ref,
+        subj_ref.borrow_term(),
+        vocab::rml_cc::PROPERTY::ALLOW_EMPTY.to_rcterm(),
+    )
+    .ok()
+    .and_then(|value| value.lexical_form().map(|lex| lex == "true"))
+    .unwrap_or(false);
+
+    let gather = get_list_items(graph_ref, gather_list)
+        .into_iter()
+        .map(|om_ref| {
+            match ObjectMap::extract_self_term_map(om_ref, graph_ref)? {
+                TermMapEnum::ObjectMap(om) => Ok(om),
+                term_map => {
+                    Err(ParseError::GenericError(format!(
+                        "Gathered term map {:?} is not an object map",
+                        term_map
+                    )))
+                }
+            }
+        })
+        .collect::<ExtractorResult<Vec<_>>>()?;
+
+    Ok(Some(GatherMap {
+        gather,
+        gather_as,
+        strategy,
+        allow_empty,
+    }))
+}
+
+/// Term map info of a gather map which generates a blank node as head of
+/// the collection unless it has an expression for it.
+fn gather_term_map_info<TS>(
+    subj_ref: TS,
+    graph_ref: &FastGraph,
+) -> ExtractorResult<CommonTermMapInfo>
+where
+    TS: Term + Clone,
+{
+    let (expression, default_term_type) =
+        match ExpressionMapEnum::extract_self(subj_ref.borrow_term(), graph_ref)
+        {
+            Ok(expression) => {
+                (expression, vocab::rml_core::CLASS::IRI.to_rcterm())
+            }
+            Err(_) => {
+                let expression = ExpressionMapEnum::try_new_unknown(
+                    vocab::rml_cc::PROPERTY::GATHER.to_rcterm(),
+                    RcTerm::from_term(subj_ref.borrow_term()),
+                )?;
+                (expression, vocab::rml_core::CLASS::BLANKNODE.to_rcterm())
+            }
+        };
+
+    let term_type = get_object_with_ps(
+        graph_ref,
+        subj_ref.borrow_term(),
+        &[&vocab::rml_core::PROPERTY::TERMTYPE.to_rcterm()],
+    )
+    .unwrap_or(default_term_type);
+
+    Ok(CommonTermMapInfo {
+        identifier: RcTerm::from_term(subj_ref),
+        term_type,
+        expression,
+        logical_targets: vec![LogicalTarget::default()],
+    })
+}
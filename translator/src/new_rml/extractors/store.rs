@@ -9,7 +9,7 @@ use sophia_api::triple::Triple;
 use sophia_inmem::graph::FastGraph;
 
 use super::error::SophiaStoreError;
-use super::RcTerm;
+use super::{FromVocab, RcTerm};
 
 pub fn get_subgraph_subject<TS>(
     graph: &FastGraph,
@@ -174,3 +174,29 @@ where
         pred: format!("{:?}", pred_vec),
     })
 }
+
+/// Returns the items of the RDF list starting at the `list_head` node by
+/// following its `rdf:first` and `rdf:rest` triples.
+pub fn get_list_items<TS>(graph: &FastGraph, list_head: TS) -> Vec<RcTerm>
+where
+    TS: Term + Debug,
+{
+    let first_pred = vocab::rdf::PROPERTY::FIRST.to_rcterm();
+    let rest_pred = vocab::rdf::PROPERTY::REST.to_rcterm();
+
+    let mut items = Vec::new();
+    let mut visited = HashSet::new();
+    let mut node = RcTerm::from_term(list_head);
+    // Stop at cycles in malformed lists
+    while visited.insert(node.clone()) {
+        let Ok(item) = get_object(graph, &node, &first_pred) else {
+            break;
+        };
+        items.push(item);
+        match get_object(graph, &node, &rest_pred) {
+            Ok(rest) => node = rest,
+            Err(_) => break,
+        }
+    }
+    items
+}
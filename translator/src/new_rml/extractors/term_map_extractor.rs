@@ -191,6 +191,10 @@ impl<'a> TryInto<RMLTermMapType> for &'a RcTerm {
             {
                 Ok(RMLTermMapType::ObjectMap)
             }
+            // Term maps in the rml:gather list of a gather map
+            value if value == &vocab::rdf::PROPERTY::FIRST.to_rcterm() => {
+                Ok(RMLTermMapType::ObjectMap)
+            }
             value
                 if value == &vocab::r2rml::PROPERTY::GRAPHMAP.to_rcterm()
                     || value
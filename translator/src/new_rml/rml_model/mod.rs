@@ -1,3 +1,4 @@
+use sophia_api::term::Term;
 use v2::core::TriplesMap;
 
 //pub mod v1;
@@ -30,4 +31,46 @@ impl Document {
             triples_maps,
         }
     }
+
+    /// Restricts the document to the triples map with the given
+    /// `identifier`, an IRI or blank node label, and the parent triples
+    /// maps it is joined with, e.g. to inspect the operators into which one
+    /// triples map is translated.
+    ///
+    /// Returns [None] if the document has no such triples map.
+    pub fn restrict_to(&self, identifier: &str) -> Option<Document> {
+        let tm = self.triples_maps.iter().find(|tm| {
+            tm.identifier.iri().is_some_and(|iri| iri.as_str() == identifier)
+                || tm
+                    .identifier
+                    .bnode_id()
+                    .is_some_and(|bnode| bnode.as_str() == identifier)
+        })?;
+
+        let mut identifiers = vec![tm.identifier.clone()];
+        let mut idx = 0;
+        while let Some(current) = identifiers.get(idx) {
+            let tm = self
+                .triples_maps
+                .iter()
+                .find(|tm| tm.identifier == *current)?;
+            for (parent, _) in tm.get_parent_tms_pred_refom_pairs() {
+                if !identifiers.contains(&parent) {
+                    identifiers.push(parent);
+                }
+            }
+            idx += 1;
+        }
+
+        let triples_maps = self
+            .triples_maps
+            .iter()
+            .filter(|tm| identifiers.contains(&tm.identifier))
+            .cloned()
+            .collect();
+        Some(Document {
+            default_base_iri: self.default_base_iri.clone(),
+            triples_maps,
+        })
+    }
 }
@@ -191,6 +191,7 @@ pub struct ObjectMap {
     pub term_map_info: CommonTermMapInfo,
     pub language_map:  Option<ExpressionMapEnum>,
     pub datatype_map:  Option<ExpressionMapEnum>,
+    pub gather_map:    Option<GatherMap>,
 }
 
 impl ObjectMap {
@@ -204,10 +205,37 @@ impl ObjectMap {
             term_map_attributes.extend(langtype_map.get_ref_attributes());
         }
 
+        if let Some(gather_map) = &self.gather_map {
+            term_map_attributes.extend(
+                gather_map.gather.iter().flat_map(|om| om.get_ref_attributes()),
+            );
+        }
+
         term_map_attributes
     }
 }
 
+/// Gather map of an object map which gathers the terms generated by the
+/// `gather` object maps into a single collection or container term of
+/// type `gather_as` (e.g. `rdf:List`).
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct GatherMap {
+    pub gather:      Vec<ObjectMap>,
+    pub gather_as:   RcTerm,
+    pub strategy:    RcTerm,
+    pub allow_empty: bool,
+}
+
+impl GatherMap {
+    pub fn is_list(&self) -> bool {
+        self.gather_as == vocab::rdf::CLASS::RDF_LIST.to_rcterm()
+    }
+
+    pub fn is_append_strategy(&self) -> bool {
+        self.strategy == vocab::rml_cc::PROPERTY::APPEND.to_rcterm()
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct GraphMap {
     pub term_map_info: CommonTermMapInfo,
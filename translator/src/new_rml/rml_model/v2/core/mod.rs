@@ -62,6 +62,59 @@ impl TriplesMap {
 
         poms.next().is_some()
     }
+
+    /// Splits the triples map into a triples map without the object maps
+    /// which have a gather map, and a triples map with only those object
+    /// maps if there are any.
+    ///
+    /// The terms of gather maps are aggregated across the mapping tuples of
+    /// the triples map, so their triples are generated separately.
+    pub fn split_gather_maps(&self) -> (TriplesMap, Option<TriplesMap>) {
+        let is_gather_map = |om_enum: &TermMapEnum| {
+            om_enum
+                .try_unwrap_object_map_ref()
+                .is_ok_and(|om| om.gather_map.is_some())
+        };
+
+        let mut without_gather_tm = self.clone();
+        for pom in &mut without_gather_tm.predicate_object_map_vec {
+            pom.object_map_vec.retain(|om_enum| !is_gather_map(om_enum));
+        }
+
+        let gather_poms: Vec<_> = self
+            .predicate_object_map_vec
+            .iter()
+            .filter_map(|pom| {
+                let object_map_vec: Vec<_> = pom
+                    .object_map_vec
+                    .iter()
+                    .filter(|om_enum| is_gather_map(om_enum))
+                    .cloned()
+                    .collect();
+                (!object_map_vec.is_empty()).then(|| {
+                    PredicateObjectMap {
+                        predicate_map_vec: pom.predicate_map_vec.clone(),
+                        object_map_vec,
+                        ref_object_map: Vec::new(),
+                        graph_map_vec: pom.graph_map_vec.clone(),
+                    }
+                })
+            })
+            .collect();
+        if gather_poms.is_empty() {
+            return (without_gather_tm, None);
+        }
+
+        let mut gather_tm = self.clone();
+        gather_tm.predicate_object_map_vec = gather_poms;
+        // The class triples are generated with the other triples
+        if let TermMapEnum::SubjectMap(sm) = &mut gather_tm.subject_map {
+            sm.classes.clear();
+        }
+
+        (without_gather_tm, Some(gather_tm))
+    }
+
     pub fn get_parent_tms_pred_refom_pairs(
         &self,
     ) -> HashSet<(RcTerm, PredicateRefObjGraphTuple)> {
@@ -7,6 +7,7 @@ pub enum TranslationError {
     SourceError(String),
     ExtendError(String),
     JoinError(String),
+    GatherError(String),
     PlanError(PlanError),
     Infallible,
 }
@@ -23,6 +24,7 @@ impl Display for TranslationError {
             TranslationError::SourceError(msg) => write!(f, "error while translating for source operator with msg: {}", msg),
             TranslationError::ExtendError(msg) => write!(f, "error while translating for extend operator with msg: {}", msg),
             TranslationError::JoinError(msg) => write!(f, "error while translating for join oeprator with msg: {}", msg), 
+            TranslationError::GatherError(msg) => write!(f, "error while translating for gather map with msg: {}", msg),
             TranslationError::PlanError(_) => write!(f, "error while generating the plan"),
             TranslationError::Infallible => write!(f, "something messed up happened and failed when it isn't supposed to!"),
         }
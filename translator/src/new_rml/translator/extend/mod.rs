@@ -111,7 +111,7 @@ pub fn insert_non_constant_func(
     }
 }
 
-fn extend_lang_dtype_function_for_om(
+pub fn extend_lang_dtype_function_for_om(
     store: &SearchStore<'_>,
     base_iri: &str,
     om: &ObjectMap,
@@ -160,6 +160,23 @@ pub fn extend_from_term_map(
     base_iri: &str,
     term_map_info: &CommonTermMapInfo,
 ) -> NewRMLTranslationResult<(String, Function)> {
+    let function =
+        extend_function_from_term_map(store, base_iri, term_map_info)?;
+
+    let var = store
+        .termm_id_quad_var_map
+        .get(&term_map_info.identifier)
+        .unwrap()
+        .to_string();
+    Ok((var, function))
+}
+
+/// Extend function generating the terms of the given term map.
+pub fn extend_function_from_term_map(
+    store: &SearchStore,
+    base_iri: &str,
+    term_map_info: &CommonTermMapInfo,
+) -> NewRMLTranslationResult<Function> {
     let inner_func = extension_func_from_exp_map(
         store,
         &term_map_info.expression,
@@ -210,12 +227,7 @@ pub fn extend_from_term_map(
         }
     }?;
 
-    let var = store
-        .termm_id_quad_var_map
-        .get(&term_map_info.identifier)
-        .unwrap()
-        .to_string();
-    Ok((var, function))
+    Ok(function)
 }
 
 pub fn extension_func_from_exp_map(
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use operator::{Aggregate, AggregateFunction, Extend};
+
+use super::error::TranslationError;
+use super::extend::{
+    extend_from_term_map, extend_function_from_term_map,
+    extend_lang_dtype_function_for_om, insert_non_constant_func,
+};
+use super::store::SearchStore;
+use super::OperatorTranslator;
+use crate::new_rml::error::NewRMLTranslationResult;
+use crate::new_rml::rml_model::v2::core::expression_map::term_map::{
+    CommonTermMapInfo, GatherMap, ObjectMap,
+};
+use crate::new_rml::rml_model::v2::core::TriplesMap;
+
+/// Translates the gather maps of a triples map, which only contains object
+/// maps with a gather map (see [TriplesMap::split_gather_maps]), to an
+/// [Extend] operator followed by an [Aggregate] operator.
+///
+/// The mapping tuples are grouped by the subject, predicates and graphs of
+/// the triples map, and the terms of the gathered object map are collected
+/// into a list under the variable of the gather map. The serializer
+/// generates the `rdf:List` of the collected terms for the variable.
+#[derive(Debug, Clone)]
+pub struct GatherOperatorTranslator {}
+
+impl OperatorTranslator for GatherOperatorTranslator {
+    type Input = TriplesMap;
+
+    type Output = (Extend, Aggregate);
+
+    fn translate_with_store(
+        store: &SearchStore,
+        tm: &Self::Input,
+    ) -> NewRMLTranslationResult<Self::Output> {
+        let base_iri = &tm.base_iri;
+        let mut extend_pairs = HashMap::new();
+
+        let mut group_by_term_maps: Vec<&CommonTermMapInfo> =
+            vec![tm.subject_map.as_ref()];
+        if let Ok(sm) = tm.subject_map.try_unwrap_subject_map_ref() {
+            group_by_term_maps.extend(sm.graph_maps.iter().map(AsRef::as_ref));
+        }
+        for pom in &tm.predicate_object_map_vec {
+            group_by_term_maps
+                .extend(pom.predicate_map_vec.iter().map(AsRef::as_ref));
+            group_by_term_maps
+                .extend(pom.graph_map_vec.iter().map(AsRef::as_ref));
+        }
+        for term_map_info in group_by_term_maps {
+            let (var, func) =
+                extend_from_term_map(store, base_iri, term_map_info)?;
+            insert_non_constant_func(&mut extend_pairs, var, func);
+        }
+
+        let mut group_by: Vec<_> = extend_pairs.keys().cloned().collect();
+        group_by.sort();
+        let mut aggregate = Aggregate::new(group_by);
+
+        let gather_oms = tm
+            .predicate_object_map_vec
+            .iter()
+            .flat_map(|pom| pom.object_map_vec.iter())
+            .filter_map(|om_enum| om_enum.try_unwrap_object_map_ref().ok());
+        for om in gather_oms {
+            let Some(gather_map) = &om.gather_map else {
+                continue;
+            };
+            let gathered_om = supported_gathered_om(gather_map, om)
+                .map_err(|msg| {
+                    TranslationError::GatherError(format!(
+                        "{} in gather map {:?}",
+                        msg, om.term_map_info.identifier
+                    ))
+                })?;
+
+            let om_var = store
+                .termm_id_quad_var_map
+                .get(&om.term_map_info.identifier)
+                .ok_or_else(|| {
+                    TranslationError::GatherError(format!(
+                        "No variable found for gather map {:?}",
+                        om.term_map_info.identifier
+                    ))
+                })?;
+            let gathered_var = format!("{}_gathered", om_var);
+
+            let func = extend_function_from_term_map(
+                store,
+                base_iri,
+                &gathered_om.term_map_info,
+            )?;
+            let func = extend_lang_dtype_function_for_om(
+                store,
+                base_iri,
+                gathered_om,
+                func,
+            )?;
+            extend_pairs.insert(gathered_var.clone(), func);
+
+            aggregate = aggregate.with_function(
+                om_var,
+                AggregateFunction::CollectList {
+                    attribute: gathered_var,
+                },
+            );
+        }
+
+        Ok((Extend { extend_pairs }, aggregate))
+    }
+}
+
+/// Returns the object map gathered by the `gather_map` of the object map
+/// `om`, or why the gather map is not supported.
+///
+/// Only gather maps generating an `rdf:List` with a blank node as head from
+/// a single object map are supported, without generating empty lists.
+fn supported_gathered_om<'a>(
+    gather_map: &'a GatherMap,
+    om: &ObjectMap,
+) -> Result<&'a ObjectMap, String> {
+    if !gather_map.is_list() {
+        return Err(format!(
+            "Gathering as {:?} is not supported, only as rdf:List",
+            gather_map.gather_as
+        ));
+    }
+    if !gather_map.is_append_strategy() {
+        return Err(format!(
+            "Gather strategy {:?} is not supported, only rml:append",
+            gather_map.strategy
+        ));
+    }
+    if gather_map.allow_empty {
+        return Err("Empty lists are not supported, only \
+                    rml:allowEmptyListAndContainer false"
+            .to_string());
+    }
+    if !om.term_map_info.is_bnode_term_type() {
+        return Err("Named collections are not supported, only blank nodes"
+            .to_string());
+    }
+
+    match gather_map.gather.as_slice() {
+        [gathered_om] if gathered_om.gather_map.is_none() => Ok(gathered_om),
+        [_] => Err("Nested gather maps are not supported".to_string()),
+        gathered_oms => {
+            Err(format!(
+                "Gathering {} object maps is not supported, only one",
+                gathered_oms.len()
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use operator::Operator;
+
+    use super::*;
+    use crate::new_rml::extractors::io::parse_file;
+    use crate::new_rml::extractors::FromVocab;
+    use crate::new_rml::translator::NewRMLDocumentTranslator;
+    use crate::{test_case, LanguageTranslator};
+
+    #[test]
+    fn translate_gather_list_test() {
+        let document =
+            parse_file(test_case!("rml-cc/gather-list.ttl").into()).unwrap();
+        let plan =
+            NewRMLDocumentTranslator::translate_to_plan(document).unwrap();
+        let graph = plan.graph.borrow();
+
+        let aggregates: Vec<_> = graph
+            .node_weights()
+            .filter_map(|node| {
+                match &node.operator {
+                    Operator::AggregateOp { config } => Some(config),
+                    _ => None,
+                }
+            })
+            .collect();
+        assert_eq!(aggregates.len(), 1);
+        let aggregate = aggregates[0];
+        assert_eq!(aggregate.group_by, vec!["sm_0".to_string()]);
+
+        let (list_var, function) =
+            aggregate.aggregate_pairs.iter().next().unwrap();
+        let AggregateFunction::CollectList { attribute } = function else {
+            panic!("Expected a collect list function but got {:?}", function);
+        };
+        assert_eq!(attribute, &format!("{}_gathered", list_var));
+
+        // Only the branch of the gather map serializes the list
+        let templates: Vec<_> = graph
+            .node_weights()
+            .filter_map(|node| {
+                match &node.operator {
+                    Operator::SerializerOp { config } => {
+                        Some(config.template.clone())
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+        assert_eq!(templates.len(), 2);
+        let list_triple =
+            format!("?sm_0 <http://example.com/values> ?{} .", list_var);
+        assert_eq!(
+            templates
+                .iter()
+                .filter(|template| template.contains(&list_triple))
+                .count(),
+            1
+        );
+        assert!(templates.iter().all(|template| {
+            template.contains(&list_triple)
+                != template.contains("<http://example.com/Record>")
+        }));
+    }
+
+    #[test]
+    fn unsupported_gather_test() {
+        let document =
+            parse_file(test_case!("rml-cc/gather-list.ttl").into()).unwrap();
+        let om = document.triples_maps[0]
+            .predicate_object_map_vec
+            .iter()
+            .flat_map(|pom| pom.object_map_vec.iter())
+            .filter_map(|om_enum| om_enum.try_unwrap_object_map_ref().ok())
+            .find(|om| om.gather_map.is_some())
+            .unwrap();
+        let gather_map = om.gather_map.as_ref().unwrap();
+        assert!(supported_gathered_om(gather_map, om).is_ok());
+
+        let bag = GatherMap {
+            gather_as: vocab::rdf::CLASS::RDF_BAG.to_rcterm(),
+            ..gather_map.clone()
+        };
+        assert!(supported_gathered_om(&bag, om).is_err());
+
+        let allow_empty = GatherMap {
+            allow_empty: true,
+            ..gather_map.clone()
+        };
+        assert!(supported_gathered_om(&allow_empty, om).is_err());
+
+        let multiple = GatherMap {
+            gather: vec![gather_map.gather[0].clone(); 2],
+            ..gather_map.clone()
+        };
+        assert!(supported_gathered_om(&multiple, om).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use operator::{Extend, Operator, Rename, Serializer, Target};
+use operator::{Extend, Operator, Rename, Serializer};
 use plan::states::join::join;
 use plan::states::Processed;
 use plan::Plan;
@@ -135,12 +135,12 @@ impl OperatorTranslator for JoinTranslator {
                     store,
                 ),
                 options:  None,
-                format:   operator::formats::DataFormat::NQuads,
+                format:   store.default_target.data_format.clone(),
             };
 
             extended_plan
                 .serialize(serializer)?
-                .sink(&Target::default())?;
+                .sink(&store.default_target)?;
         }
         Ok(())
     }
@@ -1,5 +1,6 @@
 pub mod error;
 mod extend;
+mod gather;
 mod join;
 mod serializer;
 mod source;
@@ -9,8 +10,8 @@ use std::cell::RefMut;
 use std::path::Path;
 
 use extend::ExtendOperatorTranslator;
+use gather::GatherOperatorTranslator;
 use join::JoinTranslator;
-use operator::Target;
 use plan::states::Processed;
 use plan::Plan;
 use serializer::SerializerOperatorTranslator;
@@ -18,9 +19,11 @@ use store::SearchStore;
 
 use super::error::NewRMLTranslationResult;
 use super::rml_model::v2::core::TriplesMap;
+use super::rml_model::v2::TermMapEnum;
 use super::rml_model::Document;
 use crate::new_rml::error::NewRMLTranslationError;
 use crate::new_rml::extractors::io::parse_file;
+use crate::options::TranslationOptions;
 use crate::LanguageTranslator;
 
 pub trait OperatorTranslator {
@@ -44,17 +47,25 @@ pub trait OperatorTranslator {
 pub struct NewRMLDocumentTranslator {}
 
 impl LanguageTranslator<&Path> for NewRMLDocumentTranslator {
-    fn translate_to_plan(path: &Path) -> crate::LanguageTranslateResult {
+    fn translate_to_plan_with_options(
+        path: &Path,
+        options: &TranslationOptions,
+    ) -> crate::LanguageTranslateResult {
         let document = parse_file(path.to_path_buf())
             .map_err(|err| NewRMLTranslationError::ParseError(err.into()))?;
-        NewRMLDocumentTranslator::translate_to_plan(document)
+        NewRMLDocumentTranslator::translate_to_plan_with_options(
+            document, options,
+        )
     }
 }
 
 impl LanguageTranslator<Document> for NewRMLDocumentTranslator {
-    fn translate_to_plan(
+    fn translate_to_plan_with_options(
         mut model: Document,
+        options: &TranslationOptions,
     ) -> crate::LanguageTranslateResult {
+        apply_document_options(&mut model, options);
+
         //preprocessing to change all logical sources to logical views
         for tm in model.triples_maps.iter_mut() {
             tm.transform_to_logical_view().map_err(|err| {
@@ -63,7 +74,8 @@ impl LanguageTranslator<Document> for NewRMLDocumentTranslator {
         }
 
         log::debug!("{:#?}", model);
-        let search_store = SearchStore::from_document(&model)?;
+        let search_store =
+            SearchStore::from_document(&model, options.default_target())?;
 
         for tm in model.triples_maps.iter() {
             JoinTranslator::translate_with_store(&search_store, tm)?;
@@ -90,6 +102,22 @@ impl LanguageTranslator<Document> for NewRMLDocumentTranslator {
                 .unwrap()
                 .borrow_mut();
 
+            // Gather maps aggregate the mapping tuples, so they are
+            // translated in their own branch from the sourced plan
+            let (tm_vec, gather_tm_vec): (Vec<_>, Vec<_>) =
+                tm_vec.iter().map(|tm| tm.split_gather_maps()).unzip();
+            for gather_tm in gather_tm_vec.iter().flatten() {
+                plan_with_gather_operators(&search_store, &plan, gather_tm)?;
+            }
+
+            let tm_vec: Vec<_> = tm_vec
+                .iter()
+                .filter(|tm| tm.generates_triples_without_joins())
+                .collect();
+            if tm_vec.is_empty() {
+                continue;
+            }
+
             for tm in &tm_vec {
                 let extended_plan =
                     plan_with_extend_operator(&search_store, &mut plan, tm)?;
@@ -104,7 +132,7 @@ impl LanguageTranslator<Document> for NewRMLDocumentTranslator {
 
             plan.serialize(serializer_operator)
                 .map_err(Into::<NewRMLTranslationError>::into)?
-                .sink(&Target::default())
+                .sink(&search_store.default_target)
                 .map_err(Into::<NewRMLTranslationError>::into)?;
         }
 
@@ -112,6 +140,54 @@ impl LanguageTranslator<Document> for NewRMLDocumentTranslator {
     }
 }
 
+/// Overrides the base IRI of the triples maps and removes their graph maps
+/// if named graphs are not generated.
+fn apply_document_options(model: &mut Document, options: &TranslationOptions) {
+    for tm in model.triples_maps.iter_mut() {
+        if let Some(base_iri) = &options.base_iri {
+            tm.base_iri = base_iri.clone();
+        }
+
+        if !options.emits_named_graphs() {
+            if let TermMapEnum::SubjectMap(sm) = &mut tm.subject_map {
+                sm.graph_maps.clear();
+            }
+            tm.predicate_object_map_vec
+                .iter_mut()
+                .for_each(|pom| pom.graph_map_vec.clear());
+        }
+    }
+}
+
+/// Adds a branch to the `plan` generating the triples of the gather maps in
+/// the `gather_tm`.
+fn plan_with_gather_operators(
+    search_store: &SearchStore<'_>,
+    plan: &Plan<Processed>,
+    gather_tm: &TriplesMap,
+) -> Result<(), NewRMLTranslationError> {
+    let (extend_op, aggregate) = GatherOperatorTranslator::translate_with_store(
+        search_store,
+        gather_tm,
+    )?;
+    let serializer_operator =
+        SerializerOperatorTranslator::translate_with_store(
+            search_store,
+            &vec![gather_tm],
+        )?;
+
+    plan.clone()
+        .apply(&extend_op.into(), "ExtendOperator")
+        .map_err(Into::<NewRMLTranslationError>::into)?
+        .aggregate(aggregate)
+        .map_err(Into::<NewRMLTranslationError>::into)?
+        .serialize(serializer_operator)
+        .map_err(Into::<NewRMLTranslationError>::into)?
+        .sink(&search_store.default_target)
+        .map_err(Into::<NewRMLTranslationError>::into)?;
+    Ok(())
+}
+
 fn plan_with_extend_operator(
     search_store: &SearchStore<'_>,
     plan: &mut RefMut<Plan<Processed>>,
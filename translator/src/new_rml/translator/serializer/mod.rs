@@ -107,7 +107,7 @@ impl<'a> OperatorTranslator for SerializerOperatorTranslator<'a> {
         Ok(Serializer {
             template: graph_pattern.into_iter().collect::<Vec<_>>().join("\n"),
             options:  None,
-            format:   operator::formats::DataFormat::NQuads,
+            format:   store.default_target.data_format.clone(),
         })
     }
 }
@@ -1,5 +1,5 @@
 use operator::formats::ReferenceFormulation;
-use operator::Field as OperatorField;
+use operator::{Field as OperatorField, Unnest, UnnestType};
 use sophia_turtle::serializer::nt;
 
 use crate::new_rml::error::{NewRMLTranslationError, NewRMLTranslationResult};
@@ -15,22 +15,15 @@ pub fn translate_rml_field(
     log::debug!("Translating field: {:?}", field);
     match &field.kind {
         RMLFieldKind::Iterable(rmliterable) => {
-            let value = &rmliterable.iterator;
-            let ref_form: ReferenceFormulation = rmliterable
-                .reference_formulation
-                .clone()
-                .and_then(|rmliter_ref_form| rmliter_ref_form.try_into().ok())
-                .unwrap_or_else(|| ref_form.clone());
-
-            let inner_fields =
-                translate_rml_field_vec(&field.fields, ref_form.clone())?;
+            // The nested values of an iterable field are exploded with an
+            // unnest operator afterwards (see [translate_unnest_vec])
             Ok(OperatorField {
-                alias: field.name.clone(),
-                constant: None,
-                iterator: value.clone(),
-                reference: None,
+                alias:                 field.name.clone(),
+                reference:             rmliterable.iterator.clone(),
+                constant:              None,
+                iterator:              None,
                 reference_formulation: ref_form,
-                inner_fields,
+                inner_fields:          vec![],
             })
         }
         RMLFieldKind::Expression(expression_map) => {
@@ -91,3 +84,126 @@ pub fn translate_rml_field_vec(
         },
     )
 }
+
+/// Translates the nested iterable fields into [Unnest] configs.
+///
+/// The configs are ordered such that the unnest config of a parent iterable
+/// field comes before the configs of its nested iterable fields.
+/// Nested fields are named as `<parent name>.<field name>` and the index of
+/// the nested value is available as `<parent name>.#`.
+pub fn translate_unnest_vec(
+    fields: &[RMLField],
+    ref_form: ReferenceFormulation,
+) -> NewRMLTranslationResult<Vec<Unnest>> {
+    let mut result = Vec::new();
+    for field in fields {
+        collect_unnest(field, None, &ref_form, &mut result)?;
+    }
+    Ok(result)
+}
+
+fn collect_unnest(
+    field: &RMLField,
+    parent: Option<(&str, &RMLFieldKind)>,
+    ref_form: &ReferenceFormulation,
+    result: &mut Vec<Unnest>,
+) -> NewRMLTranslationResult<()> {
+    let name = match parent {
+        Some((parent_name, _)) => format!("{}.{}", parent_name, field.name),
+        None => field.name.clone(),
+    };
+
+    let inner_ref_form = match &field.kind {
+        RMLFieldKind::Iterable(rmliterable) => {
+            let iter_ref_form: ReferenceFormulation = rmliterable
+                .reference_formulation
+                .clone()
+                .and_then(|rmliter_ref_form| rmliter_ref_form.try_into().ok())
+                .unwrap_or_else(|| ref_form.clone());
+
+            // An iterable nested in an expression field iterates over the
+            // value of the expression field (e.g. JSON values inside a CSV
+            // column). Otherwise, the iterable field's own attribute
+            // already contains the nested values.
+            let (attribute, iterator) = match parent {
+                Some((parent_name, RMLFieldKind::Expression(_))) => {
+                    (parent_name.to_string(), rmliterable.iterator.clone())
+                }
+                _ => (name.clone(), None),
+            };
+
+            result.push(Unnest {
+                attribute,
+                alias: name.clone(),
+                iterator,
+                reference_formulation: iter_ref_form.clone(),
+                fields: translate_rml_field_vec(
+                    &field.fields,
+                    iter_ref_form.clone(),
+                )?,
+                ordinality: Some(format!("{}.#", name)),
+                unnest_type: UnnestType::Inner,
+            });
+            iter_ref_form
+        }
+        RMLFieldKind::Expression(_) => ref_form.clone(),
+    };
+
+    for nested_field in &field.fields {
+        collect_unnest(
+            nested_field,
+            Some((&name, &field.kind)),
+            &inner_ref_form,
+            result,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::new_rml::rml_model::v2::core::RMLIterable;
+
+    #[test]
+    fn nested_iterable_unnest_test() -> NewRMLTranslationResult<()> {
+        let items = RMLField {
+            name:   "item".to_string(),
+            kind:   RMLFieldKind::Iterable(RMLIterable {
+                iterator:              Some("$.items[*]".to_string()),
+                reference_formulation: None,
+            }),
+            fields: vec![
+                RMLField::from_ref_str("$.type"),
+                RMLField {
+                    name:   "tag".to_string(),
+                    kind:   RMLFieldKind::Iterable(RMLIterable {
+                        iterator:              Some("$.tags[*]".to_string()),
+                        reference_formulation: None,
+                    }),
+                    fields: vec![],
+                },
+            ],
+        };
+
+        let source_fields = translate_rml_field_vec(
+            &[items.clone()],
+            ReferenceFormulation::JSONPath,
+        )?;
+        assert_eq!(source_fields.len(), 1);
+        assert_eq!(source_fields[0].reference.as_deref(), Some("$.items[*]"));
+        assert!(source_fields[0].inner_fields.is_empty());
+
+        let unnests =
+            translate_unnest_vec(&[items], ReferenceFormulation::JSONPath)?;
+        let attributes: Vec<_> =
+            unnests.iter().map(|unnest| unnest.attribute.as_str()).collect();
+        assert_eq!(attributes, vec!["item", "item.tag"]);
+        assert_eq!(unnests[0].ordinality.as_deref(), Some("item.#"));
+        assert_eq!(unnests[0].fields.len(), 2);
+        assert!(unnests.iter().all(|unnest| unnest.iterator.is_none()));
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 mod fields;
 mod kind;
+pub mod unnest;
 
 use std::collections::HashMap;
 
@@ -0,0 +1,37 @@
+use operator::{formats, Unnest};
+
+use super::fields::translate_unnest_vec;
+use crate::new_rml::error::NewRMLTranslationResult;
+use crate::new_rml::rml_model::v2::core::AbstractLogicalSourceEnum;
+use crate::new_rml::translator::OperatorTranslator;
+
+/// Translates the nested iterable fields of a logical view into [Unnest]
+/// configs which have to be applied right after the source operator.
+#[derive(Debug, Clone)]
+pub struct UnnestTranslator {}
+
+impl OperatorTranslator for UnnestTranslator {
+    type Input = AbstractLogicalSourceEnum;
+
+    type Output = Vec<Unnest>;
+
+    fn translate(
+        abs_ls: &Self::Input,
+    ) -> NewRMLTranslationResult<Self::Output> {
+        let logical_view = match abs_ls {
+            AbstractLogicalSourceEnum::LogicalSource(_) => return Ok(vec![]),
+            AbstractLogicalSourceEnum::LogicalView(logical_view) => {
+                logical_view
+            }
+        };
+
+        let rml_iterable = &abs_ls.get_iterable();
+        let mut reference_formulation = formats::ReferenceFormulation::CSVRows;
+
+        if let Some(ref_form) = &rml_iterable.reference_formulation {
+            reference_formulation = ref_form.try_into()?;
+        }
+
+        translate_unnest_vec(&logical_view.fields, reference_formulation)
+    }
+}
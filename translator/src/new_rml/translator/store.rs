@@ -1,11 +1,13 @@
 use std::collections::{HashMap, HashSet};
 
+use operator::Target;
 use plan::data_type::RcRefCellPlan;
 use plan::states::{Init, Processed};
 use plan::Plan;
 use sophia_term::RcTerm;
 use uuid::Uuid;
 
+use super::source::unnest::UnnestTranslator;
 use super::source::AbstractLogicalSourceTranslator;
 use super::OperatorTranslator;
 use crate::new_rml::error::NewRMLTranslationResult;
@@ -31,6 +33,9 @@ pub struct SearchStore<'a> {
     pub om_search_map:          HashMap<RcTerm, &'a TermMapEnum>,
     pub gm_search_map:          HashMap<RcTerm, &'a TermMapEnum>,
     pub tm_search_map:          HashMap<RcTerm, &'a TriplesMap>,
+    /// Target of the serialized output, the logical targets of the
+    /// document are not supported yet
+    pub default_target:         Target,
 }
 
 impl SearchStore<'_> {
@@ -64,6 +69,7 @@ impl SearchStore<'_> {
 
     pub fn from_document(
         document: &Document,
+        default_target: Target,
     ) -> NewRMLTranslationResult<SearchStore<'_>> {
         let mut tm_search_map = HashMap::new();
         let mut abs_ls_search_map = HashMap::new();
@@ -188,6 +194,7 @@ impl SearchStore<'_> {
             root_plan: Some(root_plan),
             reference_attr_map: HashMap::new(),
             tm_id_join_map: HashMap::new(),
+            default_target,
         })
     }
 }
@@ -199,7 +206,13 @@ fn create_ls_id_sourced_plan_map(
     let mut ls_id_sourced_plan_map = HashMap::new();
     for abs_ls in abs_ls_search_map.values().copied() {
         let source = AbstractLogicalSourceTranslator::translate(abs_ls)?;
-        let sourced_plan: RcRefCellPlan<Processed> = plan.source(source).into();
+        let mut processed_plan = plan.source(source);
+
+        for unnest in UnnestTranslator::translate(abs_ls)? {
+            processed_plan =
+                processed_plan.apply(&unnest.into(), "UnnestOperator")?;
+        }
+        let sourced_plan: RcRefCellPlan<Processed> = processed_plan.into();
 
         ls_id_sourced_plan_map.insert(abs_ls.get_identifier(), sourced_plan);
     }
@@ -0,0 +1,376 @@
+//!
+//! Options to configure the translation of mapping documents to mapping
+//! plans, shared by the translators of every mapping language.
+//!
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::str::FromStr;
+
+use operator::formats::DataFormat;
+use operator::{IOType, Target};
+use vocab::ToString;
+
+use crate::detect::MappingLanguage;
+
+/// Options for the translation of a mapping document.
+///
+/// The output options are defaults, they only apply to the generated output
+/// for which the mapping document does not declare a logical target.
+#[derive(Debug, Clone)]
+pub struct TranslationOptions {
+    /// Inserts a [Limit](operator::Limit) operator before every serializer
+    /// to only generate a sample of the output
+    pub sample_limit:  Option<usize>,
+    /// Translates the document with the given mapping language instead of
+    /// detecting it
+    pub language:      Option<MappingLanguage>,
+    /// Overrides the base IRI declared in RML documents, against which
+    /// relative IRIs are resolved
+    pub base_iri:      Option<String>,
+    pub serialization: SerializationFormat,
+    pub target:        OutputTarget,
+    pub compression:   Option<Compression>,
+    /// Generates the quads of named graphs, otherwise every triple is
+    /// generated in the default graph
+    pub named_graphs:  bool,
+    /// Values of the `${name}` placeholders in the configuration of the
+    /// sources and targets, resolved after the translation
+    pub params:        HashMap<String, String>,
+}
+
+impl Default for TranslationOptions {
+    fn default() -> Self {
+        Self {
+            sample_limit:  None,
+            language:      None,
+            base_iri:      None,
+            serialization: SerializationFormat::NQuads,
+            target:        OutputTarget::StdOut,
+            compression:   None,
+            named_graphs:  true,
+            params:        HashMap::new(),
+        }
+    }
+}
+
+impl TranslationOptions {
+    /// Keys of the options which can be set with [TranslationOptions::set].
+    pub const KEYS: [&'static str; 9] = [
+        "sample-limit",
+        "language",
+        "base-iri",
+        "serialization",
+        "target",
+        "compression",
+        "named-graphs",
+        "param",
+        "param-file",
+    ];
+
+    /// Sets the option with the given `key` from its string `value`, as
+    /// given to the language bindings. Underscores in the key are treated
+    /// as dashes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if the key is unknown or the value is
+    /// invalid for the option.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key.replace('_', "-").as_str() {
+            "sample-limit" => {
+                self.sample_limit = Some(value.parse().map_err(|_| {
+                    format!("invalid sample limit {}", value)
+                })?);
+            }
+            "language" => self.language = Some(value.parse()?),
+            "base-iri" => self.base_iri = Some(value.to_string()),
+            "serialization" => self.serialization = value.parse()?,
+            "target" => self.target = value.parse()?,
+            "compression" => self.compression = Some(value.parse()?),
+            "named-graphs" => {
+                self.named_graphs = match value.to_lowercase().as_str() {
+                    "true" => true,
+                    "false" => false,
+                    _ => {
+                        return Err(format!(
+                            "invalid boolean {} for named-graphs",
+                            value
+                        ))
+                    }
+                };
+            }
+            "param" => {
+                let (name, param) = parse_param(value)?;
+                self.params.insert(name, param);
+            }
+            "param-file" => {
+                let content = fs::read_to_string(value).map_err(|err| {
+                    format!("cannot read parameter file {}: {}", value, err)
+                })?;
+                self.params.extend(parse_param_file(&content)?);
+            }
+            _ => {
+                return Err(format!(
+                    "unknown translation option {}, expected one of: {}",
+                    key,
+                    Self::KEYS.join(", ")
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if quads of named graphs are generated, which is not possible
+    /// if the output is serialized as N-Triples.
+    pub fn emits_named_graphs(&self) -> bool {
+        self.named_graphs
+            && self.serialization != SerializationFormat::NTriples
+    }
+
+    /// Gets the target of the output for which the mapping document does
+    /// not declare a logical target.
+    pub fn default_target(&self) -> Target {
+        let mut configuration = HashMap::new();
+        let target_type = match &self.target {
+            OutputTarget::StdOut => IOType::StdOut,
+            OutputTarget::File(path) => {
+                configuration.insert("path".to_string(), path.clone());
+                IOType::File
+            }
+        };
+
+        if let Some(compression) = self.compression {
+            configuration
+                .insert("compression".to_string(), compression.to_iri());
+        }
+
+        Target {
+            configuration,
+            target_type,
+            data_format: self.serialization.into(),
+        }
+    }
+}
+
+/// Parses a parameter given as `name=value`, surrounding whitespace of the
+/// name and value is trimmed.
+///
+/// # Errors
+///
+/// Returns an error message if there is no `=` or the name is empty.
+pub fn parse_param(param: &str) -> Result<(String, String), String> {
+    match param.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("invalid parameter {}, expected name=value", param)),
+    }
+}
+
+/// Parses the content of a parameter file with a `name=value` parameter on
+/// every line. Empty lines and lines starting with `#` are ignored.
+///
+/// # Errors
+///
+/// Returns an error message with the line number of the first invalid
+/// parameter.
+pub fn parse_param_file(
+    content: &str,
+) -> Result<HashMap<String, String>, String> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(idx, line)| {
+            parse_param(line)
+                .map_err(|err| format!("line {}: {}", idx + 1, err))
+        })
+        .collect()
+}
+
+/// RDF serialization formats supported by the serializer operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SerializationFormat {
+    #[default]
+    NQuads,
+    NTriples,
+}
+
+impl SerializationFormat {
+    pub const VALUES: [&'static str; 2] = ["nquads", "ntriples"];
+}
+
+impl From<SerializationFormat> for DataFormat {
+    fn from(format: SerializationFormat) -> Self {
+        match format {
+            SerializationFormat::NQuads => DataFormat::NQuads,
+            SerializationFormat::NTriples => DataFormat::NTriples,
+        }
+    }
+}
+
+impl FromStr for SerializationFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "nquads" => Ok(SerializationFormat::NQuads),
+            "ntriples" => Ok(SerializationFormat::NTriples),
+            _ => {
+                Err(format!(
+                    "unknown serialization format {}, expected one of: {}",
+                    s,
+                    SerializationFormat::VALUES.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+/// Target to which the generated output is written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum OutputTarget {
+    #[default]
+    StdOut,
+    /// File at the given path
+    File(String),
+}
+
+impl Display for OutputTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputTarget::StdOut => write!(f, "stdout"),
+            OutputTarget::File(path) => write!(f, "{}", path),
+        }
+    }
+}
+
+impl FromStr for OutputTarget {
+    type Err = String;
+
+    /// Parses `stdout` or `-` as [OutputTarget::StdOut], anything else as
+    /// the path of a file.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("target cannot be empty".to_string()),
+            "-" | "stdout" => Ok(OutputTarget::StdOut),
+            path => Ok(OutputTarget::File(path.to_string())),
+        }
+    }
+}
+
+/// Compression of the generated output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    GZip,
+    Zip,
+}
+
+impl Compression {
+    pub const VALUES: [&'static str; 2] = ["gzip", "zip"];
+
+    /// Gets the IRI of the compression in the
+    /// [RML compression](http://semweb.mmlab.be/ns/rml-compression#)
+    /// vocabulary.
+    pub fn to_iri(self) -> String {
+        match self {
+            Compression::GZip => vocab::comp::CLASS::GZIP.to_string(),
+            Compression::Zip => vocab::comp::CLASS::ZIP.to_string(),
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gzip" => Ok(Compression::GZip),
+            "zip" => Ok(Compression::Zip),
+            _ => {
+                Err(format!(
+                    "unknown compression {}, expected one of: {}",
+                    s,
+                    Compression::VALUES.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_options_test() {
+        let mut options = TranslationOptions::default();
+        options.set("base_iri", "http://example.com/").unwrap();
+        options.set("serialization", "NTriples").unwrap();
+        options.set("target", "output.nt").unwrap();
+        options.set("compression", "gzip").unwrap();
+        options.set("named-graphs", "False").unwrap();
+
+        assert_eq!(options.base_iri.as_deref(), Some("http://example.com/"));
+        assert_eq!(options.serialization, SerializationFormat::NTriples);
+        assert_eq!(options.target, OutputTarget::File("output.nt".into()));
+        assert_eq!(options.compression, Some(Compression::GZip));
+        assert!(!options.named_graphs);
+
+        assert!(options.set("named-graphs", "maybe").is_err());
+        assert!(options.set("serialization", "turtle").is_err());
+        assert!(options.set("unknown", "value").is_err());
+    }
+
+    #[test]
+    fn set_params_test() {
+        let mut options = TranslationOptions::default();
+        options.set("param", "broker=localhost:9092").unwrap();
+        options.set("param", " db = jdbc:mysql://host/db?a=b").unwrap();
+
+        assert_eq!(options.params["broker"], "localhost:9092");
+        assert_eq!(options.params["db"], "jdbc:mysql://host/db?a=b");
+        assert!(options.set("param", "=value").is_err());
+        assert!(options.set("param", "broker").is_err());
+        assert!(options.set("param-file", "missing.params").is_err());
+    }
+
+    #[test]
+    fn parse_param_file_test() {
+        let content = "# Paths\ndata_dir=/data\n\n  topic = people\n";
+        let params = parse_param_file(content).unwrap();
+
+        assert_eq!(params.len(), 2);
+        assert_eq!(params["data_dir"], "/data");
+        assert_eq!(params["topic"], "people");
+
+        let err = parse_param_file("a=b\ninvalid").unwrap_err();
+        assert!(err.starts_with("line 2:"));
+    }
+
+    #[test]
+    fn default_target_test() {
+        let options = TranslationOptions {
+            target: OutputTarget::File("output.nq.gz".to_string()),
+            compression: Some(Compression::GZip),
+            ..Default::default()
+        };
+        let target = options.default_target();
+
+        assert_eq!(target.target_type, IOType::File);
+        assert_eq!(target.data_format, DataFormat::NQuads);
+        assert_eq!(
+            target.configuration.get("path").map(String::as_str),
+            Some("output.nq.gz")
+        );
+        assert_eq!(
+            target.configuration.get("compression").map(String::as_str),
+            Some("http://semweb.mmlab.be/ns/rml-compression#gzip")
+        );
+
+        let target = TranslationOptions::default().default_target();
+        assert_eq!(target, Target::default());
+    }
+}
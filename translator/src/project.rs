@@ -0,0 +1,337 @@
+//!
+//! Mapping projects of RML documents split across several Turtle files,
+//! e.g. shared logical sources, triples maps per entity and function
+//! declarations, which are merged into one RDF graph before translation.
+//!
+//! The documents imported with `owl:imports` are followed transitively.
+//! Only imports of local files are followed, either as `file:` IRIs or as
+//! IRIs relative to the base IRI of the importing document.
+//!
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::debug;
+use sophia_api::graph::{Graph, MutableGraph};
+use sophia_api::term::{BnodeId, FromTerm, SimpleTerm, Term};
+use sophia_api::triple::Triple;
+use sophia_api::MownStr;
+use sophia_inmem::graph::FastGraph;
+use vocab::ToString;
+
+use crate::detect::{self, DetectedMapping, MappingLanguage, ParsedMapping};
+use crate::error::{TranslationError, TranslationErrorKind};
+use crate::rml::error::RMLTranslationError;
+use crate::rml::parser::extractors::error::ParseError;
+use crate::rml::parser::extractors::io::{
+    extract_base_iri, load_graph_str_with_base,
+};
+
+/// Mapping documents of several files merged into one RDF graph.
+#[derive(Debug, Clone, Default)]
+pub struct MappingProject {
+    /// Files of the project in the order in which they are merged
+    pub files:    Vec<PathBuf>,
+    /// Triples of every file, parsed with the prefixes and base IRI of the
+    /// file itself
+    pub graph:    FastGraph,
+    /// Base IRI declared by the first file
+    pub base_iri: Option<String>,
+}
+
+impl MappingProject {
+    /// Loads the mapping project consisting of the files at the given
+    /// `paths` and the files they import.
+    ///
+    /// Every file is parsed on its own, with the prefixes it declares and
+    /// its base IRI, or its own `file:` IRI if it declares none. The blank
+    /// nodes of different files are kept apart, even if they have the same
+    /// label. Every file is merged once, before the files it imports.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or is not valid Turtle.
+    pub fn load(
+        paths: &[PathBuf],
+    ) -> Result<MappingProject, TranslationError> {
+        let mut project = MappingProject::default();
+        let mut visited = HashSet::new();
+        for path in paths {
+            project.add_file(path, &mut visited)?;
+        }
+
+        Ok(project)
+    }
+
+    /// Detects the RML version of the project, unless it is forced with
+    /// the given `language`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for ShExML, which cannot be split across files, or
+    /// if the project does not use any of the RML vocabularies.
+    pub fn detect(
+        self,
+        language: Option<MappingLanguage>,
+    ) -> Result<DetectedMapping, TranslationError> {
+        let parsed = match language {
+            None => return detect::detect_rml_graph(self.graph),
+            Some(MappingLanguage::RML) => ParsedMapping::RML(self.graph),
+            Some(MappingLanguage::NewRML) => ParsedMapping::NewRML(self.graph),
+            Some(MappingLanguage::ShExML) => {
+                return Err(RMLTranslationError::from(ParseError::GenericError(
+                    "mapping projects only consist of RML documents"
+                        .to_string(),
+                ))
+                .into());
+            }
+        };
+
+        Ok(DetectedMapping {
+            parsed,
+            confidence: 1.0,
+        })
+    }
+
+    fn add_file(
+        &mut self,
+        path: &Path,
+        visited: &mut HashSet<PathBuf>,
+    ) -> Result<(), TranslationError> {
+        let path = path.canonicalize().map_err(|error| {
+            file_error(TranslationErrorKind::FileStdError {
+                file: path.to_path_buf(),
+                error,
+            })
+        })?;
+        if !visited.insert(path.clone()) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|error| {
+            file_error(TranslationErrorKind::FileStdError {
+                file: path.clone(),
+                error,
+            })
+        })?;
+        debug!(
+            "Merging {} into the mapping project",
+            path.to_string_lossy()
+        );
+
+        // Documents without a base IRI resolve their relative IRIs against
+        // the file itself
+        let declared_base_iri =
+            content.lines().filter_map(extract_base_iri).next();
+        if self.files.is_empty() {
+            self.base_iri = declared_base_iri.clone();
+        }
+        let base_iri = declared_base_iri.unwrap_or_else(|| file_iri(&path));
+
+        // The base IRI is given to the parser instead of declared in the
+        // content, to keep the lines of parse errors as in the file
+        let graph =
+            load_graph_str_with_base(&content, &base_iri).map_err(|err| {
+                file_error(TranslationErrorKind::FileLanguageError {
+                    file:          path.clone(),
+                    lang_err_kind: err.into(),
+                })
+            })?;
+        let imports = find_imports(&path, &graph, &base_iri);
+        self.merge_graph(&graph);
+        self.files.push(path);
+
+        for import in imports {
+            self.add_file(&import, visited)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the triples of the `graph` of the next file to the project,
+    /// with its blank nodes relabeled to the file.
+    fn merge_graph(&mut self, graph: &FastGraph) {
+        let file_idx = self.files.len();
+        let relabel = |term: SimpleTerm| -> SimpleTerm<'static> {
+            let label = term
+                .bnode_id()
+                .map(|label| format!("f{}_{}", file_idx, label.as_str()));
+            match label {
+                Some(label) => {
+                    SimpleTerm::BlankNode(BnodeId::new_unchecked(
+                        MownStr::from(label),
+                    ))
+                }
+                None => SimpleTerm::from_term(term),
+            }
+        };
+
+        for triple in graph.triples().filter_map(|triple| triple.ok()) {
+            let [s, p, o] = triple.to_spo().map(|term| term.as_simple());
+            self.graph
+                .insert(relabel(s), relabel(p), relabel(o))
+                .expect("the in-memory graph has room for the triples");
+        }
+    }
+}
+
+fn file_error(kind: TranslationErrorKind) -> TranslationError {
+    TranslationError { kind }
+}
+
+/// Finds the paths of the local files imported by the `graph` of the
+/// document at `path` with the given `base_iri`.
+fn find_imports(
+    path: &Path,
+    graph: &FastGraph,
+    base_iri: &str,
+) -> Vec<PathBuf> {
+    let owl_imports = vocab::owl::PROPERTY::IMPORTS.to_string();
+    let mut imports = Vec::new();
+    for triple in graph.triples().filter_map(|triple| triple.ok()) {
+        let is_import = triple
+            .p()
+            .iri()
+            .is_some_and(|iri| iri.as_str() == owl_imports);
+        let Some(import) = triple.o().iri().filter(|_| is_import) else {
+            continue;
+        };
+
+        match import_path(import.as_str(), path, base_iri) {
+            Some(import_path) => imports.push(import_path),
+            None => {
+                debug!(
+                    "Skipped import of {} which is not a local document",
+                    import.as_str()
+                );
+            }
+        }
+    }
+
+    // Merge the imports in a deterministic order
+    imports.sort();
+    imports.dedup();
+    imports
+}
+
+/// Resolves the `import` IRI of the document at `path` with the given
+/// `base_iri` to the path of a local file.
+fn import_path(import: &str, path: &Path, base_iri: &str) -> Option<PathBuf> {
+    if let Some(file) = import.strip_prefix("file://") {
+        return Some(PathBuf::from(file.replace("%20", " ")));
+    }
+
+    let base_dir = &base_iri[..=base_iri.rfind('/')?];
+    let relative = import.strip_prefix(base_dir)?;
+    Some(path.parent()?.join(relative.replace("%20", " ")))
+}
+
+fn file_iri(path: &Path) -> String {
+    format!("file://{}", path.to_string_lossy().replace(' ', "%20"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::TranslationOptions;
+    use crate::test_case;
+
+    fn has_iri(project: &MappingProject, iri: &str) -> bool {
+        project.graph.triples().filter_map(|triple| triple.ok()).any(
+            |triple| {
+                triple
+                    .to_spo()
+                    .iter()
+                    .any(|term| term.iri().is_some_and(|t| t.as_str() == iri))
+            },
+        )
+    }
+
+    fn translate_project(project: MappingProject) -> String {
+        let base_iri = project.base_iri.clone();
+        let detected = project.detect(None).unwrap();
+        assert_eq!(detected.parsed.language(), MappingLanguage::RML);
+
+        let plan = detect::translate_parsed_with_base(
+            detected.parsed,
+            base_iri,
+            &TranslationOptions::default(),
+        )
+        .unwrap();
+        plan.to_json_string().unwrap()
+    }
+
+    #[test]
+    fn load_project_test() {
+        let path = PathBuf::from(test_case!("rml/project/airports.ttl"));
+        let project = MappingProject::load(&[path]).unwrap();
+
+        let file_names: Vec<_> = project
+            .files
+            .iter()
+            .map(|file| file.file_name().unwrap().to_string_lossy())
+            .collect();
+        assert_eq!(file_names, ["airports.ttl", "positions.ttl"]);
+        assert_eq!(project.base_iri.as_deref(), Some("http://example.com/ns#"));
+        assert!(has_iri(&project, "http://example.com/ns#AirportMapping"));
+        assert!(has_iri(&project, "http://example.com/ns#PositionMapping"));
+    }
+
+    #[test]
+    fn translate_project_test() {
+        let path = PathBuf::from(test_case!("rml/project/airports.ttl"));
+        let project = MappingProject::load(&[path]).unwrap();
+        let plan_json = translate_project(project);
+
+        assert!(plan_json.contains("Airport.csv"));
+        assert!(plan_json.contains("Positions.csv"));
+        assert!(plan_json.contains("JoinOp"));
+    }
+
+    #[test]
+    fn project_prefixes_test() {
+        let path =
+            PathBuf::from(test_case!("rml/project/prefixes/people.ttl"));
+        let project = MappingProject::load(&[path]).unwrap();
+
+        // Both files declare the prefix ex: for a different namespace
+        assert!(has_iri(&project, "http://example.com/people#Person"));
+        assert!(has_iri(&project, "http://example.com/places#Place"));
+        assert!(!has_iri(&project, "http://example.com/people#Place"));
+
+        // Both files label their logical source _:source
+        let sources: HashSet<_> = project
+            .graph
+            .triples()
+            .filter_map(|triple| triple.ok())
+            .filter(|triple| {
+                triple.p().iri().is_some_and(|iri| {
+                    iri.as_str() == "http://semweb.mmlab.be/ns/rml#source"
+                })
+            })
+            .map(|triple| triple.s().bnode_id().unwrap().to_string())
+            .collect();
+        assert_eq!(sources.len(), 2);
+
+        let plan_json = translate_project(project);
+        assert!(plan_json.contains("People.csv"));
+        assert!(plan_json.contains("Places.csv"));
+        assert!(plan_json.contains("http://example.com/places#name"));
+    }
+
+    #[test]
+    fn missing_import_test() {
+        let path =
+            PathBuf::from(test_case!("rml/project/missing_import.ttl"));
+        assert!(MappingProject::load(&[path]).is_err());
+    }
+
+    #[test]
+    fn syntax_error_line_test() {
+        let path =
+            PathBuf::from(test_case!("rml/project/invalid_syntax.ttl"));
+        let err = MappingProject::load(&[path]).unwrap_err();
+
+        // The missing ';' of line 5 is found at the start of line 6
+        assert!(format!("{:?}", err).contains("on line 6 at position 3"));
+    }
+}
@@ -28,7 +28,9 @@ use self::operators::extend::*;
 use self::operators::fragment::FragmentTranslator;
 use self::operators::serializer::{self, translate_serializer_op};
 use self::util::generate_lt_quads_from_spo;
+use crate::options::TranslationOptions;
 use crate::rml::parser::extractors::io::parse_file;
+use crate::rml::parser::rml_model::source_target::DEFAULT_LOGICAL_TARGET_ID;
 use crate::rml::types::SearchMap;
 use crate::rml::util::{
     generate_logtarget_map, generate_lt_quads_from_doc, generate_variable_map,
@@ -38,21 +40,37 @@ use crate::{LanguageTranslator, OperatorTranslator};
 pub struct OptimizedRMLDocumentTranslator;
 
 impl LanguageTranslator<&Path> for OptimizedRMLDocumentTranslator {
-    fn translate_to_plan(path: &Path) -> crate::LanguageTranslateResult {
+    fn translate_to_plan_with_options(
+        path: &Path,
+        options: &TranslationOptions,
+    ) -> crate::LanguageTranslateResult {
         let doc = parse_file(path.to_path_buf())?;
-        Self::translate_to_plan(doc)
+        Self::translate_to_plan_with_options(doc, options)
     }
 }
 
 impl LanguageTranslator<Document> for OptimizedRMLDocumentTranslator {
-    fn translate_to_plan(doc: Document) -> crate::LanguageTranslateResult {
-        let base_iri = doc.default_base_iri.clone();
+    fn translate_to_plan_with_options(
+        mut doc: Document,
+        options: &TranslationOptions,
+    ) -> crate::LanguageTranslateResult {
+        let base_iri = options
+            .base_iri
+            .clone()
+            .or_else(|| doc.default_base_iri.clone());
+        if !options.emits_named_graphs() {
+            remove_graph_maps(&mut doc);
+        }
         let mut plan = Plan::<()>::new();
 
         //For each triples maps, create a plan with source operator applied
         // Search dictionaries instantiations
         let variable_map = generate_variable_map(&doc);
-        let target_map = generate_logtarget_map(&doc);
+        let mut target_map = generate_logtarget_map(&doc);
+        // Output without a declared logical target goes to the default target
+        if let Some(target) = target_map.get_mut(DEFAULT_LOGICAL_TARGET_ID) {
+            *target = options.default_target();
+        }
         let lt_id_quad_map = generate_lt_quads_from_doc(&doc);
         let tm_sourced_pairs: Vec<_> = doc
             .triples_maps
@@ -153,6 +171,23 @@ impl LanguageTranslator<Document> for OptimizedRMLDocumentTranslator {
     }
 }
 
+/// Removes the graph maps of the `doc` so that every triple is generated in
+/// the default graph.
+fn remove_graph_maps(doc: &mut Document) {
+    for tm in doc.triples_maps.iter_mut() {
+        tm.subject_map.graph_maps.clear();
+        for pom in tm.po_maps.iter_mut() {
+            pom.graph_maps.clear();
+            pom.predicate_maps
+                .iter_mut()
+                .for_each(|pm| pm.graph_maps.clear());
+            pom.object_maps
+                .iter_mut()
+                .for_each(|om| om.graph_maps.clear());
+        }
+    }
+}
+
 fn partition_pom_join_nonjoin(
     poms: Vec<PredicateObjectMap>,
 ) -> (Vec<PredicateObjectMap>, Vec<PredicateObjectMap>) {
@@ -6,16 +6,31 @@ use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
 
+use sophia_api::parser::TripleParser;
+use sophia_api::prelude::Iri;
 use sophia_api::source::TripleSource;
 use sophia_inmem::graph::FastGraph;
-use sophia_turtle::parser::turtle;
+use sophia_turtle::parser::turtle::{self, TurtleParser};
 
 use super::error::ParseError;
 use super::triplesmap_extractor::{self, extract_triples_maps};
 use super::ExtractorResult;
 use crate::rml::parser::rml_model::{Document, TriplesMap};
 
-fn extract_base_iri(input: &str) -> Option<String> {
+/// Base IRI of the RML documents which do not declare their own `@base`.
+pub const DEFAULT_BASE_IRI: &str = "http://example.com/base/";
+
+/// Declares the [DEFAULT_BASE_IRI] in the `input_string` of an RML document
+/// without `@base`, so that its relative IRIs can be resolved.
+pub fn with_default_base(input_string: String) -> String {
+    if input_string.contains("@base") {
+        input_string
+    } else {
+        format!("@base <{}> .\n{}", DEFAULT_BASE_IRI, input_string)
+    }
+}
+
+pub(crate) fn extract_base_iri(input: &str) -> Option<String> {
     input
         .strip_prefix("@base")
         .map(|e| e[0..e.len() - 1].replace(['<', '>'], "").trim().to_string())
@@ -57,6 +72,36 @@ pub fn load_graph_str(input_str: &str) -> ExtractorResult<FastGraph> {
     }
 }
 
+/// Parse the input string to [sophia's in-memory graph](FastGraph), with
+/// the relative IRIs resolved against the `base_iri` unless the document
+/// declares its own `@base`.
+///
+/// # Error
+/// Returns an error if the `base_iri` is invalid or if something goes wrong
+/// with [sophia's turtle parsing](TurtleParser)
+pub fn load_graph_str_with_base(
+    input_str: &str,
+    base_iri: &str,
+) -> ExtractorResult<FastGraph> {
+    let base = Iri::new(base_iri.to_string()).map_err(|err| {
+        ParseError::GenericError(format!(
+            "Invalid base IRI {}: {}",
+            base_iri, err
+        ))
+    })?;
+    let parser = TurtleParser { base: Some(base) };
+    match parser.parse_str(input_str).collect_triples() {
+        Ok(it) => Ok(it),
+        Err(err) => {
+            Err(ParseError::GenericError(format!(
+                "Something went wrong with sophia's turtle parsing: {}",
+                err
+            ))
+            .into())
+        }
+    }
+}
+
 fn try_create_document(
     triples_maps: Vec<TriplesMap>,
     base_iri: Option<String>,
@@ -83,8 +128,27 @@ fn try_create_document(
 /// Returns an error if something goes wrong while parsing into the data model.
 pub fn parse_str(input_str: &str) -> ExtractorResult<Document> {
     let graph = load_graph_str(input_str)?;
-    let triples_maps = extract_triples_maps(&graph)?;
+    parse_graph(input_str, &graph)
+}
+
+/// Parses the already loaded `graph` of the input str representation of an 
+/// RML document into the data model [Document].
+///
+/// # Errors
+///
+/// Returns an error if something goes wrong while parsing into the data model.
+pub fn parse_graph(input_str: &str, graph: &FastGraph) -> ExtractorResult<Document> {
     let base_iri = input_str.split('\n').filter_map(extract_base_iri).next();
+    parse_graph_with_base(graph, base_iri)
+}
+
+/// Parses the already loaded `graph` of an RML document, e.g. merged from
+/// several files, with the given `base_iri` into the data model [Document].
+pub fn parse_graph_with_base(
+    graph: &FastGraph,
+    base_iri: Option<String>,
+) -> ExtractorResult<Document> {
+    let triples_maps = extract_triples_maps(graph)?;
     try_create_document(triples_maps, base_iri)
 }
 
@@ -109,12 +173,8 @@ pub fn parse_file(path: PathBuf) -> ExtractorResult<Document> {
 
         let mut input_string = String::default();
         BufReader::new(File::open(path.clone())?).read_to_string(&mut input_string)?;
-        
-        // Add default @base if none present
-        if !input_string.contains("@base") {
-            input_string = format!("@base <http://example.com/base/> .\n{}", input_string);
-        }
-        
+        let input_string = with_default_base(input_string);
+
         let graph = load_graph_str(&input_string)?;
         let triples_maps = extract_triples_maps(&graph)?;
         let base_iri = input_string.split('\n').filter_map(extract_base_iri).next();
@@ -156,4 +216,31 @@ mod tests {
         // One TriplesMap should be parsed
         assert!(parsed_res.unwrap().triples_maps.len() == 2);
     }
+
+    #[test]
+    fn restrict_to_test() -> ExtractorResult<()> {
+        let path = PathBuf::from(test_case!("rml/multiple_tm_join.ttl"));
+        let document = parse_file(path)?;
+        let identifier_of = |name: &str| {
+            document
+                .triples_maps
+                .iter()
+                .map(|tm| tm.identifier.clone())
+                .find(|identifier| identifier.ends_with(name))
+                .unwrap()
+        };
+        let airport = identifier_of("AirportMapping");
+        let position = identifier_of("PositionMapping");
+
+        // The airports are joined with their positions
+        let restricted = document.restrict_to(&airport).unwrap();
+        assert_eq!(restricted.triples_maps.len(), 2);
+
+        let restricted = document.restrict_to(&position).unwrap();
+        assert_eq!(restricted.triples_maps.len(), 1);
+        assert_eq!(restricted.triples_maps[0].identifier, position);
+
+        assert!(document.restrict_to("http://example.com/ns#None").is_none());
+        Ok(())
+    }
 }
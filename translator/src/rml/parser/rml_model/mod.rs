@@ -1,5 +1,6 @@
 use self::source_target::LogicalSource;
 use self::term_map::{GraphMap, ObjectMap, PredicateMap, SubjectMap};
+use super::extractors::rcterm_to_string;
 
 pub mod join;
 pub mod source_target;
@@ -11,6 +12,47 @@ pub struct Document {
     pub triples_maps:     Vec<TriplesMap>,
 }
 
+impl Document {
+    /// Restricts the document to the triples map with the given
+    /// `identifier` and the parent triples maps it is joined with, e.g. to
+    /// inspect the operators into which one triples map is translated.
+    ///
+    /// Returns [None] if the document has no such triples map.
+    pub fn restrict_to(&self, identifier: &str) -> Option<Document> {
+        let mut identifiers = vec![identifier.to_string()];
+        let mut idx = 0;
+        while let Some(current) = identifiers.get(idx) {
+            let tm = self
+                .triples_maps
+                .iter()
+                .find(|tm| &tm.identifier == current)?;
+            let parents = tm
+                .po_maps
+                .iter()
+                .flat_map(|pom| &pom.object_maps)
+                .filter_map(|om| om.parent_tm.as_ref())
+                .map(rcterm_to_string);
+            for parent in parents {
+                if !identifiers.contains(&parent) {
+                    identifiers.push(parent);
+                }
+            }
+            idx += 1;
+        }
+
+        let triples_maps = self
+            .triples_maps
+            .iter()
+            .filter(|tm| identifiers.contains(&tm.identifier))
+            .cloned()
+            .collect();
+        Some(Document {
+            default_base_iri: self.default_base_iri.clone(),
+            triples_maps,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TriplesMap {
     pub identifier:     String,
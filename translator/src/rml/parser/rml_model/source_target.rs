@@ -53,6 +53,9 @@ pub struct LdesInformation{
     pub ldes_generate_immutable_iri: bool
 }
 
+/// Identifier of the logical target of term maps which do not declare one.
+pub const DEFAULT_LOGICAL_TARGET_ID: &str = "default";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LogicalTarget {
     pub identifier:    String,
@@ -66,7 +69,7 @@ pub struct LogicalTarget {
 impl Default for LogicalTarget {
     fn default() -> Self {
         Self {
-            identifier:    String::from("default"),
+            identifier:    String::from(DEFAULT_LOGICAL_TARGET_ID),
             compression:   Default::default(),
             serialization: RcTerm::from_term(Iri::new_unchecked(
                 vocab::formats::CLASS::NQUADS.to_string(),
@@ -118,7 +121,7 @@ impl From<&LogicalTarget> for operator::Target {
 
         if let Some(comp_iri) = val.compression.as_ref() {
             configuration.insert(
-                "compresssion".to_string(),
+                "compression".to_string(),
                 rcterm_to_string(comp_iri),
             );
         }
@@ -0,0 +1,1043 @@
+//! Converter of ShExML documents into equivalent RML documents in Turtle.
+//!
+//! Every shape is converted into a triples map for each source iterator its
+//! subject expression draws values from, e.g. a shape over a `UNION` of two
+//! iterators results in two triples maps. The triples maps of the same
+//! iterator share one logical source, such that links between their shapes
+//! are referencing object maps without any join condition.
+//!
+//! Constructs without an RML counterpart in this conversion (e.g. matchers,
+//! functions, joins or nested iterators) are not skipped silently but are
+//! reported all together with
+//! [ShExMLTranslationError::UnsupportedConstructs].
+
+use std::collections::BTreeSet;
+
+use plan::error::PlanError;
+use vocab::{ToString, PAIR};
+
+use super::error::ShExMLTranslationError;
+use super::operators::source::resolve_iterator_query;
+use super::parcombi::{
+    DataType, ExpressionReferenceIdent, ExpressionStmtEnum, FieldType,
+    IndexedShExMLDocument, IteratorType, Object, PrefixNameSpace, Shape,
+    ShapeExpression, ShapeIdent, ShapeReference, ShExMLDocument, Source,
+    SourceType, Subject,
+};
+use super::util::get_linked_shape;
+
+const INDENT: &str = "    ";
+
+/// Namespace of the generated triples maps and logical sources.
+const NAMESPACE: &str = "urn:shexml:";
+
+/// Prefixes used to abbreviate the IRIs of the RML and RDF vocabularies.
+const PREFIXES: [(&str, &str); 3] = [
+    (vocab::rml_core::PREFIX, vocab::rml_core::IRI),
+    (vocab::d2rq::PREFIX, vocab::d2rq::IRI),
+    (vocab::rdf::PREFIX, vocab::rdf::IRI),
+];
+
+/// Pair of the source and iterator identifiers of a ShExML expression.
+type SourceIterator = (String, String);
+
+type PredicateObjectPairs = Vec<(String, RMLNode)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RMLNode {
+    Iri(String),
+    Literal(String),
+    Blank(PredicateObjectPairs),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Reference(String),
+}
+
+/// Converts the ShExML document into an RML document in Turtle.
+pub fn convert_to_rml(
+    document: &ShExMLDocument,
+) -> Result<String, ShExMLTranslationError> {
+    let indexed_document = document.clone().convert_to_indexed();
+    let mut converter = RMLConverter {
+        document:        &indexed_document,
+        logical_sources: Vec::new(),
+        unsupported:     Vec::new(),
+    };
+
+    let mut triples_maps = Vec::new();
+    for graph_shapes in &document.graph_shapes {
+        for shape in &graph_shapes.shapes {
+            triples_maps
+                .extend(converter.convert_shape(&graph_shapes.ident, shape));
+        }
+    }
+
+    if !converter.unsupported.is_empty() {
+        return Err(ShExMLTranslationError::UnsupportedConstructs(
+            converter.unsupported,
+        ));
+    }
+
+    triples_maps.extend(converter.logical_sources);
+    Ok(TurtleWriter::default().write_document(&triples_maps))
+}
+
+struct RMLConverter<'a> {
+    document:        &'a IndexedShExMLDocument,
+    logical_sources: Vec<(String, PredicateObjectPairs)>,
+    unsupported:     Vec<String>,
+}
+
+impl RMLConverter<'_> {
+    fn report(&mut self, message: String) -> RMLNode {
+        if !self.unsupported.contains(&message) {
+            self.unsupported.push(message);
+        }
+        RMLNode::Blank(vec![])
+    }
+
+    fn convert_shape(
+        &mut self,
+        graph: &ShapeIdent,
+        shape: &Shape,
+    ) -> Vec<(String, PredicateObjectPairs)> {
+        let context = format!("shape {}", shape.ident);
+        let Some(expr_ident) = shape_expression_ident(shape) else {
+            self.report(format!(
+                "{}: the shape does not reference any expression to iterate \
+                 over",
+                context
+            ));
+            return vec![];
+        };
+        let Some(expr_stmt) = self.document.expression_stmts.get(expr_ident)
+        else {
+            self.report(format!(
+                "{}: {} is not a declared expression",
+                context, expr_ident
+            ));
+            return vec![];
+        };
+
+        let mut pred_obj_pairs: Vec<_> = shape.pred_obj_pairs.iter().collect();
+        pred_obj_pairs.sort_by_key(|(predicate, _)| predicate.to_string());
+
+        let mut result = Vec::new();
+        for source_iter in source_iterators(&expr_stmt.expr_enum) {
+            let logical_source =
+                self.convert_logical_source(&context, &source_iter);
+            let subject_map = self.convert_subject(
+                &context,
+                graph,
+                &shape.subject,
+                &source_iter,
+            );
+            let mut pairs = vec![
+                pair(
+                    vocab::rdf::PROPERTY::TYPE,
+                    vocab_node(vocab::rml_core::CLASS::TRIPLES_MAP),
+                ),
+                pair(vocab::rml_core::PROPERTY::LOGICAL_SOURCE, logical_source),
+                pair(vocab::rml_core::PROPERTY::SUBJECT_MAP, subject_map),
+            ];
+
+            for (predicate, object) in &pred_obj_pairs {
+                // Objects without values from the iterator do not generate
+                // any triples for the records of the iterator
+                if !has_values(self.document, &object.expression, &source_iter)
+                {
+                    continue;
+                }
+                let context = format!("{} predicate {}", context, predicate);
+                let predicate_iri =
+                    self.prefixed_iri(&context, &predicate.prefix)
+                        + &predicate.local;
+                let object_map =
+                    self.convert_object(&context, graph, object, &source_iter);
+
+                pairs.push(pair(
+                    vocab::rml_core::PROPERTY::PREDICATE_OBJECT_MAP,
+                    RMLNode::Blank(vec![
+                        pair(
+                            vocab::rml_core::PROPERTY::PREDICATE,
+                            RMLNode::Iri(predicate_iri),
+                        ),
+                        pair(vocab::rml_core::PROPERTY::OBJECT_MAP, object_map),
+                    ]),
+                ));
+            }
+
+            result.push((
+                triples_map_iri(graph, &shape.ident, &source_iter),
+                pairs,
+            ));
+        }
+
+        result
+    }
+
+    fn convert_logical_source(
+        &mut self,
+        context: &str,
+        source_iter: &SourceIterator,
+    ) -> RMLNode {
+        let (source_ident, iter_ident) = source_iter;
+        let iri = format!("{}{}.{}", NAMESPACE, source_ident, iter_ident);
+        if self
+            .logical_sources
+            .iter()
+            .any(|(logical_source_iri, _)| *logical_source_iri == iri)
+        {
+            return RMLNode::Iri(iri);
+        }
+
+        let document = self.document;
+        let (Some(source), Some(iterator)) = (
+            document.sources.get(source_ident),
+            document.iterators.get(iter_ident),
+        ) else {
+            return self.report(format!(
+                "{}: source {} or iterator {} is not declared",
+                context, source_ident, iter_ident
+            ));
+        };
+        let resolved_query = match resolve_iterator_query(document, iterator) {
+            Ok(resolved_query) => resolved_query,
+            Err(PlanError::GenericError(message)) => {
+                return self.report(format!("{}: {}", context, message))
+            }
+            Err(err) => return self.report(format!("{}: {}", context, err)),
+        };
+        if resolved_query.query_path.is_some() {
+            return self.report(format!(
+                "{}: iterator {} uses an external query file",
+                context, iter_ident
+            ));
+        }
+
+        let reference_formulation = match &resolved_query.iter_type {
+            IteratorType::JSONPath => vocab::rml_io::CLASS::JSONPATH,
+            IteratorType::XPath => vocab::rml_io::CLASS::XPATH,
+            IteratorType::CSVRows => vocab::rml_io::CLASS::CSV,
+            IteratorType::SQL => vocab::rml_io::CLASS::SQL_QUERY,
+            IteratorType::SPARQL => {
+                return self.report(format!(
+                    "{}: SPARQL iterator {} is not supported",
+                    context, iter_ident
+                ));
+            }
+        };
+        let source_node = self.convert_source(context, source);
+
+        let mut pairs = vec![
+            pair(
+                vocab::rdf::PROPERTY::TYPE,
+                vocab_node(vocab::rml_io::CLASS::LOGICAL_SOURCE),
+            ),
+            pair(vocab::rml_io::PROPERTY::SOURCE, source_node),
+            pair(
+                vocab::rml_core::PROPERTY::REFERENCE_FORMULATION,
+                vocab_node(reference_formulation),
+            ),
+        ];
+        if let Some(query) = resolved_query.query {
+            pairs.push(pair(
+                vocab::rml_core::PROPERTY::ITERATOR,
+                RMLNode::Literal(query),
+            ));
+        }
+
+        self.logical_sources.push((iri.clone(), pairs));
+        RMLNode::Iri(iri)
+    }
+
+    fn convert_source(&mut self, context: &str, source: &Source) -> RMLNode {
+        let uri = RMLNode::Literal(source.uri.clone());
+        match &source.source_type {
+            SourceType::File => {
+                RMLNode::Blank(vec![
+                    pair(
+                        vocab::rdf::PROPERTY::TYPE,
+                        vocab_node(vocab::rml_io::CLASS::RELATIVE_PATH_SOURCE),
+                    ),
+                    pair(
+                        vocab::rml_io::PROPERTY::ROOT,
+                        vocab_node(vocab::rml_io::CLASS::CURRENT_WORKING_DIR),
+                    ),
+                    pair(vocab::rml_io::PROPERTY::PATH, uri),
+                ])
+            }
+            SourceType::JDBC(_) => {
+                RMLNode::Blank(vec![
+                    pair(
+                        vocab::rdf::PROPERTY::TYPE,
+                        vocab_node(vocab::d2rq::CLASS::DATABASE),
+                    ),
+                    pair(vocab::d2rq::PROPERTY::JDBCDSN, uri),
+                ])
+            }
+            SourceType::HTTP | SourceType::HTTPS => {
+                self.report(format!(
+                    "{}: remote source {} <{}> is not supported",
+                    context, source.ident, source.uri
+                ))
+            }
+        }
+    }
+
+    fn convert_subject(
+        &mut self,
+        context: &str,
+        graph: &ShapeIdent,
+        subject: &Subject,
+        source_iter: &SourceIterator,
+    ) -> RMLNode {
+        let mut pairs = match (&subject.prefix, &subject.expression) {
+            (
+                PrefixNameSpace::BNodePrefix,
+                ShapeExpression::Reference(reference),
+            ) => {
+                let parts =
+                    self.resolve_reference(context, reference, source_iter);
+                let mut pairs = expression_pairs(None, &parts);
+                pairs.push(pair(
+                    vocab::rml_core::PROPERTY::TERMTYPE,
+                    vocab_node(vocab::rml_core::CLASS::BLANKNODE),
+                ));
+                pairs
+            }
+            (prefix, ShapeExpression::Reference(reference)) => {
+                let prefix_iri = self.prefixed_iri(context, prefix);
+                let parts =
+                    self.resolve_reference(context, reference, source_iter);
+                expression_pairs(Some(&prefix_iri), &parts)
+            }
+            (prefix, ShapeExpression::Static { value })
+                if *prefix != PrefixNameSpace::BNodePrefix =>
+            {
+                let iri = self.prefixed_iri(context, prefix) + value;
+                vec![pair(
+                    vocab::rml_core::PROPERTY::CONSTANT,
+                    RMLNode::Iri(iri),
+                )]
+            }
+            (_, expression) => {
+                return self.report_expression(context, "subject", expression)
+            }
+        };
+
+        if *graph != ShapeIdent::base() {
+            let graph_iri =
+                self.prefixed_iri(context, &graph.prefix) + &graph.local;
+            pairs.push(pair(
+                vocab::rml_core::PROPERTY::GRAPH,
+                RMLNode::Iri(graph_iri),
+            ));
+        }
+
+        RMLNode::Blank(pairs)
+    }
+
+    fn convert_object(
+        &mut self,
+        context: &str,
+        graph: &ShapeIdent,
+        object: &Object,
+        source_iter: &SourceIterator,
+    ) -> RMLNode {
+        let mut pairs = match (&object.prefix, &object.expression) {
+            (_, ShapeExpression::Link { other_shape_ident }) => {
+                return self.convert_link(
+                    context,
+                    graph,
+                    other_shape_ident,
+                    source_iter,
+                );
+            }
+            (
+                Some(PrefixNameSpace::BNodePrefix),
+                ShapeExpression::Reference(reference),
+            ) => {
+                let parts =
+                    self.resolve_reference(context, reference, source_iter);
+                let mut pairs = expression_pairs(None, &parts);
+                pairs.push(pair(
+                    vocab::rml_core::PROPERTY::TERMTYPE,
+                    vocab_node(vocab::rml_core::CLASS::BLANKNODE),
+                ));
+                pairs
+            }
+            (Some(prefix), ShapeExpression::Reference(reference)) => {
+                let prefix_iri = self.prefixed_iri(context, prefix);
+                let parts =
+                    self.resolve_reference(context, reference, source_iter);
+                expression_pairs(Some(&prefix_iri), &parts)
+            }
+            (Some(prefix), ShapeExpression::Static { value })
+                if *prefix != PrefixNameSpace::BNodePrefix =>
+            {
+                let iri = self.prefixed_iri(context, prefix) + value;
+                vec![pair(
+                    vocab::rml_core::PROPERTY::CONSTANT,
+                    RMLNode::Iri(iri),
+                )]
+            }
+            (None, ShapeExpression::Reference(reference)) => {
+                let parts =
+                    self.resolve_reference(context, reference, source_iter);
+                let mut pairs = expression_pairs(None, &parts);
+                if parts.len() > 1 {
+                    pairs.push(pair(
+                        vocab::rml_core::PROPERTY::TERMTYPE,
+                        vocab_node(vocab::rml_core::CLASS::LITERAL),
+                    ));
+                }
+                pairs
+            }
+            (None, ShapeExpression::Static { value }) => {
+                vec![pair(
+                    vocab::rml_core::PROPERTY::CONSTANT,
+                    RMLNode::Literal(value.clone()),
+                )]
+            }
+            (_, expression) => {
+                return self.report_expression(context, "object", expression)
+            }
+        };
+
+        match &object.language {
+            Some(ShapeExpression::Static { value }) => {
+                pairs.push(pair(
+                    vocab::rml_core::PROPERTY::LANGUAGE,
+                    RMLNode::Literal(value.clone()),
+                ));
+            }
+            Some(ShapeExpression::Reference(reference)) => {
+                let parts =
+                    self.resolve_reference(context, reference, source_iter);
+                pairs.push(pair(
+                    vocab::rml_core::PROPERTY::LANGUAGE_MAP,
+                    RMLNode::Blank(expression_pairs(None, &parts)),
+                ));
+            }
+            Some(expression) => {
+                self.report_expression(context, "language", expression);
+            }
+            None => (),
+        }
+
+        if let Some(datatype) = &object.datatype {
+            let datatype_pair =
+                self.convert_datatype(context, datatype, source_iter);
+            pairs.push(datatype_pair);
+        }
+
+        RMLNode::Blank(pairs)
+    }
+
+    fn convert_datatype(
+        &mut self,
+        context: &str,
+        datatype: &DataType,
+        source_iter: &SourceIterator,
+    ) -> (String, RMLNode) {
+        match (&datatype.prefix, &datatype.local_expr) {
+            (Some(prefix), ShapeExpression::Static { value }) => {
+                let iri = self.prefixed_iri(context, prefix) + value;
+                pair(vocab::rml_core::PROPERTY::DATATYPE, RMLNode::Iri(iri))
+            }
+            (prefix, ShapeExpression::Reference(reference)) => {
+                let prefix_iri = prefix
+                    .as_ref()
+                    .map(|prefix| self.prefixed_iri(context, prefix));
+                let parts =
+                    self.resolve_reference(context, reference, source_iter);
+                pair(
+                    vocab::rml_core::PROPERTY::DATATYPE_MAP,
+                    RMLNode::Blank(expression_pairs(
+                        prefix_iri.as_deref(),
+                        &parts,
+                    )),
+                )
+            }
+            (_, expression) => {
+                pair(
+                    vocab::rml_core::PROPERTY::DATATYPE,
+                    self.report_expression(context, "datatype", expression),
+                )
+            }
+        }
+    }
+
+    fn convert_link(
+        &mut self,
+        context: &str,
+        graph: &ShapeIdent,
+        other_shape_ident: &ShapeIdent,
+        source_iter: &SourceIterator,
+    ) -> RMLNode {
+        let document = self.document;
+        let Some((linked_graph, linked_shape)) =
+            get_linked_shape(document, graph, other_shape_ident)
+        else {
+            return self.report(format!(
+                "{}: linked shape {} is not defined",
+                context, other_shape_ident
+            ));
+        };
+
+        let linked_source_iters = shape_expression_ident(linked_shape)
+            .and_then(|expr_ident| document.expression_stmts.get(expr_ident))
+            .map(|expr_stmt| source_iterators(&expr_stmt.expr_enum))
+            .unwrap_or_default();
+        if !linked_source_iters.contains(source_iter) {
+            return self.report(format!(
+                "{}: linked shape {} is not generated from iterator {}.{}, \
+                 links across different iterators are not supported",
+                context, other_shape_ident, source_iter.0, source_iter.1
+            ));
+        }
+
+        RMLNode::Blank(vec![pair(
+            vocab::rml_core::PROPERTY::PARENT_TRIPLES_MAP,
+            RMLNode::Iri(triples_map_iri(
+                linked_graph,
+                other_shape_ident,
+                source_iter,
+            )),
+        )])
+    }
+
+    fn report_expression(
+        &mut self,
+        context: &str,
+        position: &str,
+        expression: &ShapeExpression,
+    ) -> RMLNode {
+        let kind = match expression {
+            ShapeExpression::Matching { .. } => "matcher",
+            ShapeExpression::Conditional { .. } => "conditional",
+            ShapeExpression::Function { .. } => "function",
+            ShapeExpression::Link { .. } => "shape link",
+            ShapeExpression::Static { .. } => "blank node constant",
+            ShapeExpression::Reference(_) => "reference",
+        };
+        self.report(format!(
+            "{}: {} expression [{}] as {} is not supported",
+            context, kind, expression, position
+        ))
+    }
+
+    fn prefixed_iri(
+        &mut self,
+        context: &str,
+        prefix: &PrefixNameSpace,
+    ) -> String {
+        match self.document.prefixes.get(&prefix.to_string()) {
+            Some(prefix) => prefix.uri.clone(),
+            None => {
+                self.report(format!(
+                    "{}: prefix {} is not defined",
+                    context, prefix
+                ));
+                String::new()
+            }
+        }
+    }
+
+    /// Resolves the shape reference into the parts of a template for the
+    /// given source iterator.
+    fn resolve_reference(
+        &mut self,
+        context: &str,
+        reference: &ShapeReference,
+        source_iter: &SourceIterator,
+    ) -> Vec<TemplatePart> {
+        let document = self.document;
+        let expr_ident = &reference.expr_ident;
+        let Some(expr_stmt) = document.expression_stmts.get(expr_ident) else {
+            let kind = if document.matchers.contains_key(expr_ident) {
+                "matcher"
+            } else if document.auto_increments.contains_key(expr_ident) {
+                "autoincrement"
+            } else if document.functions.contains_key(expr_ident) {
+                "function"
+            } else {
+                "undeclared expression"
+            };
+            self.report(format!(
+                "{}: {} {} is not supported",
+                context, kind, expr_ident
+            ));
+            return vec![];
+        };
+
+        let parts = resolve_expression(
+            document,
+            &expr_stmt.expr_enum,
+            reference.field.as_deref(),
+            source_iter,
+        );
+        match parts {
+            Ok(Some(parts)) => parts,
+            Ok(None) => {
+                self.report(format!(
+                    "{}: expression {} has no values from iterator {}.{}",
+                    context, expr_ident, source_iter.0, source_iter.1
+                ));
+                vec![]
+            }
+            Err(reason) => {
+                self.report(format!("{}: {}", context, reason));
+                vec![]
+            }
+        }
+    }
+}
+
+/// Gets the identifier of the expression the triples maps of the shape
+/// iterate over, which is the expression of the subject or otherwise the
+/// first expression of the objects.
+fn shape_expression_ident(shape: &Shape) -> Option<&String> {
+    let mut pred_obj_pairs: Vec<_> = shape.pred_obj_pairs.iter().collect();
+    pred_obj_pairs.sort_by_key(|(predicate, _)| predicate.to_string());
+
+    let object_expressions =
+        pred_obj_pairs.into_iter().map(|(_, object)| &object.expression);
+    std::iter::once(&shape.subject.expression)
+        .chain(object_expressions)
+        .find_map(|expression| {
+            match expression {
+                ShapeExpression::Reference(reference) => {
+                    Some(&reference.expr_ident)
+                }
+                _ => None,
+            }
+        })
+}
+
+fn has_values(
+    document: &IndexedShExMLDocument,
+    expression: &ShapeExpression,
+    source_iter: &SourceIterator,
+) -> bool {
+    let ShapeExpression::Reference(reference) = expression else {
+        return true;
+    };
+
+    document
+        .expression_stmts
+        .get(&reference.expr_ident)
+        .map(|expr_stmt| {
+            let parts = resolve_expression(
+                document,
+                &expr_stmt.expr_enum,
+                reference.field.as_deref(),
+                source_iter,
+            );
+            !matches!(parts, Ok(None))
+        })
+        .unwrap_or(true)
+}
+
+/// Gets the source iterators the expression draws its values from, where
+/// the values of a join and a string concatenation are drawn from their left
+/// side.
+fn source_iterators(expr_enum: &ExpressionStmtEnum) -> Vec<SourceIterator> {
+    let reference = match expr_enum {
+        ExpressionStmtEnum::Union(left_box, right_box) => {
+            let mut result = source_iterators(left_box);
+            for source_iter in source_iterators(right_box) {
+                if !result.contains(&source_iter) {
+                    result.push(source_iter);
+                }
+            }
+            return result;
+        }
+        ExpressionStmtEnum::Join(left_box, _) => {
+            return source_iterators(left_box)
+        }
+        ExpressionStmtEnum::ConcatenateString { left_reference, .. } => {
+            left_reference
+        }
+        ExpressionStmtEnum::Basic { reference } => reference,
+    };
+
+    vec![(
+        reference.source_ident.clone(),
+        reference.iterator_ident.clone(),
+    )]
+}
+
+/// Resolves the `field` of the expression into the parts of a template for
+/// the given source iterator.
+///
+/// Returns `None` if the expression has no values from the source iterator,
+/// and an error with the reason if the expression cannot be converted.
+fn resolve_expression(
+    document: &IndexedShExMLDocument,
+    expr_enum: &ExpressionStmtEnum,
+    field: Option<&str>,
+    source_iter: &SourceIterator,
+) -> Result<Option<Vec<TemplatePart>>, String> {
+    let is_from_source_iter = |reference: &ExpressionReferenceIdent| {
+        reference.source_ident == source_iter.0
+            && reference.iterator_ident == source_iter.1
+    };
+
+    match expr_enum {
+        ExpressionStmtEnum::Basic { reference } => {
+            if !is_from_source_iter(reference) {
+                return Ok(None);
+            }
+            let field = match (reference.field.as_deref(), field) {
+                (Some(field), None) | (None, Some(field)) => field,
+                _ => {
+                    return Err(format!(
+                        "reference to {}.{} does not select a single field",
+                        reference.source_ident, reference.iterator_ident
+                    ))
+                }
+            };
+            let query =
+                resolve_field(document, &reference.iterator_ident, field)?;
+            Ok(Some(vec![TemplatePart::Reference(query)]))
+        }
+        ExpressionStmtEnum::Union(left_box, right_box) => {
+            match resolve_expression(document, left_box, field, source_iter)? {
+                Some(parts) => Ok(Some(parts)),
+                None => {
+                    resolve_expression(document, right_box, field, source_iter)
+                }
+            }
+        }
+        ExpressionStmtEnum::ConcatenateString {
+            left_reference,
+            concate_string,
+            right_reference,
+        } => {
+            match (
+                is_from_source_iter(left_reference),
+                is_from_source_iter(right_reference),
+                field,
+            ) {
+                (false, false, _) => Ok(None),
+                (true, true, None) => {
+                    let mut parts = Vec::new();
+                    for reference in [left_reference, right_reference] {
+                        let field = reference.field.as_deref().ok_or_else(|| {
+                            format!(
+                                "string concatenation of the whole iterator \
+                                 {}.{} is not supported",
+                                reference.source_ident, reference.iterator_ident
+                            )
+                        })?;
+                        let query = resolve_field(
+                            document,
+                            &reference.iterator_ident,
+                            field,
+                        )?;
+                        parts.push(TemplatePart::Reference(query));
+                    }
+                    parts.insert(1, TemplatePart::Text(concate_string.clone()));
+                    Ok(Some(parts))
+                }
+                (true, true, Some(field)) => {
+                    Err(format!(
+                        "field {} of a string concatenation is not supported",
+                        field
+                    ))
+                }
+                _ => {
+                    Err(format!(
+                        "string concatenation of {}.{} and {}.{} from \
+                         different iterators is not supported",
+                        left_reference.source_ident,
+                        left_reference.iterator_ident,
+                        right_reference.source_ident,
+                        right_reference.iterator_ident
+                    ))
+                }
+            }
+        }
+        ExpressionStmtEnum::Join(..) => {
+            Err("JOIN expressions are not supported".to_string())
+        }
+    }
+}
+
+/// Resolves the field of the iterator into its query.
+fn resolve_field(
+    document: &IndexedShExMLDocument,
+    iter_ident: &str,
+    field: &str,
+) -> Result<String, String> {
+    let iterator = document
+        .iterators
+        .get(iter_ident)
+        .ok_or_else(|| format!("iterator {} is not declared", iter_ident))?;
+
+    if field.contains('.') {
+        return Err(format!(
+            "field {}.{} of a nested iterator is not supported",
+            iter_ident, field
+        ));
+    }
+
+    let shexml_field = iterator
+        .fields
+        .iter()
+        .find(|shexml_field| shexml_field.ident == field)
+        .ok_or_else(|| {
+            format!(
+                "field {} is not declared in iterator {}",
+                field, iter_ident
+            )
+        })?;
+    match shexml_field.field_type {
+        FieldType::Normal => Ok(shexml_field.query.clone()),
+        FieldType::Push | FieldType::Pop => {
+            Err(format!(
+                "pushed or popped field {}.{} is not supported",
+                iter_ident, field
+            ))
+        }
+    }
+}
+
+/// Creates the pairs of a term map which takes the value of a single
+/// reference as is, or of a template otherwise.
+fn expression_pairs(
+    prefix_iri: Option<&str>,
+    parts: &[TemplatePart],
+) -> PredicateObjectPairs {
+    if let (None, [TemplatePart::Reference(reference)]) = (prefix_iri, parts) {
+        return vec![pair(
+            vocab::rml_core::PROPERTY::REFERENCE,
+            RMLNode::Literal(reference.clone()),
+        )];
+    }
+
+    let mut template = escape_template(prefix_iri.unwrap_or_default());
+    for part in parts {
+        match part {
+            TemplatePart::Text(text) => template += &escape_template(text),
+            TemplatePart::Reference(reference) => {
+                template += &format!("{{{}}}", escape_template(reference));
+            }
+        }
+    }
+    vec![pair(
+        vocab::rml_core::PROPERTY::TEMPLATE,
+        RMLNode::Literal(template),
+    )]
+}
+
+fn escape_template(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('{', "\\{")
+        .replace('}', "\\}")
+}
+
+fn triples_map_iri(
+    graph: &ShapeIdent,
+    shape_ident: &ShapeIdent,
+    source_iter: &SourceIterator,
+) -> String {
+    let shape_name = shape_ident.to_string();
+    let shape_name = shape_name.trim_start_matches(':');
+    let (source_ident, iter_ident) = source_iter;
+
+    if *graph == ShapeIdent::base() {
+        format!("{}{}/{}.{}", NAMESPACE, shape_name, source_ident, iter_ident)
+    } else {
+        let graph_name = graph.to_string();
+        format!(
+            "{}{}/{}/{}.{}",
+            NAMESPACE,
+            graph_name.trim_start_matches(':'),
+            shape_name,
+            source_ident,
+            iter_ident
+        )
+    }
+}
+
+fn pair(predicate: PAIR, object: RMLNode) -> (String, RMLNode) {
+    (predicate.to_string(), object)
+}
+
+fn vocab_node(pair: PAIR) -> RMLNode {
+    RMLNode::Iri(pair.to_string())
+}
+
+/// Writes the resources with their predicate-object pairs in Turtle, where
+/// the IRIs of the RML vocabulary are abbreviated with [PREFIXES].
+#[derive(Debug, Default)]
+struct TurtleWriter {
+    output:        String,
+    used_prefixes: BTreeSet<usize>,
+}
+
+impl TurtleWriter {
+    fn write_document(
+        mut self,
+        resources: &[(String, PredicateObjectPairs)],
+    ) -> String {
+        for (index, (iri, pairs)) in resources.iter().enumerate() {
+            if index > 0 {
+                self.output.push('\n');
+            }
+            self.output += &format!("<{}>\n", iri);
+            self.write_pairs(pairs, 1);
+            self.output += " .\n";
+        }
+
+        let prefixes: String = self
+            .used_prefixes
+            .iter()
+            .map(|index| {
+                let (prefix, iri) = PREFIXES[*index];
+                format!("@prefix {}: <{}> .\n", prefix, iri)
+            })
+            .collect();
+        format!("{}\n{}", prefixes, self.output)
+    }
+
+    fn write_pairs(&mut self, pairs: &PredicateObjectPairs, depth: usize) {
+        for (index, (predicate, object)) in pairs.iter().enumerate() {
+            if index > 0 {
+                self.output += " ;\n";
+            }
+            self.output += &INDENT.repeat(depth);
+            if *predicate == vocab::rdf::PROPERTY::TYPE.to_string() {
+                self.output.push('a');
+            } else {
+                let predicate = self.abbreviate(predicate);
+                self.output += &predicate;
+            }
+            self.output.push(' ');
+            self.write_node(object, depth);
+        }
+    }
+
+    fn write_node(&mut self, node: &RMLNode, depth: usize) {
+        match node {
+            RMLNode::Iri(iri) => {
+                let iri = self.abbreviate(iri);
+                self.output += &iri;
+            }
+            RMLNode::Literal(value) => {
+                let escaped = value
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r")
+                    .replace('\t', "\\t");
+                self.output += &format!("\"{}\"", escaped);
+            }
+            RMLNode::Blank(pairs) => {
+                self.output += "[\n";
+                self.write_pairs(pairs, depth + 1);
+                self.output += &format!("\n{}]", INDENT.repeat(depth));
+            }
+        }
+    }
+
+    fn abbreviate(&mut self, iri: &str) -> String {
+        for (index, (prefix, namespace)) in PREFIXES.iter().enumerate() {
+            let local = iri.strip_prefix(namespace).filter(|local| {
+                local.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && local
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+            if let Some(local) = local {
+                self.used_prefixes.insert(index);
+                return format!("{}:{}", prefix, local);
+            }
+        }
+        format!("<{}>", iri)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shexml::parcombi;
+    use crate::test_case;
+
+    #[test]
+    fn convert_to_rml_test() -> Result<(), ShExMLTranslationError> {
+        let input_shexml = test_case!("shexml/rml_conversion/input.shexml");
+        let expected_rml = std::fs::read_to_string(test_case!(
+            "shexml/rml_conversion/output.ttl"
+        ))
+        .unwrap();
+        let shexml_document = parcombi::parse_file(input_shexml)?;
+
+        assert_eq!(convert_to_rml(&shexml_document)?, expected_rml);
+        Ok(())
+    }
+
+    #[test]
+    fn convert_unsupported_constructs_test() {
+        let document = r#"PREFIX : <http://example.com/>
+SOURCE films_json <https://example.com/films.json>
+SOURCE actors_json <actors.json>
+ITERATOR film_json <jsonpath: $.films[*]> {
+    FIELD id <id>
+    FIELD name <name>
+    ITERATOR actors <actors[*]> {
+        FIELD name <name>
+    }
+}
+ITERATOR actor_json <jsonpath: $.actors[*]> {
+    FIELD id <id>
+    FIELD film <film>
+}
+EXPRESSION films <films_json.film_json>
+EXPRESSION actors <actors_json.actor_json>
+EXPRESSION film_ids <films_json.film_json.id UNION actors_json.actor_json.film JOIN films_json.film_json.id>
+MATCHER names <Alien, ALIEN AS Alien>
+
+:Films :[films.id] {
+    :name [films.name MATCHING names] ;
+    :actor [films.actors.name] ;
+    :cast @:Actors ;
+}
+
+:Actors :[actors.id] {
+    :film [film_ids] ;
+}
+"#;
+        let shexml_document =
+            parcombi::parse_string(document.to_string()).unwrap();
+
+        let constructs = match convert_to_rml(&shexml_document) {
+            Err(ShExMLTranslationError::UnsupportedConstructs(constructs)) => {
+                constructs
+            }
+            result => panic!("Expected unsupported constructs: {:?}", result),
+        };
+        assert_eq!(
+            constructs,
+            vec![
+                "shape :Films: remote source films_json \
+                 <https://example.com/films.json> is not supported",
+                "shape :Films predicate :actor: field film_json.actors.name \
+                 of a nested iterator is not supported",
+                "shape :Films predicate :cast: linked shape :Actors is not \
+                 generated from iterator films_json.film_json, links across \
+                 different iterators are not supported",
+                "shape :Films predicate :name: matcher expression \
+                 [films.name MATCHING names] as object is not supported",
+                "shape :Actors predicate :film: JOIN expressions are not \
+                 supported",
+            ]
+        );
+    }
+}
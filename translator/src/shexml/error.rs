@@ -9,6 +9,9 @@ pub enum ShExMLTranslationError {
     ParseCombiError(ParseCombiError),
     IOError(std::io::Error),
     PlanError(Box<PlanError>),
+    /// Constructs of the document which cannot be converted into another
+    /// mapping language, each described with its location in the document.
+    UnsupportedConstructs(Vec<String>),
 }
 
 impl From<ParseCombiError> for ShExMLTranslationError {
@@ -36,6 +39,12 @@ impl Display for ShExMLTranslationError {
             ShExMLTranslationError::PlanError(_err) => {
                 write!(f, "error while translating to algebraic plan")
             }
+            ShExMLTranslationError::UnsupportedConstructs(constructs) => {
+                write!(f, "unsupported constructs in ShExML document:")?;
+                constructs
+                    .iter()
+                    .try_for_each(|construct| write!(f, "\n  - {}", construct))
+            }
         }
     }
 }
@@ -4,10 +4,10 @@ use std::rc::Rc;
 
 use error::ShExMLTranslationError;
 use log::{debug, trace};
-use operator::{Extend, Function, Rename, Serializer, Target};
+use operator::{Extend, Function, Rename, Serializer, Unnest};
 use parcombi::{
-    IndexedShExMLDocument, Object, PrefixNameSpace, ShExMLDocument,
-    ShapeExpression, ShapeIdent, Subject,
+    IndexedShExMLDocument, Object, ShExMLDocument, ShapeExpression,
+    ShapeIdent, Subject,
 };
 use plan::data_type::RcRefCellPlan;
 use plan::error::PlanError;
@@ -15,13 +15,16 @@ use plan::states::{Processed, Serialized, Sunk};
 use plan::Plan;
 
 use self::util::IndexVariableTerm;
+use crate::options::TranslationOptions;
 use crate::shexml::operators::source::ShExMLSourceTranslator;
-use crate::shexml::operators::{extend, rename};
+use crate::shexml::operators::{extend, join, lookup, rename, unnest};
 use crate::shexml::util::{
-    get_quads_from_same_source, variablelize_quads, ShExMLQuads,
+    get_link_quads_to_different_source, get_quads_from_same_source,
+    linked_subject, variablelize_quads, ShExMLQuads,
 };
 use crate::{LanguageTranslator, OperatorTranslator};
 
+pub mod converter;
 pub mod error;
 mod operators;
 pub mod parcombi;
@@ -32,8 +35,9 @@ mod util;
 pub struct ShExMLTranslator;
 
 impl LanguageTranslator<ShExMLDocument> for ShExMLTranslator {
-    fn translate_to_plan(
+    fn translate_to_plan_with_options(
         model: ShExMLDocument,
+        options: &TranslationOptions,
     ) -> crate::LanguageTranslateResult {
         let mut plan = Plan::new();
         debug!("Indexing shexml document");
@@ -45,21 +49,68 @@ impl LanguageTranslator<ShExMLDocument> for ShExMLTranslator {
         };
 
         debug!("Translating all source operators");
-        let scidentkey_sourcedplan_exprident_pairval_map: HashMap<
+        let mut scidentkey_sourcedplan_exprident_pairval_map: HashMap<
             String,
             (RcRefCellPlan<Processed>, Vec<String>),
-        > = source_translator
-            .translate()?
-            .into_iter()
-            .map(|(key, value)| {
-                (key, (Rc::new(RefCell::new(plan.source(value.0))), value.1))
-            })
-            .collect();
+        > = HashMap::new();
+        let mut scidentkey_iterident_unnests_map: HashMap<
+            String,
+            (String, Vec<Unnest>),
+        > = HashMap::new();
+        for (key, (source, expr_idents)) in source_translator.translate()? {
+            let unnests =
+                unnest::translate_unnest_vec(&indexed_document, &source)
+                    .map_err(ShExMLTranslationError::from)?;
+            let iter_ident =
+                source.root_iterator.alias.clone().unwrap_or_default();
+            let sourced_plan = plan.source(source);
+
+            scidentkey_iterident_unnests_map
+                .insert(key.clone(), (iter_ident, unnests));
+            scidentkey_sourcedplan_exprident_pairval_map.insert(
+                key,
+                (Rc::new(RefCell::new(sourced_plan)), expr_idents),
+            );
+        }
+
+        debug!("Extracting joins across different sources");
+        let joins = join::extract_joins(&indexed_document);
+
+        // Quads only referencing auto increments are generated from a single
+        // source to not generate them for the mapping tuples of every source
+        let auto_increment_source_ident =
+            scidentkey_sourcedplan_exprident_pairval_map
+                .iter()
+                .filter(|(_, (_, expr_idents))| !expr_idents.is_empty())
+                .map(|(source_iter_ident, _)| source_iter_ident)
+                .min()
+                .cloned();
 
         for (source_iter_ident, (sourced_plan, expr_idents)) in
             scidentkey_sourcedplan_exprident_pairval_map.iter()
         {
-            let expr_idents_hashset =
+            if expr_idents.is_empty() {
+                debug!(
+                    "Skipping source {} since it is only used for joins",
+                    source_iter_ident
+                );
+                continue;
+            }
+
+            let source_joins: Vec<_> = joins
+                .iter()
+                .filter(|shexml_join| {
+                    shexml_join.child_source_iter_ident() == *source_iter_ident
+                })
+                .collect();
+            let (joined_plan, join_rename_pairs) = join::add_join_ops(
+                &source_joins,
+                sourced_plan.clone(),
+                &scidentkey_sourcedplan_exprident_pairval_map,
+            )
+            .map_err(ShExMLTranslationError::from)?;
+
+            let expr_idents_hashset: HashSet<&str> =
                 expr_idents.iter().map(|ident| ident.as_str()).collect();
 
             //filter out quads that could be generated from the same source
@@ -70,33 +121,179 @@ impl LanguageTranslator<ShExMLDocument> for ShExMLTranslator {
             let filtered_same_source_quads = get_quads_from_same_source(
                 &indexed_document,
                 indexed_document.graph_shapes.values(),
-                expr_idents_hashset,
+                expr_idents_hashset.clone(),
+                auto_increment_source_ident.as_ref() == Some(source_iter_ident),
             );
-
-            debug!(
-                "Adding non join related ops for source: {:?}",
-                source_iter_ident
+            let link_quads = get_link_quads_to_different_source(
+                &indexed_document,
+                indexed_document.graph_shapes.values(),
+                expr_idents_hashset.clone(),
             );
-            trace!("Quads: {:#?}", filtered_same_source_quads);
-            add_non_join_related_op(
+
+            if filtered_same_source_quads.is_empty() && link_quads.is_empty() {
+                debug!(
+                    "Skipping source {} since it does not generate any quads",
+                    source_iter_ident
+                );
+                continue;
+            }
+
+            let iterident_unnests =
+                &scidentkey_iterident_unnests_map[source_iter_ident];
+            if !filtered_same_source_quads.is_empty() {
+                add_iterator_ops(
+                    &indexed_document,
+                    &filtered_same_source_quads,
+                    joined_plan.clone(),
+                    source_iter_ident,
+                    iterident_unnests,
+                    &join_rename_pairs,
+                    options,
+                )?;
+            }
+
+            // Shape links to shapes of other iterators are generated from
+            // the mapping tuples joined with the linked shape's iterator
+            let link_quads_vec = join::group_link_quads_by_join(
                 &indexed_document,
-                &filtered_same_source_quads,
-                sourced_plan.clone(),
-                source_iter_ident,
-            )?;
+                &source_joins,
+                &expr_idents_hashset,
+                &scidentkey_sourcedplan_exprident_pairval_map,
+                &link_quads,
+            )
+            .map_err(ShExMLTranslationError::from)?;
+            for link_quads in link_quads_vec {
+                let (linked_plan, link_rename_pairs) = join::add_link_join_op(
+                    &indexed_document,
+                    link_quads.shexml_join,
+                    &link_quads.linked_expr_idents,
+                    joined_plan.clone(),
+                    &scidentkey_sourcedplan_exprident_pairval_map,
+                )
+                .map_err(ShExMLTranslationError::from)?;
+
+                let mut rename_pairs = join_rename_pairs.clone();
+                rename_pairs.extend(link_rename_pairs);
+                add_iterator_ops(
+                    &indexed_document,
+                    &link_quads.quads,
+                    linked_plan,
+                    source_iter_ident,
+                    iterident_unnests,
+                    &rename_pairs,
+                    options,
+                )?;
+            }
         }
 
-        // TODO: Also try to handle joins across different sources in ShExML  <21-03-24, Min Oo> //
-
         Ok(plan)
     }
 }
 
+/// Adds the operators generating the `quads` from the mapping tuples of the
+/// source's ShExML iterator to the `sourced_plan`, with the ident of the
+/// iterator and the unnest configs of its nested iterators given in
+/// `iterident_unnests`.
+///
+/// Quads are generated from the mapping tuples of the iterator, nested or
+/// not, whose fields they reference (see
+/// [unnest::group_quads_by_iterator]).
+fn add_iterator_ops(
+    doc: &IndexedShExMLDocument,
+    quads: &ShExMLQuads<'_>,
+    sourced_plan: RcRefCellPlan<Processed>,
+    source_iter_ident: &str,
+    iterident_unnests: &(String, Vec<Unnest>),
+    join_rename_pairs: &HashMap<String, String>,
+    options: &TranslationOptions,
+) -> Result<(), ShExMLTranslationError> {
+    let (iter_ident, unnests) = iterident_unnests;
+    let iterident_quads_map =
+        unnest::group_quads_by_iterator(doc, iter_ident, unnests, quads)?;
+    let iterident_plan_map = add_unnest_ops(
+        sourced_plan,
+        iter_ident,
+        unnests,
+        iterident_quads_map.keys(),
+    )?;
+
+    for (iterator_ident, quads) in iterident_quads_map.iter() {
+        debug!(
+            "Adding non join related ops for source: {:?} and iterator: {:?}",
+            source_iter_ident, iterator_ident
+        );
+        trace!("Quads: {:#?}", quads);
+        add_non_join_related_op(
+            doc,
+            quads,
+            iterident_plan_map[iterator_ident].clone(),
+            source_iter_ident,
+            join_rename_pairs,
+            options,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Adds the unnest operators of the nested iterators, with the given
+/// `iterator_idents` or nested iterators thereof, to the plan of the source's
+/// ShExML iterator `iter_ident`.
+///
+/// The unnest operator of a nested iterator is added to the plan of its
+/// parent iterator, such that sibling nested iterators are unnested
+/// independently from each other. Returns the plans of the iterators
+/// identified by the unnested attribute of the nested iterators.
+fn add_unnest_ops<'a>(
+    sourced_plan: RcRefCellPlan<Processed>,
+    iter_ident: &str,
+    unnests: &[Unnest],
+    iterator_idents: impl std::iter::Iterator<Item = &'a String>,
+) -> Result<HashMap<String, RcRefCellPlan<Processed>>, PlanError> {
+    let mut required_idents = HashSet::new();
+    for iterator_ident in iterator_idents {
+        let mut ident = iterator_ident.as_str();
+        while ident != iter_ident {
+            required_idents.insert(ident);
+            ident = ident
+                .rsplit_once('.')
+                .map_or(iter_ident, |(parent, _)| parent);
+        }
+    }
+
+    let mut result = HashMap::from([(iter_ident.to_string(), sourced_plan)]);
+    for unnest in unnests {
+        if !required_idents.contains(unnest.attribute.as_str()) {
+            continue;
+        }
+
+        let parent_plan = result
+            .get(unnest::parent_attribute(unnest))
+            .ok_or_else(|| {
+                PlanError::GenericError(format!(
+                    "Plan of the parent iterator of {} cannot be found",
+                    unnest.attribute
+                ))
+            })?;
+        let unnested_plan = (**parent_plan)
+            .borrow_mut()
+            .apply(&unnest.clone().into(), "UnnestOperator")?;
+        result.insert(
+            unnest.attribute.clone(),
+            Rc::new(RefCell::new(unnested_plan)),
+        );
+    }
+
+    Ok(result)
+}
+
 fn add_non_join_related_op(
     doc: &IndexedShExMLDocument,
     quads: &ShExMLQuads<'_>,
     sourced_plan: RcRefCellPlan<Processed>,
     source_iter_ident: &str,
+    join_rename_pairs: &HashMap<String, String>,
+    options: &TranslationOptions,
 ) -> Result<Plan<Sunk>, ShExMLTranslationError> {
     debug!("Variabelizing quads");
     let variabelized_terms = variablelize_quads(quads);
@@ -107,6 +304,7 @@ fn add_non_join_related_op(
         sourced_plan.clone(),
         &variabelized_terms,
         source_iter_ident,
+        join_rename_pairs,
     )?;
 
     let mut serialized_plan = add_serializer_op_from_quads(
@@ -114,13 +312,10 @@ fn add_non_join_related_op(
         quads,
         &mut renamed_extended_plan,
         &variabelized_terms,
+        options,
     )?;
 
-    Ok(serialized_plan.sink(&Target {
-        configuration: HashMap::new(),
-        target_type:   operator::IOType::StdOut,
-        data_format:   operator::formats::DataFormat::NQuads,
-    })?)
+    Ok(serialized_plan.sink(&options.default_target())?)
 }
 
 fn add_rename_extend_op_from_quads(
@@ -129,18 +324,28 @@ fn add_rename_extend_op_from_quads(
     sourced_plan: RcRefCellPlan<Processed>,
     variablized_terms: &IndexVariableTerm<'_>,
     source_iter_ident: &str,
+    join_rename_pairs: &HashMap<String, String>,
 ) -> Result<Plan<Processed>, PlanError> {
     let mut expression_extend_func_pairs: Vec<(String, Function)> = Vec::new();
     let expression_stmts_map = &doc.expression_stmts;
     let mut expr_ident_set = HashSet::new();
-    let mut rename_pairs = HashMap::new();
-    for (subj, _, obj, _) in quads {
+    let mut rename_pairs: HashMap<String, String> = HashMap::new();
+    for (subj, _, obj, graph) in quads {
         expr_ident_set.extend(subj.expression.extract_expr_idents());
-        expr_ident_set.extend(obj.expression.extract_expr_idents());
+        expr_ident_set.extend(obj.extract_expr_idents());
+        if let Some(linked_subj) = linked_subject(doc, graph, obj) {
+            expr_ident_set.extend(linked_subj.expression.extract_expr_idents());
+        }
     }
 
     trace!("Expression identifier set: {:#?}", expr_ident_set);
-    for expr_ident in expr_ident_set {
+    let mut expr_idents: Vec<_> = expr_ident_set.into_iter().collect();
+    expr_idents.sort();
+
+    // An attribute can only be renamed once, the other expressions
+    // referencing the same attribute get a copy of the renamed attribute
+    let mut copy_extend_pairs = HashMap::new();
+    for expr_ident in expr_idents {
         if let Some(expression_stmt) = expression_stmts_map.get(expr_ident) {
             //Add string concatentation extend functions
             let concate_extend_pairs =
@@ -158,7 +363,20 @@ fn add_rename_extend_op_from_quads(
                 expression_stmt,
                 source_iter_ident,
             );
-            rename_pairs.extend(rename_pairs_translated);
+            for (from, to) in rename_pairs_translated {
+                if let Some(renamed) = rename_pairs.get(&from) {
+                    if *renamed != to {
+                        copy_extend_pairs.insert(
+                            to,
+                            Function::Reference {
+                                value: renamed.clone(),
+                            },
+                        );
+                    }
+                } else {
+                    rename_pairs.insert(from, to);
+                }
+            }
         }
     }
 
@@ -188,6 +406,9 @@ fn add_rename_extend_op_from_quads(
         false => next_plan,
     };
 
+    // Add the rename pairs of the joined attributes
+    rename_pairs.extend(join_rename_pairs.clone());
+
     trace!("Rename pairs: {:#?}", rename_pairs);
     if rename_pairs.is_empty() {
         return Err(PlanError::GenericError(
@@ -213,6 +434,28 @@ fn add_rename_extend_op_from_quads(
         false => next_plan,
     };
 
+    if !copy_extend_pairs.is_empty() {
+        trace!("Copies of renamed attributes: {:#?}", copy_extend_pairs);
+        let extend_op = operator::Operator::ExtendOp {
+            config: Extend {
+                extend_pairs: copy_extend_pairs,
+            },
+        };
+        let copied_plan = (*next_plan)
+            .borrow_mut()
+            .apply(&extend_op, "Extend_Copy_Renamed")?;
+        next_plan = Rc::new(copied_plan.into());
+    }
+
+    // Add the lookups of the matchers before the values which use them
+    for lookup in lookup::translate_lookups(doc, quads) {
+        trace!("Lookup of a matcher: {:#?}", lookup);
+        let looked_up_plan = (*next_plan)
+            .borrow_mut()
+            .apply(&lookup.into(), "Lookup_Matcher")?;
+        next_plan = Rc::new(looked_up_plan.into());
+    }
+
     // Add extend operator with the final values for triples serialization
     let sub_objgraph_map: HashMap<&Subject, Vec<(&Object, &ShapeIdent)>> =
         quads.iter().fold(HashMap::new(), |mut acc, quad| {
@@ -230,54 +473,42 @@ fn add_rename_extend_op_from_quads(
         HashMap::new();
 
     for (subj, obj_graph_pairs) in sub_objgraph_map.iter() {
-        if let Some(subj_term_func) = extend::term::rdf_term_function(
-            doc,
-            Some(&subj.prefix),
-            &subj.expression,
-        ) {
-            let subj_term_iri_func =
-                if subj.prefix == PrefixNameSpace::BNodePrefix {
-                    Function::BlankNode {
-                        inner_function: subj_term_func.into(),
-                    }
-                } else {
-                    Function::Iri {
-                        base_iri:       None,
-                        inner_function: subj_term_func.into(),
-                    }
-                };
-
-            for (obj, graph_shape_ident) in obj_graph_pairs.iter() {
-                let subj_variable =
-                    variablized_terms.subject_variable_index.get(subj).unwrap();
-
-                if triples_extend_func_pairs.get(subj_variable).is_none() {
-                    triples_extend_func_pairs.insert(
-                        subj_variable.to_string(),
-                        subj_term_iri_func.clone(),
-                    );
-                }
-
-                match &obj.expression {
-                    // Since it is a shape link just ignore the generation of obj function and
-                    // reuse the linked target's subject variable during BGP generation
-                    ShapeExpression::Link { .. } => {
+        let Some(subj_func) = extend::term::subject_function(doc, subj) else {
+            continue;
+        };
+        let subj_variable = variablized_terms.subject_variable(subj)?;
+        triples_extend_func_pairs
+            .entry(subj_variable.to_string())
+            .or_insert(subj_func);
+
+        for (obj, graph_shape_ident) in obj_graph_pairs.iter() {
+            let obj_func_opt = match &obj.expression {
+                // The object of a shape link is the subject of the linked
+                // shape for the same mapping tuple, which is only generated
+                // if the linked subject is not already part of the quads
+                ShapeExpression::Link { other_shape_ident } => {
+                    let subj_index = &variablized_terms.subject_variable_index;
+                    let has_linked_subj_variable =
+                        linked_subject(doc, graph_shape_ident, obj)
+                            .is_some_and(|linked_subj| {
+                                subj_index.contains_key(linked_subj)
+                            });
+                    if has_linked_subj_variable {
                         continue;
                     }
-                    _ => {
-                        if let Some(obj_func) =
-                            extend::term::obj_lang_datatype_function(doc, obj)
-                        {
-                            let obj_variable = variablized_terms
-                                .object_variable_index
-                                .get(obj)
-                                .unwrap();
-
-                            triples_extend_func_pairs
-                                .insert(obj_variable.to_string(), obj_func);
-                        }
-                    }
+                    extend::term::link_function(
+                        doc,
+                        graph_shape_ident,
+                        other_shape_ident,
+                    )?
                 }
+                _ => extend::term::obj_lang_datatype_function(doc, obj),
+            };
+
+            if let Some(obj_func) = obj_func_opt {
+                let obj_variable = variablized_terms.object_variable(obj)?;
+                triples_extend_func_pairs
+                    .insert(obj_variable.to_string(), obj_func);
             }
         }
     }
@@ -300,52 +531,37 @@ fn add_serializer_op_from_quads(
     quads: &ShExMLQuads<'_>,
     extended_plan: &mut Plan<Processed>,
     variablized_terms: &IndexVariableTerm<'_>,
+    options: &TranslationOptions,
 ) -> Result<Plan<Serialized>, PlanError> {
     let mut bgp_patterns = Vec::new();
     debug!("Generating BGPs for serialization");
     for (subj, pred, obj, graph) in quads {
-        let subj_variable =
-            variablized_terms.subject_variable_index.get(*subj).unwrap();
+        let subj_variable = variablized_terms.subject_variable(subj)?;
         let graph = *graph;
-
-        let obj_variable = match &obj.expression {
-            ShapeExpression::Link { other_shape_ident } => {
-                trace!("Object has a shape link expression: {:?}", obj);
-                let link_subj = &doc
-                    .shapes
-                    .get(&(graph.clone(), other_shape_ident.clone()))
-                    .unwrap()
-                    .subject;
-
-                trace!("Linked subject is: {:?}", link_subj);
-                variablized_terms
-                    .subject_variable_index
-                    .get(&link_subj)
-                    .unwrap()
-            }
-            _ => variablized_terms.object_variable_index.get(*obj).unwrap(),
-        };
+        let obj_variable =
+            variablized_terms.object_term_variable(doc, graph, obj)?;
 
         if let Some(pred_prefix_value) =
             doc.prefixes.get(&pred.prefix.to_string())
         {
             let pred_prefix_uri = &pred_prefix_value.uri;
-            let graph_value = if graph.local == *"" {
-                "".to_string()
+            let triple_pattern = format!(
+                "?{} <{}{}> ?{}",
+                subj_variable, pred_prefix_uri, pred.local, obj_variable,
+            );
+
+            // Quads of named graphs are serialized with the graph IRI
+            let graph_term = if options.emits_named_graphs() {
+                graph_term(doc, graph)?
             } else {
-                let graph_prefix_uri =
-                    &doc.prefixes.get(&graph.prefix.to_string()).unwrap().uri;
-                format!("<{}{}>", graph_prefix_uri, graph.local)
+                None
+            };
+            let single_bgp = match graph_term {
+                Some(graph_value) => {
+                    format!("{} {} .", triple_pattern, graph_value)
+                }
+                None => format!("{} .", triple_pattern),
             };
-
-            let single_bgp = format!(
-                "?{} <{}{}> ?{} {} .",
-                subj_variable,
-                pred_prefix_uri,
-                pred.local,
-                obj_variable,
-                graph_value
-            );
 
             bgp_patterns.push(single_bgp);
         };
@@ -353,8 +569,32 @@ fn add_serializer_op_from_quads(
     let serializer = Serializer {
         template: bgp_patterns.join("\n"),
         options:  None,
-        format:   operator::formats::DataFormat::NQuads,
+        format:   options.serialization.into(),
     };
 
     extended_plan.serialize(serializer)
 }
+
+/// Gets the N-Quads term of the graph in which the quads of a graph shape
+/// are generated, which is `None` for the default graph. Graphs built from
+/// expressions are already rejected by the lexer, so the graph always has a
+/// static name.
+fn graph_term(
+    doc: &IndexedShExMLDocument,
+    graph: &ShapeIdent,
+) -> Result<Option<String>, PlanError> {
+    if *graph == ShapeIdent::base() {
+        return Ok(None);
+    }
+
+    let graph_prefix = doc.prefixes.get(&graph.prefix.to_string()).ok_or_else(
+        || {
+            PlanError::GenericError(format!(
+                "Prefix {} of graph {} is not defined",
+                graph.prefix, graph
+            ))
+        },
+    )?;
+
+    Ok(Some(format!("<{}{}>", graph_prefix.uri, graph.local)))
+}
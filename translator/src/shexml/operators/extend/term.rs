@@ -1,11 +1,16 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use log::warn;
 use operator::Function;
+use plan::error::PlanError;
 
+use crate::shexml::operators::lookup;
 use crate::shexml::parcombi::{
     IndexedShExMLDocument, Object, PrefixNameSpace, ShapeExpression,
+    ShapeIdent, ShapeReference, Subject,
 };
+use crate::shexml::util::get_linked_shape;
 
 pub fn obj_lang_datatype_function(
     doc: &IndexedShExMLDocument,
@@ -27,9 +32,17 @@ pub fn obj_lang_datatype_function(
             })
         }
     } else {
+        // Datatypes and language tags can be static or be evaluated for
+        // every mapping tuple from the referenced expressions
         let dtype_function = obj.datatype.as_ref().and_then(|dtype| {
             rdf_term_function(doc, dtype.prefix.as_ref(), &dtype.local_expr)
-                .map(|fun| fun.into())
+                .map(|fun| {
+                    Function::Iri {
+                        base_iri:       None,
+                        inner_function: fun.into(),
+                    }
+                    .into()
+                })
         });
 
         let langtype_function = obj.language.as_ref().and_then(|lang_expr| {
@@ -44,6 +57,47 @@ pub fn obj_lang_datatype_function(
     }
 }
 
+/// Gets the function generating the IRI or blank node of the `subj`.
+pub fn subject_function(
+    doc: &IndexedShExMLDocument,
+    subj: &Subject,
+) -> Option<Function> {
+    let inner_function =
+        rdf_term_function(doc, Some(&subj.prefix), &subj.expression)?.into();
+    if subj.prefix == PrefixNameSpace::BNodePrefix {
+        Some(Function::BlankNode { inner_function })
+    } else {
+        Some(Function::Iri {
+            base_iri: None,
+            inner_function,
+        })
+    }
+}
+
+/// Gets the function generating the subject of the shape linked to with
+/// the `other_shape_ident` by a shape in the graph `graph_ident`.
+///
+/// # Errors
+///
+/// Returns an error if the linked shape is not defined.
+pub fn link_function(
+    doc: &IndexedShExMLDocument,
+    graph_ident: &ShapeIdent,
+    other_shape_ident: &ShapeIdent,
+) -> Result<Option<Function>, PlanError> {
+    let (_, linked_shape) =
+        get_linked_shape(doc, graph_ident, other_shape_ident).ok_or_else(
+            || {
+                PlanError::GenericError(format!(
+                    "Linked shape {} is not defined",
+                    other_shape_ident
+                ))
+            },
+        )?;
+
+    Ok(subject_function(doc, &linked_shape.subject))
+}
+
 pub fn rdf_term_function(
     doc: &IndexedShExMLDocument,
     prefix_ns_opt: Option<&PrefixNameSpace>,
@@ -51,31 +105,39 @@ pub fn rdf_term_function(
 ) -> Option<Function> {
     let function_value_opt = match shape_expression {
         ShapeExpression::Reference(reference) => {
-            Some(Function::Reference {
-                value: reference.to_string(),
-            })
+            match doc.auto_increments.get(&reference.expr_ident) {
+                Some(auto_increment) => {
+                    Some(Function::AutoIncrement {
+                        start:  auto_increment.start,
+                        step:   auto_increment.step,
+                        end:    auto_increment.end,
+                        prefix: auto_increment.prefix.clone(),
+                        suffix: auto_increment.suffix.clone(),
+                    })
+                }
+                None => {
+                    Some(Function::Reference {
+                        value: reference.to_string(),
+                    })
+                }
+            }
         }
         ShapeExpression::Matching {
             reference,
             matcher_ident,
         } => {
-            let ref_func = Function::Reference {
-                value: reference.to_string(),
-            };
-
-            doc.matchers.get(matcher_ident).map(|matcher| {
-                Function::Replace {
-                    replace_map:    matcher.rename_map.clone(),
-                    inner_function: ref_func.into(),
+            // The matched value is added by the lookup operator of the
+            // matcher
+            doc.matchers.get(matcher_ident).map(|_| {
+                Function::Reference {
+                    value: lookup::matched_attribute(reference, matcher_ident),
                 }
             })
         }
 
-        ShapeExpression::Link { other_shape_ident } => {
-            Some(Function::Reference {
-                value: other_shape_ident.to_string(),
-            })
-        }
+        // Shape links are generated from the subject of the linked shape
+        // (see [link_function])
+        ShapeExpression::Link { .. } => None,
 
         ShapeExpression::Static { value } => {
             Some(Function::Constant {
@@ -83,13 +145,24 @@ pub fn rdf_term_function(
             })
         }
 
-        shape_expression => {
-            warn!(
-                "Extracting external functions from shape expression: {:#?} is not supported",
-                shape_expression
-            );
+        ShapeExpression::Function {
+            fun_method_ident,
+            params_idents,
+        } => fno_function(doc, fun_method_ident, params_idents),
 
-            None
+        ShapeExpression::Conditional {
+            reference,
+            conditional_expr,
+        } => {
+            rdf_term_function(doc, None, conditional_expr).map(|condition| {
+                Function::Conditional {
+                    condition_function: condition.into(),
+                    inner_function:     Function::Reference {
+                        value: reference.to_string(),
+                    }
+                    .into(),
+                }
+            })
         }
     };
     //Still need to handle templating if there is a prefix
@@ -116,3 +189,266 @@ pub fn rdf_term_function(
 
     None
 }
+
+/// Translates the application of a method from the declared `FUNCTIONS`
+/// into a [Function::FnO] with the identifier `<function uri>#<method>`.
+///
+/// ShExML functions only have positional parameters, so the parameters are
+/// identified by their position in the function application.
+fn fno_function(
+    doc: &IndexedShExMLDocument,
+    fun_method_ident: &ShapeReference,
+    params_idents: &[ShapeReference],
+) -> Option<Function> {
+    let function_decl_opt = doc.functions.get(&fun_method_ident.expr_ident);
+    let (Some(function_decl), Some(method)) =
+        (function_decl_opt, fun_method_ident.field.as_ref())
+    else {
+        warn!(
+            "Function {} is not declared with a FUNCTIONS statement",
+            fun_method_ident
+        );
+        return None;
+    };
+
+    let parameters: HashMap<_, _> = params_idents
+        .iter()
+        .enumerate()
+        .map(|(idx, param)| {
+            (
+                idx.to_string(),
+                Rc::new(Function::Reference {
+                    value: param.to_string(),
+                }),
+            )
+        })
+        .collect();
+
+    Some(Function::FnO {
+        fno_identifier: format!("{}#{}", function_decl.uri, method),
+        parameters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shexml::parcombi;
+    use crate::test_case;
+
+    fn find_object<'a>(
+        doc: &'a IndexedShExMLDocument,
+        shape_ident: &str,
+        pred_local: &str,
+    ) -> &'a Object {
+        let shape = doc
+            .shapes
+            .iter()
+            .find(|((_, ident), _)| ident.local == shape_ident)
+            .map(|(_, shape)| shape)
+            .unwrap();
+        let (_, obj) = shape
+            .pred_obj_pairs
+            .iter()
+            .find(|(pred, _)| pred.local == pred_local)
+            .unwrap();
+
+        obj
+    }
+
+    fn object_function(
+        doc: &IndexedShExMLDocument,
+        shape_ident: &str,
+        pred_local: &str,
+    ) -> Option<Function> {
+        let obj = find_object(doc, shape_ident, pred_local);
+
+        rdf_term_function(doc, obj.prefix.as_ref(), &obj.expression)
+    }
+
+    fn literal_functions(
+        doc: &IndexedShExMLDocument,
+        pred_local: &str,
+    ) -> (Option<Function>, Option<Function>) {
+        let obj = find_object(doc, "Films", pred_local);
+        let literal = obj_lang_datatype_function(doc, obj).unwrap();
+        let Function::Literal {
+            dtype_function,
+            langtype_function,
+            ..
+        } = literal
+        else {
+            panic!("Expected a literal function but got: {:?}", literal);
+        };
+
+        (
+            dtype_function.map(|fun| (*fun).clone()),
+            langtype_function.map(|fun| (*fun).clone()),
+        )
+    }
+
+    fn xsd_template(inner_function: Function) -> Function {
+        let template = format!("{}{{func_value}}", vocab::xsd::IRI);
+        Function::Iri {
+            base_iri:       None,
+            inner_function: Function::TemplateFunctionValue {
+                template,
+                variable_function_pairs: vec![(
+                    "func_value".to_string(),
+                    inner_function.into(),
+                )],
+            }
+            .into(),
+        }
+    }
+
+    #[test]
+    fn function_expression_test() {
+        let input = test_case!("shexml/unorganized/ExternalFunctionsTest.shexml");
+        let doc = parcombi::parse_file(input).unwrap().convert_to_indexed();
+        let function_uri = "https://raw.githubusercontent.com/herminiogg/ShExML/enhancement-%23121/src/test/resources/functions.scala";
+
+        let name_year = object_function(&doc, "Films", "titleYear").unwrap();
+        let Function::FnO {
+            fno_identifier,
+            parameters,
+        } = name_year
+        else {
+            panic!("Expected a FnO function but got: {:?}", name_year);
+        };
+
+        assert_eq!(fno_identifier, format!("{}#nameAndYear", function_uri));
+        assert_eq!(
+            parameters.get("0").map(|f| &**f),
+            Some(&Function::Reference {
+                value: "films.name".to_string(),
+            })
+        );
+        assert_eq!(
+            parameters.get("1").map(|f| &**f),
+            Some(&Function::Reference {
+                value: "films.year".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn auto_increment_expression_test() {
+        let input = test_case!("shexml/unorganized/MultipleElementIteratorExpressionWithAutoIncrementWithStringsTest.shexml");
+        let doc = parcombi::parse_file(input).unwrap().convert_to_indexed();
+
+        assert_eq!(
+            object_function(&doc, "Films", "internalId1").unwrap(),
+            Function::AutoIncrement {
+                start:  1,
+                step:   Some(2),
+                end:    Some(10),
+                prefix: Some("something: ".to_string()),
+                suffix: None,
+            }
+        );
+
+        assert_eq!(
+            object_function(&doc, "Films", "internalId3").unwrap(),
+            Function::AutoIncrement {
+                start:  1,
+                step:   None,
+                end:    Some(10),
+                prefix: None,
+                suffix: Some(" :closing".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn conditional_expression_test() {
+        let input =
+            test_case!("shexml/unorganized/ConditionalGenerationTest.shexml");
+        let doc = parcombi::parse_file(input).unwrap().convert_to_indexed();
+
+        let country =
+            object_function(&doc, "Films", "countryOfOrigin").unwrap();
+        let Function::Conditional {
+            condition_function,
+            inner_function,
+        } = country
+        else {
+            panic!("Expected a conditional function but got: {:?}", country);
+        };
+
+        assert_eq!(
+            *inner_function,
+            Function::Reference {
+                value: "films.country".to_string(),
+            }
+        );
+        assert!(matches!(
+            &*condition_function,
+            Function::FnO { fno_identifier, .. }
+                if fno_identifier.ends_with("functions.scala#outsideUSA")
+        ));
+    }
+
+    #[test]
+    fn language_expression_test() {
+        let input = test_case!("shexml/dynamic_literals/input.shexml");
+        let doc = parcombi::parse_file(input).unwrap().convert_to_indexed();
+
+        assert_eq!(
+            literal_functions(&doc, "originalTitle"),
+            (
+                None,
+                Some(Function::Constant {
+                    value: "en".to_string(),
+                })
+            )
+        );
+        assert_eq!(
+            literal_functions(&doc, "title"),
+            (
+                None,
+                Some(Function::Reference {
+                    value: "film_languages".to_string(),
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn datatype_expression_test() {
+        let input = test_case!("shexml/dynamic_literals/input.shexml");
+        let doc = parcombi::parse_file(input).unwrap().convert_to_indexed();
+
+        assert_eq!(
+            literal_functions(&doc, "duration"),
+            (
+                Some(xsd_template(Function::Constant {
+                    value: "integer".to_string(),
+                })),
+                None
+            )
+        );
+        assert_eq!(
+            literal_functions(&doc, "year"),
+            (
+                Some(Function::Iri {
+                    base_iri:       None,
+                    inner_function: Function::Reference {
+                        value: "films.year_type".to_string(),
+                    }
+                    .into(),
+                }),
+                None
+            )
+        );
+        assert_eq!(
+            literal_functions(&doc, "rating"),
+            (
+                Some(xsd_template(Function::Reference {
+                    value: "films.rating_type".to_string(),
+                })),
+                None
+            )
+        );
+    }
+}
@@ -0,0 +1,407 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use log::{debug, trace, warn};
+use operator::{JoinType, Operator, Rename};
+use plan::data_type::RcRefCellPlan;
+use plan::error::PlanError;
+use plan::states::join::join;
+use plan::states::Processed;
+use plan::Plan;
+
+use super::rename::translate_rename_pairs_map;
+use crate::shexml::parcombi::{
+    ExpressionReferenceIdent, ExpressionStmtEnum, IndexedShExMLDocument,
+};
+use crate::shexml::util::{linked_subject, ShExMLQuads};
+
+/// Join between the iterator of the `child` reference and the iterator of
+/// the `parent` reference on equal values of both references.
+///
+/// The value of the expression `expr_ident` for the joined mapping tuples is
+/// taken from the `value` reference of the parent's iterator, e.g. for
+/// `<a.it.id UNION b.it.name JOIN a.it.name>` the joined tuples of `b.it` get
+/// the value of `a.it.id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShExMLJoin<'a> {
+    pub expr_ident: &'a str,
+    pub child:      &'a ExpressionReferenceIdent,
+    pub parent:     &'a ExpressionReferenceIdent,
+    pub value:      &'a ExpressionReferenceIdent,
+}
+
+impl ShExMLJoin<'_> {
+    pub fn child_source_iter_ident(&self) -> String {
+        source_iter_ident(self.child)
+    }
+
+    pub fn parent_source_iter_ident(&self) -> String {
+        source_iter_ident(self.parent)
+    }
+
+    fn alias(&self) -> String {
+        format!("join_{}", self.expr_ident)
+    }
+}
+
+fn source_iter_ident(reference: &ExpressionReferenceIdent) -> String {
+    format!("{}.{}", reference.source_ident, reference.iterator_ident)
+}
+
+fn field_attribute(reference: &ExpressionReferenceIdent) -> Option<String> {
+    reference
+        .field
+        .as_ref()
+        .map(|field| format!("{}.{}", reference.iterator_ident, field))
+}
+
+/// Extracts all the joins from the expression statements of the document
+/// sorted on the expression identifiers.
+pub fn extract_joins(doc: &IndexedShExMLDocument) -> Vec<ShExMLJoin<'_>> {
+    let mut result = Vec::new();
+    for (expr_ident, expr_stmt) in doc.expression_stmts.iter() {
+        extract_joins_expr_enum(
+            expr_ident,
+            &expr_stmt.expr_enum,
+            &mut Vec::new(),
+            &mut result,
+        );
+    }
+
+    result.sort_by(|left, right| left.expr_ident.cmp(right.expr_ident));
+    trace!("Extracted joins: {:#?}", result);
+    result
+}
+
+fn extract_joins_expr_enum<'a>(
+    expr_ident: &'a str,
+    expr_enum: &'a ExpressionStmtEnum,
+    union_references: &mut Vec<&'a ExpressionReferenceIdent>,
+    result: &mut Vec<ShExMLJoin<'a>>,
+) {
+    match expr_enum {
+        ExpressionStmtEnum::Union(left_enum, right_enum) => {
+            if let ExpressionStmtEnum::Basic { reference } = &**left_enum {
+                union_references.push(reference);
+            }
+            extract_joins_expr_enum(
+                expr_ident,
+                left_enum,
+                union_references,
+                result,
+            );
+            extract_joins_expr_enum(
+                expr_ident,
+                right_enum,
+                union_references,
+                result,
+            );
+        }
+        ExpressionStmtEnum::Join(left_enum, right_enum) => {
+            match (&**left_enum, &**right_enum) {
+                (
+                    ExpressionStmtEnum::Basic { reference: child },
+                    ExpressionStmtEnum::Basic { reference: parent },
+                ) => {
+                    // The value of the joined tuples comes from the reference
+                    // in the union which shares the iterator with the parent.
+                    let value = union_references
+                        .iter()
+                        .find(|reference| {
+                            source_iter_ident(reference)
+                                == source_iter_ident(parent)
+                        })
+                        .copied()
+                        .unwrap_or(parent);
+
+                    result.push(ShExMLJoin {
+                        expr_ident,
+                        child,
+                        parent,
+                        value,
+                    });
+                }
+                _ => {
+                    warn!(
+                        "Join is only supported between two field references in expression: {}",
+                        expr_ident
+                    );
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Adds the join operators for the given `joins` to the `child_plan` and
+/// returns the joined plan together with the rename pairs which map the
+/// joined attributes to their expression identifiers.
+///
+/// The attributes of the parent plans are prefixed with the alias of the
+/// join to avoid clashes with the attributes of the child plan.
+/// The joins are left outer joins, since the mapping tuples of the child
+/// plan without a joined value still generate the quads of the other
+/// expressions.
+///
+/// # Errors
+///
+/// Returns an error if the plan of a parent iterator cannot be found or if
+/// the join operators cannot be added to the plans.
+pub fn add_join_ops(
+    joins: &[&ShExMLJoin<'_>],
+    child_plan: RcRefCellPlan<Processed>,
+    sourced_plans: &HashMap<String, (RcRefCellPlan<Processed>, Vec<String>)>,
+) -> Result<(RcRefCellPlan<Processed>, HashMap<String, String>), PlanError> {
+    let mut rename_pairs = HashMap::new();
+    let mut joined_plan = child_plan;
+
+    for shexml_join in joins {
+        let alias = shexml_join.alias();
+        let joined = match field_attribute(shexml_join.value) {
+            Some(value_attr) => {
+                join_parent_plan(
+                    shexml_join,
+                    &alias,
+                    joined_plan.clone(),
+                    sourced_plans,
+                    JoinType::LeftJoin,
+                )?
+                .map(|joined| (value_attr, joined))
+            }
+            None => None,
+        };
+        let Some((value_attr, joined)) = joined else {
+            warn!(
+                "Join of expression {} needs to reference fields of the iterators",
+                shexml_join.expr_ident
+            );
+            continue;
+        };
+
+        rename_pairs.insert(
+            format!("{}.{}", alias, value_attr),
+            shexml_join.expr_ident.to_string(),
+        );
+        joined_plan = Rc::new(RefCell::new(joined));
+    }
+
+    Ok((joined_plan, rename_pairs))
+}
+
+/// Adds the join operator for the shape links of the child's iterator to
+/// shapes generated from the parent's iterator of the `shexml_join`, and
+/// returns the joined plan together with the rename pairs of the parent's
+/// attributes for the expressions in `linked_expr_idents`.
+///
+/// Only the joined mapping tuples generate the linked quads, so the join is
+/// an inner join which should be used in a separate branch of the plan.
+///
+/// # Errors
+///
+/// Returns an error if the join does not reference fields of the iterators,
+/// if the plan of the parent iterator cannot be found or if the join
+/// operator cannot be added to the plans.
+pub fn add_link_join_op(
+    doc: &IndexedShExMLDocument,
+    shexml_join: &ShExMLJoin<'_>,
+    linked_expr_idents: &HashSet<&str>,
+    child_plan: RcRefCellPlan<Processed>,
+    sourced_plans: &HashMap<String, (RcRefCellPlan<Processed>, Vec<String>)>,
+) -> Result<(RcRefCellPlan<Processed>, HashMap<String, String>), PlanError> {
+    let alias = format!("link_{}", shexml_join.expr_ident);
+    let joined = join_parent_plan(
+        shexml_join,
+        &alias,
+        child_plan,
+        sourced_plans,
+        JoinType::InnerJoin,
+    )?
+    .ok_or_else(|| {
+        PlanError::GenericError(format!(
+            "Join of expression {} needs to reference fields of the iterators",
+            shexml_join.expr_ident
+        ))
+    })?;
+
+    let parent_key = shexml_join.parent_source_iter_ident();
+    let mut rename_pairs = HashMap::new();
+    for expr_ident in linked_expr_idents {
+        let Some(expr_stmt) = doc.expression_stmts.get(*expr_ident) else {
+            continue;
+        };
+        let parent_rename_pairs = translate_rename_pairs_map(
+            &doc.iterators,
+            expr_stmt,
+            &parent_key,
+        );
+        for (from, to) in parent_rename_pairs {
+            rename_pairs.insert(format!("{}.{}", alias, from), to);
+        }
+    }
+
+    Ok((Rc::new(RefCell::new(joined)), rename_pairs))
+}
+
+/// Quads of shape links across iterators which are generated with the
+/// join `shexml_join` of both iterators.
+#[derive(Debug, Clone)]
+pub struct LinkQuads<'a, 'b> {
+    pub shexml_join:        &'b ShExMLJoin<'b>,
+    pub linked_expr_idents: HashSet<&'a str>,
+    pub quads:              ShExMLQuads<'a>,
+}
+
+/// Groups the `link_quads` of shape links across iterators on the join,
+/// out of the `joins` of the child's iterator, whose parent iterator
+/// generates the subjects of the linked shapes.
+///
+/// The parent iterator of the join needs to generate all the expressions
+/// in the subject of the linked shape, and the child iterator none of
+/// them, to not mix up the attributes of both iterators. The linked
+/// expressions are returned together with the quads of each join.
+///
+/// # Errors
+///
+/// Returns an error if none of the `joins` can be used for a shape link.
+pub fn group_link_quads_by_join<'a, 'b>(
+    doc: &'a IndexedShExMLDocument,
+    joins: &[&'b ShExMLJoin<'b>],
+    child_expr_idents: &HashSet<&str>,
+    sourced_plans: &HashMap<String, (RcRefCellPlan<Processed>, Vec<String>)>,
+    link_quads: &ShExMLQuads<'a>,
+) -> Result<Vec<LinkQuads<'a, 'b>>, PlanError> {
+    let mut result: Vec<LinkQuads<'a, 'b>> = Vec::new();
+    for quad in link_quads {
+        let (_, _, obj, graph) = quad;
+        let linked_expr_idents = linked_subject(doc, graph, obj)
+            .map(|linked_subj| linked_subj.expression.extract_expr_idents())
+            .unwrap_or_default();
+
+        let shexml_join = joins
+            .iter()
+            .copied()
+            .find(|shexml_join| {
+                let parent_key = shexml_join.parent_source_iter_ident();
+                sourced_plans.get(&parent_key).is_some_and(
+                    |(_, parent_expr_idents)| {
+                        linked_expr_idents.iter().all(|expr_ident| {
+                            parent_expr_idents
+                                .iter()
+                                .any(|parent_ident| parent_ident == expr_ident)
+                        })
+                    },
+                )
+            })
+            .filter(|_| linked_expr_idents.is_disjoint(child_expr_idents))
+            .ok_or_else(|| {
+                PlanError::GenericError(format!(
+                    "Shape link {:?} across iterators needs a join between the iterators generating both shapes",
+                    obj.expression
+                ))
+            })?;
+
+        match result
+            .iter_mut()
+            .find(|link_quads| link_quads.shexml_join == shexml_join)
+        {
+            Some(link_quads) => {
+                link_quads.linked_expr_idents.extend(linked_expr_idents);
+                link_quads.quads.push(*quad);
+            }
+            None => {
+                result.push(LinkQuads {
+                    shexml_join,
+                    linked_expr_idents,
+                    quads: vec![*quad],
+                });
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Joins the plan of the parent iterator of the `shexml_join`, with its
+/// attributes prefixed with the `alias`, to the `child_plan`.
+///
+/// Returns `None` if the join does not reference fields of the iterators.
+fn join_parent_plan(
+    shexml_join: &ShExMLJoin<'_>,
+    alias: &str,
+    child_plan: RcRefCellPlan<Processed>,
+    sourced_plans: &HashMap<String, (RcRefCellPlan<Processed>, Vec<String>)>,
+    join_type: JoinType,
+) -> Result<Option<Plan<Processed>>, PlanError> {
+    let (Some(child_attr), Some(parent_attr)) = (
+        field_attribute(shexml_join.child),
+        field_attribute(shexml_join.parent),
+    ) else {
+        return Ok(None);
+    };
+
+    let parent_key = shexml_join.parent_source_iter_ident();
+    let (parent_plan, _) = sourced_plans.get(&parent_key).ok_or(
+        PlanError::GenericError(format!(
+            "Plan for the joined iterator {} cannot be found",
+            parent_key
+        )),
+    )?;
+
+    debug!(
+        "Joining {} with {} for expression {}",
+        shexml_join.child_source_iter_ident(),
+        parent_key,
+        shexml_join.expr_ident
+    );
+
+    let parent_rename_op = Operator::RenameOp {
+        config: Rename {
+            alias:        Some(alias.to_string()),
+            rename_pairs: HashMap::new(),
+        },
+    };
+    let joined = join(child_plan, Rc::clone(parent_plan))?
+        .alias(alias)?
+        .apply_to_right_fragment(
+            parent_rename_op,
+            "Rename_join".into(),
+            alias.into(),
+        )?
+        .where_by(vec![child_attr])?
+        .equal_to_with_join_type(
+            vec![format!("{}.{}", alias, parent_attr)],
+            join_type,
+        )?;
+
+    Ok(Some(joined))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shexml::parcombi;
+    use crate::test_case;
+
+    #[test]
+    fn extract_joins_test() {
+        let input = test_case!(
+            "shexml/unorganized/JsonAndXmlEventsMappingWithJoin.shexml"
+        );
+        let doc = parcombi::parse_file(input).unwrap().convert_to_indexed();
+        let joins = extract_joins(&doc);
+
+        assert_eq!(joins.len(), 1);
+        let venue_join = &joins[0];
+        assert_eq!(venue_join.expr_ident, "venues_union");
+        assert_eq!(
+            venue_join.child_source_iter_ident(),
+            "events_xml.events_iterator"
+        );
+        assert_eq!(
+            venue_join.parent_source_iter_ident(),
+            "performances_json.performances_iterator"
+        );
+        assert_eq!(venue_join.value.field.as_deref(), Some("venues_ids"));
+    }
+}
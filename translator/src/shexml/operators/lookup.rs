@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, HashMap};
+
+use log::trace;
+use operator::formats::ReferenceFormulation;
+use operator::{
+    IOType, Iterator, Lookup, LookupMissPolicy, Source, LOOKUP_INLINE_TABLE,
+};
+
+use crate::shexml::parcombi::{
+    IndexedShExMLDocument, Matcher, ShapeExpression, ShapeReference,
+};
+use crate::shexml::util::{linked_subject, ShExMLQuads};
+
+const KEY_FIELD: &str = "key";
+const VALUE_FIELD: &str = "value";
+
+/// Name of the attribute holding the value of the `reference` after
+/// applying the matcher with the given `matcher_ident`.
+pub fn matched_attribute(
+    reference: &ShapeReference,
+    matcher_ident: &str,
+) -> String {
+    format!("{}_matching_{}", reference, matcher_ident)
+}
+
+/// Translates the matchers applied by the shape expressions of the `quads`
+/// into [Lookup] configs, with the pairs of every matcher inlined as
+/// lookup table.
+pub fn translate_lookups(
+    doc: &IndexedShExMLDocument,
+    quads: &ShExMLQuads<'_>,
+) -> Vec<Lookup> {
+    let mut matchings = BTreeMap::new();
+    for (subj, _, obj, graph) in quads {
+        add_matchings(&subj.expression, &mut matchings);
+        add_matchings(&obj.expression, &mut matchings);
+        if let Some(linked_subj) = linked_subject(doc, graph, obj) {
+            add_matchings(&linked_subj.expression, &mut matchings);
+        }
+        if let Some(language) = &obj.language {
+            add_matchings(language, &mut matchings);
+        }
+        if let Some(datatype) = &obj.datatype {
+            add_matchings(&datatype.local_expr, &mut matchings);
+        }
+    }
+    trace!("Matching expressions: {:#?}", matchings);
+
+    matchings
+        .into_iter()
+        .filter_map(|(alias, (reference, matcher_ident))| {
+            let matcher = doc.matchers.get(matcher_ident)?;
+            Some(Lookup {
+                attribute:     reference.to_string(),
+                alias,
+                table:         matcher_table(matcher),
+                key_field:     KEY_FIELD.to_string(),
+                value_field:   VALUE_FIELD.to_string(),
+                default_value: None,
+                miss_policy:   LookupMissPolicy::Passthrough,
+            })
+        })
+        .collect()
+}
+
+fn add_matchings<'a>(
+    shape_expr: &'a ShapeExpression,
+    matchings: &mut BTreeMap<String, (&'a ShapeReference, &'a str)>,
+) {
+    match shape_expr {
+        ShapeExpression::Matching {
+            reference,
+            matcher_ident,
+        } => {
+            matchings.insert(
+                matched_attribute(reference, matcher_ident),
+                (reference, matcher_ident),
+            );
+        }
+        ShapeExpression::Conditional {
+            conditional_expr, ..
+        } => add_matchings(conditional_expr, matchings),
+        _ => {}
+    }
+}
+
+/// Lookup table of the `matcher` with its matched values as keys and the
+/// value they are renamed to as values.
+fn matcher_table(matcher: &Matcher) -> Source {
+    let mut rows = BTreeMap::new();
+    for (renamed, values) in &matcher.rename_map {
+        for value in values {
+            rows.insert(value.as_str(), renamed.as_str());
+        }
+    }
+
+    let mut csv = format!("{},{}\n", KEY_FIELD, VALUE_FIELD);
+    for (key, value) in rows {
+        csv += &format!("{},{}\n", csv_field(key), csv_field(value));
+    }
+
+    let config = HashMap::from([(LOOKUP_INLINE_TABLE.to_string(), csv)]);
+    Source {
+        config,
+        source_type:   IOType::File,
+        root_iterator: Iterator {
+            reference_formulation: ReferenceFormulation::CSVRows,
+            ..Default::default()
+        },
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn matcher_table_test() {
+        let matcher = Matcher {
+            ident:      "names".to_string(),
+            rename_map: HashMap::from([(
+                "Spain".to_string(),
+                HashSet::from([
+                    "España".to_string(),
+                    "Spain, the".to_string(),
+                ]),
+            )]),
+        };
+        let table = matcher_table(&matcher);
+
+        assert_eq!(
+            table.config.get(LOOKUP_INLINE_TABLE).unwrap(),
+            "key,value\nEspaña,Spain\n\"Spain, the\",Spain\n"
+        );
+    }
+}
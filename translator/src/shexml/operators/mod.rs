@@ -1,3 +1,6 @@
 pub mod source;
 pub mod extend;
+pub mod join;
+pub mod lookup;
 pub mod rename;
+pub mod unnest;
@@ -86,8 +86,7 @@ fn extract_reference_expr_enum(
     expr_enum: &ExpressionStmtEnum,
 ) -> HashSet<ExpressionReferenceIdent> {
     match expr_enum {
-        ExpressionStmtEnum::Join(left_enum, right_enum)
-        | ExpressionStmtEnum::Union(left_enum, right_enum) => {
+        ExpressionStmtEnum::Union(left_enum, right_enum) => {
             let mut left_references = extract_reference_expr_enum(left_enum);
             let right_references = extract_reference_expr_enum(right_enum);
 
@@ -97,6 +96,8 @@ fn extract_reference_expr_enum(
         ExpressionStmtEnum::Basic { reference } => {
             HashSet::from([reference.clone()])
         }
+        // References of a join are renamed after the join operator
+        // (see [super::join::add_join_ops])
         _ => HashSet::new(),
     }
 }
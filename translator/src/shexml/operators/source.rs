@@ -1,3 +1,4 @@
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 
 use log::{debug, trace};
@@ -7,7 +8,8 @@ use plan::error::PlanError;
 
 use crate::shexml::error::ShExMLTranslationError;
 use crate::shexml::parcombi::{
-    self, ExpressionStmtEnum, IndexedShExMLDocument, Iterator, SourceType,
+    self, ExpressionStmtEnum, IndexedShExMLDocument, Iterator, QueryContent,
+    SourceType,
 };
 use crate::OperatorTranslator;
 
@@ -24,36 +26,6 @@ impl<'a> OperatorTranslator<ShExMLSourceTranslatorOutput>
     for ShExMLSourceTranslator<'a>
 {
     fn translate(&self) -> ShExMLSourceTranslatorOutput {
-        let ident_config_iotyperes_iter: Vec<_> = self
-            .document
-            .sources
-            .values()
-            .map(|source| {
-                let mut config = HashMap::new();
-                config.insert("path".to_string(), source.uri.clone());
-                let source_type_res = match &source.source_type {
-                    SourceType::File => Ok(IOType::File),
-                    unsupported_type => {
-                        Err(PlanError::GenericError(format!(
-                            "Unsupported ShExML source type {:?}",
-                            unsupported_type
-                        )))
-                    }
-                };
-                source_type_res.map(|source_type| {
-                    (source.ident.as_str(), (config, source_type))
-                })
-            })
-            .collect::<Result<_, PlanError>>()?;
-
-        let ident_config_iotype_map: HashMap<_, _> =
-            ident_config_iotyperes_iter.into_iter().collect();
-
-        trace!(
-            "Generated ident config iotype map: {:#?}",
-            ident_config_iotype_map
-        );
-
         debug!("Starting translation of shexml iterators to iterators for source operator.");
         debug!("Starting pairing of (source id, iterator id) with the associated (expression id)");
         let ident_iterators_map: &HashMap<_, _> = &self.document.iterators;
@@ -72,6 +44,23 @@ impl<'a> OperatorTranslator<ShExMLSourceTranslatorOutput>
             "((Source id + iterator id), expr id) pairs:\n {:#?}",
             sourceid_iterid_pair_exprid
         );
+        let create_source = |source_ident: &str, iter_ident: &str| {
+            let shexml_source = self.document.sources.get(source_ident).ok_or(
+                PlanError::GenericError(format!(
+                    "ShExML source {} cannot be found",
+                    source_ident
+                )),
+            )?;
+            let iter = ident_iterators_map.get(iter_ident).ok_or(
+                PlanError::GenericError(format!(
+                    "ShExML iterator {} cannot be found",
+                    iter_ident
+                )),
+            )?;
+
+            translate_to_operator_source(self.document, shexml_source, iter)
+        };
+
         let mut source_expr_idents_map = HashMap::new();
         for ((source_ident, iter_ident), expr_ident) in
             sourceid_iterid_pair_exprid
@@ -84,22 +73,31 @@ impl<'a> OperatorTranslator<ShExMLSourceTranslatorOutput>
 
                 exprs.push(expr_ident.to_string());
             } else {
-                let config_iotype_pair =
-                    ident_config_iotype_map.get(source_ident).unwrap();
-
-                let iter = ident_iterators_map.get(iter_ident).unwrap();
-                let source = Source {
-                    config:        config_iotype_pair.0.clone(),
-                    source_type:   config_iotype_pair.1.clone(),
-                    root_iterator: translate_to_operator_iterator(iter),
-                };
-
+                let source = create_source(source_ident, iter_ident)?;
                 let value = (source, vec![expr_ident.to_string()]);
 
                 source_expr_idents_map.insert(key, value);
             }
         }
 
+        // Iterators which are only used as the right side of a join still
+        // need a source operator to be joined with.
+        let join_sourceid_iterid_pairs: HashSet<(&str, &str)> = self
+            .document
+            .expression_stmts
+            .values()
+            .flat_map(|expr_stmt| {
+                extract_join_source_iter_pairs(&expr_stmt.expr_enum)
+            })
+            .collect();
+        for (source_ident, iter_ident) in join_sourceid_iterid_pairs {
+            let key = format!("{}.{}", source_ident, iter_ident);
+            if let Entry::Vacant(entry) = source_expr_idents_map.entry(key) {
+                let source = create_source(source_ident, iter_ident)?;
+                entry.insert((source, Vec::new()));
+            }
+        }
+
         Ok(source_expr_idents_map)
     }
 }
@@ -109,14 +107,18 @@ fn extract_source_iter_pairs(
 ) -> HashSet<(&str, &str)> {
     let mut result = HashSet::new();
     match expr_enum {
-        ExpressionStmtEnum::Union(left_box, right_box)
-        | ExpressionStmtEnum::Join(left_box, right_box) => {
+        ExpressionStmtEnum::Union(left_box, right_box) => {
             let left_pairs = extract_source_iter_pairs(left_box);
             let right_pairs = extract_source_iter_pairs(right_box);
 
             result.extend(left_pairs);
             result.extend(right_pairs);
         }
+        ExpressionStmtEnum::Join(left_box, _) => {
+            // The right side of a join only provides the values for the
+            // joined mapping tuples of the left side's iterator
+            result.extend(extract_source_iter_pairs(left_box));
+        }
         ExpressionStmtEnum::ConcatenateString {
             left_reference,
             right_reference,
@@ -139,19 +141,157 @@ fn extract_source_iter_pairs(
     result
 }
 
+fn extract_join_source_iter_pairs(
+    expr_enum: &ExpressionStmtEnum,
+) -> HashSet<(&str, &str)> {
+    match expr_enum {
+        ExpressionStmtEnum::Union(left_box, right_box) => {
+            let mut result = extract_join_source_iter_pairs(left_box);
+            result.extend(extract_join_source_iter_pairs(right_box));
+            result
+        }
+        ExpressionStmtEnum::Join(_, right_box) => {
+            extract_source_iter_pairs(right_box)
+        }
+        _ => HashSet::new(),
+    }
+}
+
+/// Iterator query of a ShExML iterator after resolving the references to
+/// the declared queries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedIteratorQuery {
+    pub iter_type:  parcombi::IteratorType,
+    pub query:      Option<String>,
+    pub query_path: Option<String>,
+}
+
+pub fn resolve_iterator_query(
+    document: &IndexedShExMLDocument,
+    shexml_iter: &Iterator,
+) -> Result<ResolvedIteratorQuery, PlanError> {
+    let declared_query = shexml_iter
+        .query
+        .as_ref()
+        .filter(|_| shexml_iter.iter_type.is_none())
+        .and_then(|query_ident| document.queries.get(query_ident));
+
+    let (iter_type, query, query_path) = match declared_query {
+        Some(declared_query) => {
+            match &declared_query.content {
+                QueryContent::Inline(query) => {
+                    let iter_type = declared_query.iter_type.clone();
+                    (iter_type, Some(query.clone()), None)
+                }
+                QueryContent::External(uri) => {
+                    let iter_type = declared_query.iter_type.clone();
+                    (iter_type, None, Some(uri.clone()))
+                }
+            }
+        }
+        None => {
+            let iter_type = shexml_iter.iter_type.clone();
+            (iter_type, shexml_iter.query.clone(), None)
+        }
+    };
+
+    let iter_type = iter_type.ok_or(PlanError::GenericError(format!(
+        "Iterator type of ShExML iterator {} cannot be determined",
+        shexml_iter.ident
+    )))?;
+
+    Ok(ResolvedIteratorQuery {
+        iter_type,
+        query,
+        query_path,
+    })
+}
+
+fn translate_to_operator_source(
+    document: &IndexedShExMLDocument,
+    shexml_source: &parcombi::Source,
+    shexml_iter: &Iterator,
+) -> Result<Source, PlanError> {
+    let resolved_query = resolve_iterator_query(document, shexml_iter)?;
+    let uri = shexml_source.uri.clone();
+
+    let mut config = HashMap::new();
+    let source_type = match (
+        &shexml_source.source_type,
+        &resolved_query.iter_type,
+    ) {
+        (SourceType::JDBC(_), parcombi::IteratorType::SQL) => {
+            config.insert("jdbcDSN".to_string(), uri);
+            IOType::RDB
+        }
+        (SourceType::JDBC(_), iter_type) => {
+            return Err(PlanError::GenericError(format!(
+                "JDBC source {} only supports sql iterators but got {:?}",
+                shexml_source.ident, iter_type
+            )));
+        }
+        (
+            SourceType::HTTP | SourceType::HTTPS,
+            parcombi::IteratorType::SPARQL,
+        ) if !is_rdf_file(&uri) => {
+            config.insert("sparql_uri".to_string(), uri);
+            IOType::SPARQLEndpoint
+        }
+        (SourceType::File | SourceType::HTTP | SourceType::HTTPS, _) => {
+            config.insert("path".to_string(), uri);
+            IOType::File
+        }
+    };
+
+    match (&resolved_query.iter_type, &resolved_query.query) {
+        (parcombi::IteratorType::SQL, Some(query)) => {
+            config.insert("sqlQuery".to_string(), query.clone());
+        }
+        (parcombi::IteratorType::SPARQL, Some(query)) => {
+            config.insert("query".to_string(), query.clone());
+        }
+        _ => (),
+    }
+    if let Some(query_path) = &resolved_query.query_path {
+        config.insert("query_path".to_string(), query_path.clone());
+    }
+
+    Ok(Source {
+        config,
+        source_type,
+        root_iterator: translate_to_operator_iterator(
+            shexml_iter,
+            &resolved_query,
+        ),
+    })
+}
+
+/// Checks if the URI points to an RDF file which is queried with SPARQL
+/// instead of a SPARQL endpoint.
+fn is_rdf_file(uri: &str) -> bool {
+    const RDF_FILE_EXTENSIONS: [&str; 8] = [
+        ".nt", ".nq", ".ttl", ".trig", ".rdf", ".owl", ".n3", ".jsonld",
+    ];
+
+    let lowered_uri = uri.to_lowercase();
+    RDF_FILE_EXTENSIONS
+        .iter()
+        .any(|extension| lowered_uri.ends_with(extension))
+}
+
 fn translate_to_operator_iterator(
     shexml_iter: &Iterator,
+    resolved_query: &ResolvedIteratorQuery,
 ) -> operator::Iterator {
-    let reference_formulation = translate_to_reference_formulation(
-        shexml_iter.iter_type.as_ref().unwrap(),
-    );
+    let reference_formulation =
+        translate_to_reference_formulation(&resolved_query.iter_type);
 
     let fields =
         translate_to_operator_fields(shexml_iter, &reference_formulation);
 
     operator::Iterator {
         alias: Some(shexml_iter.ident.to_string()),
-        reference: shexml_iter.query.clone(),
+        reference: resolved_query.query.clone(),
         reference_formulation,
         fields,
     }
@@ -169,7 +309,7 @@ fn translate_to_reference_formulation(
     }
 }
 
-fn translate_to_operator_fields(
+pub fn translate_to_operator_fields(
     parent_shex_iter: &parcombi::Iterator,
     ref_formulation: &ReferenceFormulation,
 ) -> Vec<operator::Field> {
@@ -181,6 +321,8 @@ fn translate_to_operator_fields(
         .collect();
     result.extend(flat_fields);
 
+    // The values of a nested iterator are exploded with an unnest operator
+    // afterwards (see [super::unnest::translate_unnest_vec])
     let nested_iterator_fields: Vec<operator::Field> = parent_shex_iter
         .nested_iterator
         .iter()
@@ -191,10 +333,7 @@ fn translate_to_operator_fields(
                 constant:              None,
                 iterator:              None,
                 reference_formulation: ref_formulation.clone(),
-                inner_fields:          translate_to_operator_fields(
-                    nested_iter,
-                    ref_formulation,
-                ),
+                inner_fields:          vec![],
             }
         })
         .collect();
@@ -242,9 +381,10 @@ mod tests {
 
         let alge_source = source_translator.translate().map_err(|err| {
             ParseCombiError {
-                dbg_msg: err.to_string(),
-                msg:     err.to_string(),
-                kind:    ParseCombiErrorKind::IOError,
+                dbg_msg:     err.to_string(),
+                msg:         err.to_string(),
+                kind:        ParseCombiErrorKind::IOError,
+                diagnostics: Vec::new(),
             }
         })?;
         let expected_source_ids = vec!["films_csv_file.film_csv"];
@@ -265,4 +405,107 @@ mod tests {
 
         Ok(())
     }
+
+    fn translate_single_source(
+        document: &IndexedShExMLDocument,
+        expected_source_id: &str,
+    ) -> Source {
+        let source_translator = ShExMLSourceTranslator { document };
+        let mut alge_source = source_translator.translate().unwrap();
+        let (source, _) = alge_source.remove(expected_source_id).unwrap();
+        source
+    }
+
+    #[test]
+    fn jdbc_sql_source_test() -> ShExMLParseCombiResult<()> {
+        let input = test_case!("shexml/unorganized/FilmsMySQLTest.shexml");
+        let shexml_doc = parcombi::parse_file(input)?.convert_to_indexed();
+        let source = translate_single_source(
+            &shexml_doc,
+            "films_database.films_iterator",
+        );
+
+        assert_eq!(source.source_type, IOType::RDB);
+        assert_eq!(
+            source.config,
+            HashMap::from([
+                (
+                    "jdbcDSN".to_string(),
+                    "jdbc:mysql://localhost:3306/films".to_string()
+                ),
+                ("sqlQuery".to_string(), "SELECT * FROM films;".to_string()),
+            ])
+        );
+        assert_eq!(
+            source.root_iterator.reference_formulation,
+            ReferenceFormulation::SQLQuery
+        );
+        assert_eq!(
+            source.root_iterator.reference.as_deref(),
+            Some("SELECT * FROM films;")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn jdbc_external_query_source_test() -> ShExMLParseCombiResult<()> {
+        let input =
+            test_case!("shexml/unorganized/FilmsExternalQueryMySQLTest.shexml");
+        let shexml_doc = parcombi::parse_file(input)?.convert_to_indexed();
+        let source = translate_single_source(
+            &shexml_doc,
+            "films_database.films_iterator",
+        );
+
+        assert_eq!(source.source_type, IOType::RDB);
+        assert_eq!(
+            source.config.get("query_path").map(|path| path.as_str()),
+            Some("https://raw.githubusercontent.com/herminiogg/ShExML/enhancement-%2363/src/test/resources/filmQuery.sql")
+        );
+        assert_eq!(source.root_iterator.reference, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sparql_endpoint_source_test() -> ShExMLParseCombiResult<()> {
+        let input = test_case!("shexml/sparql_endpoint/input.shexml");
+        let shexml_doc = parcombi::parse_file(input)?.convert_to_indexed();
+        let source = translate_single_source(
+            &shexml_doc,
+            "films_endpoint.films_iterator",
+        );
+
+        assert_eq!(source.source_type, IOType::SPARQLEndpoint);
+        assert_eq!(
+            source.config.get("sparql_uri").map(|uri| uri.as_str()),
+            Some("https://query.wikidata.org/sparql")
+        );
+        assert!(source.config.contains_key("query"));
+        assert_eq!(
+            source.root_iterator.reference_formulation,
+            ReferenceFormulation::SPARQL
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sparql_file_source_test() -> ShExMLParseCombiResult<()> {
+        let input = test_case!("shexml/unorganized/FilmsSparqlFileTest.shexml");
+        let shexml_doc = parcombi::parse_file(input)?.convert_to_indexed();
+        let source = translate_single_source(
+            &shexml_doc,
+            "films_database.films_iterator",
+        );
+
+        assert_eq!(source.source_type, IOType::File);
+        assert_eq!(
+            source.root_iterator.reference_formulation,
+            ReferenceFormulation::SPARQL
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,292 @@
+use std::collections::BTreeMap;
+
+use operator::formats::ReferenceFormulation;
+use operator::{Source, Unnest, UnnestType};
+use plan::error::PlanError;
+
+use super::source::translate_to_operator_fields;
+use crate::shexml::parcombi::{IndexedShExMLDocument, Iterator, ShapeReference};
+use crate::shexml::util::{linked_subject, ShExMLQuads};
+
+/// Translates the nested iterators of the source's ShExML iterator into
+/// [Unnest] configs.
+///
+/// The configs are ordered such that the unnest config of a parent iterator
+/// comes before the configs of its nested iterators.
+/// Every config has to be applied to the plan unnesting its parent iterator
+/// (see [parent_attribute]), and not to the plan unnesting a sibling, since
+/// the values of sibling nested iterators are independent of each other.
+/// The fields of a nested iterator are named as
+/// `<iterator>.<nested iterator>.<field>`, which keeps the parent-child
+/// relationship intact for the renaming of the expressions
+/// (see [super::rename::translate_rename_pairs_map]).
+pub fn translate_unnest_vec(
+    document: &IndexedShExMLDocument,
+    source: &Source,
+) -> Result<Vec<Unnest>, PlanError> {
+    let root_iterator = &source.root_iterator;
+    let iter_ident = root_iterator.alias.as_deref().unwrap_or_default();
+    let shexml_iter = document.iterators.get(iter_ident).ok_or(
+        PlanError::GenericError(format!(
+            "ShExML iterator {} cannot be found",
+            iter_ident
+        )),
+    )?;
+
+    let mut result = Vec::new();
+    collect_unnest(
+        shexml_iter,
+        iter_ident,
+        &root_iterator.reference_formulation,
+        &mut result,
+    );
+    Ok(result)
+}
+
+fn collect_unnest(
+    parent_shex_iter: &Iterator,
+    parent_name: &str,
+    ref_formulation: &ReferenceFormulation,
+    result: &mut Vec<Unnest>,
+) {
+    for nested_iter in &parent_shex_iter.nested_iterator {
+        let name = format!("{}.{}", parent_name, nested_iter.ident);
+
+        // Mapping tuples without nested values are kept since the fields of
+        // the parent iterator could still be referenced on their own
+        result.push(Unnest {
+            attribute:             name.clone(),
+            alias:                 name.clone(),
+            iterator:              None,
+            reference_formulation: ref_formulation.clone(),
+            fields:                translate_to_operator_fields(
+                nested_iter,
+                ref_formulation,
+            ),
+            ordinality:            None,
+            unnest_type:           UnnestType::Outer,
+        });
+
+        collect_unnest(nested_iter, &name, ref_formulation, result);
+    }
+}
+
+/// Gets the unnested attribute of the parent iterator of the `unnest`
+/// config, which is the ident of the source's ShExML iterator for the
+/// top-level nested iterators.
+pub fn parent_attribute(unnest: &Unnest) -> &str {
+    unnest
+        .attribute
+        .rsplit_once('.')
+        .map(|(parent, _)| parent)
+        .unwrap_or_default()
+}
+
+/// Groups the `quads` on the iterator whose mapping tuples generate them,
+/// which is the deepest nested iterator with fields referenced by the quad,
+/// or the source's ShExML iterator `iter_ident` if the quad does not
+/// reference any nested iterator.
+///
+/// Nested iterators are identified by the unnested attribute of their
+/// config in `unnests`.
+///
+/// # Errors
+///
+/// Returns an error if a quad references the fields of sibling nested
+/// iterators, which do not share their mapping tuples.
+pub fn group_quads_by_iterator<'a>(
+    doc: &IndexedShExMLDocument,
+    iter_ident: &str,
+    unnests: &[Unnest],
+    quads: &ShExMLQuads<'a>,
+) -> Result<BTreeMap<String, ShExMLQuads<'a>>, PlanError> {
+    let mut result: BTreeMap<String, ShExMLQuads<'a>> = BTreeMap::new();
+    for quad in quads {
+        let (subj, _, obj, graph) = quad;
+        let mut references = subj.expression.extract_references();
+        references.extend(obj.extract_references());
+        if let Some(linked_subj) = linked_subject(doc, graph, obj) {
+            references.extend(linked_subj.expression.extract_references());
+        }
+
+        let mut deepest = iter_ident.to_string();
+        for reference in references {
+            let iterator = referenced_iterator(iter_ident, unnests, reference);
+            if is_nested_in(&iterator, &deepest) {
+                deepest = iterator;
+            } else if !is_nested_in(&deepest, &iterator) {
+                return Err(PlanError::GenericError(format!(
+                    "Sibling nested iterators {} and {} cannot be referenced by the same triple",
+                    deepest, iterator
+                )));
+            }
+        }
+
+        result.entry(deepest).or_default().push(*quad);
+    }
+
+    Ok(result)
+}
+
+/// Gets the unnested attribute of the deepest nested iterator in the field
+/// path of the `reference`, e.g. `film_json.actors` for `films.actors.name`,
+/// or `iter_ident` for the fields of the source's ShExML iterator.
+fn referenced_iterator(
+    iter_ident: &str,
+    unnests: &[Unnest],
+    reference: &ShapeReference,
+) -> String {
+    let segments: Vec<_> = reference
+        .field
+        .as_deref()
+        .map(|field| field.split('.').collect())
+        .unwrap_or_default();
+
+    (1..segments.len())
+        .rev()
+        .map(|len| format!("{}.{}", iter_ident, segments[..len].join(".")))
+        .find(|attribute| {
+            unnests.iter().any(|unnest| unnest.attribute == *attribute)
+        })
+        .unwrap_or_else(|| iter_ident.to_string())
+}
+
+fn is_nested_in(iterator: &str, ancestor: &str) -> bool {
+    iterator == ancestor
+        || iterator
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shexml::operators::source::ShExMLSourceTranslator;
+    use crate::shexml::parcombi::{self, Subject};
+    use crate::shexml::util::convert_graph_shape_to_quads;
+    use crate::test_case;
+    use crate::OperatorTranslator;
+
+    #[test]
+    fn nested_iterator_unnest_test() -> Result<(), PlanError> {
+        let input_shexml = test_case!("shexml/simple/input.shexml");
+        let shexml_doc =
+            parcombi::parse_file(input_shexml).unwrap().convert_to_indexed();
+        let sources = ShExMLSourceTranslator {
+            document: &shexml_doc,
+        }
+        .translate()
+        .unwrap();
+        let (xml_source, _) = &sources["films_xml_file.film_xml"];
+
+        let nested_field = xml_source
+            .root_iterator
+            .fields
+            .iter()
+            .find(|field| field.alias == "goodReviews")
+            .unwrap();
+        assert_eq!(
+            nested_field.reference.as_deref(),
+            Some("reviews/goodReviews/review")
+        );
+        assert!(nested_field.inner_fields.is_empty());
+
+        let unnests = translate_unnest_vec(&shexml_doc, xml_source)?;
+        let attributes: Vec<_> =
+            unnests.iter().map(|unnest| unnest.attribute.as_str()).collect();
+        assert_eq!(
+            attributes,
+            vec![
+                "film_xml.goodReviews",
+                "film_xml.goodReviews.nested_badReviews",
+                "film_xml.badReviews",
+            ]
+        );
+
+        let field_aliases: Vec<_> = unnests[0]
+            .fields
+            .iter()
+            .map(|field| field.alias.as_str())
+            .collect();
+        assert_eq!(
+            field_aliases,
+            vec!["user", "review", "nested_badReviews"]
+        );
+        assert!(unnests.iter().all(|unnest| {
+            unnest.reference_formulation == ReferenceFormulation::XMLPath
+        }));
+        assert_eq!(parent_attribute(&unnests[1]), "film_xml.goodReviews");
+        assert_eq!(parent_attribute(&unnests[2]), "film_xml");
+
+        Ok(())
+    }
+
+    #[test]
+    fn sibling_nested_iterator_test() -> Result<(), PlanError> {
+        let input_shexml =
+            test_case!("shexml/sibling_nested_iterators/input.shexml");
+        let shexml_doc =
+            parcombi::parse_file(input_shexml).unwrap().convert_to_indexed();
+        let sources = ShExMLSourceTranslator {
+            document: &shexml_doc,
+        }
+        .translate()
+        .unwrap();
+        let (json_source, _) = &sources["films_json_file.film_json"];
+
+        let unnests = translate_unnest_vec(&shexml_doc, json_source)?;
+        let attributes: Vec<_> =
+            unnests.iter().map(|unnest| unnest.attribute.as_str()).collect();
+        assert_eq!(attributes, vec!["film_json.actors", "film_json.reviews"]);
+        assert!(unnests
+            .iter()
+            .all(|unnest| parent_attribute(unnest) == "film_json"));
+
+        let quads: ShExMLQuads<'_> = shexml_doc
+            .graph_shapes
+            .values()
+            .flat_map(convert_graph_shape_to_quads)
+            .collect();
+        let grouped_quads = group_quads_by_iterator(
+            &shexml_doc,
+            "film_json",
+            &unnests,
+            &quads,
+        )?;
+        let grouped_predicates: Vec<_> = grouped_quads
+            .iter()
+            .map(|(iterator, quads)| {
+                let predicates: Vec<_> =
+                    quads.iter().map(|quad| quad.1.local.as_str()).collect();
+                (iterator.as_str(), predicates)
+            })
+            .collect();
+        assert_eq!(
+            grouped_predicates,
+            vec![
+                ("film_json", vec!["title"]),
+                ("film_json.actors", vec!["actor"]),
+                ("film_json.reviews", vec!["review"]),
+            ]
+        );
+
+        // A triple cannot combine the values of sibling nested iterators
+        let (_, _, actor_obj, graph) = grouped_quads["film_json.actors"][0];
+        let (subj, pred, review_obj, _) =
+            grouped_quads["film_json.reviews"][0];
+        let actor_subj = Subject {
+            prefix:     subj.prefix.clone(),
+            expression: actor_obj.expression.clone(),
+        };
+        let sibling_quads = vec![(&actor_subj, pred, review_obj, graph)];
+        assert!(group_quads_by_iterator(
+            &shexml_doc,
+            "film_json",
+            &unnests,
+            &sibling_quads
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -1,13 +1,48 @@
 use std::fmt::{Debug, Display};
+use std::hash::Hash;
 use std::io;
+use std::ops::Range;
+
+use chumsky::error::{Simple, SimpleReason};
 
 pub type ShExMLParseCombiResult<T> = Result<T, ParseCombiError>;
 
 #[derive(Debug, Clone)]
 pub struct ParseCombiError {
-    pub dbg_msg: String,
-    pub msg:     String,
-    pub kind:    ParseCombiErrorKind,
+    pub dbg_msg:     String,
+    pub msg:         String,
+    pub kind:        ParseCombiErrorKind,
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl ParseCombiError {
+    /// Creates a lexer or parser error from the errors reported by chumsky,
+    /// rendering every error with a snippet of the ShExML document.
+    ///
+    /// The spans of the errors are expected to be character offsets in the
+    /// `document`.
+    pub fn from_simple_errors<T: Display + Hash + Eq>(
+        document: &str,
+        errors: Vec<Simple<T>>,
+        kind: ParseCombiErrorKind,
+    ) -> Self {
+        let diagnostics: Vec<_> = errors
+            .iter()
+            .map(|err| ParseDiagnostic::from_simple(document, err))
+            .collect();
+        let msg = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.render(document))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        ParseCombiError {
+            dbg_msg: format!("{:?}", diagnostics),
+            msg,
+            kind,
+            diagnostics,
+        }
+    }
 }
 
 impl Display for ParseCombiError {
@@ -56,9 +91,135 @@ impl Display for ParseCombiErrorKind {
 impl From<io::Error> for ParseCombiError {
     fn from(value: io::Error) -> Self {
         ParseCombiError {
-            dbg_msg: format!("{:?}", value),
-            msg:     format!("{}", value),
-            kind:    ParseCombiErrorKind::IOError,
+            dbg_msg:     format!("{:?}", value),
+            msg:         format!("{}", value),
+            kind:        ParseCombiErrorKind::IOError,
+            diagnostics: Vec::new(),
         }
     }
 }
+
+/// Error at a location in the ShExML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDiagnostic {
+    /// Byte offsets of the erroneous input in the document
+    pub span:     Range<usize>,
+    /// Line of the start of the span, starting from 1
+    pub line:     usize,
+    /// Column (in characters) of the start of the span, starting from 1
+    pub column:   usize,
+    pub message:  String,
+    /// Inputs which were expected instead of the found input
+    pub expected: Vec<String>,
+}
+
+impl ParseDiagnostic {
+    pub fn from_simple<T: Display + Hash + Eq>(
+        document: &str,
+        err: &Simple<T>,
+    ) -> Self {
+        let found = match err.found() {
+            Some(found) => format!("'{}'", found),
+            None => "end of input".to_string(),
+        };
+        let mut message = match err.reason() {
+            SimpleReason::Unexpected => format!("unexpected {}", found),
+            SimpleReason::Unclosed { delimiter, .. } => {
+                format!("unclosed delimiter '{}'", delimiter)
+            }
+            SimpleReason::Custom(msg) => msg.clone(),
+        };
+        if let Some(label) = err.label() {
+            // Labels are of the form "lexer:sources"
+            let label = label.rsplit(':').next().unwrap_or(label);
+            message = format!("{} in {}", message, label.replace('_', " "));
+        }
+
+        let mut expected: Vec<_> = err
+            .expected()
+            .map(|expected| match expected {
+                Some(expected) => format!("'{}'", expected),
+                None => "end of input".to_string(),
+            })
+            .collect();
+        expected.sort();
+
+        let span = byte_span(document, err.span());
+        let (line, column) = line_column(document, span.start);
+        ParseDiagnostic {
+            span,
+            line,
+            column,
+            message,
+            expected,
+        }
+    }
+
+    /// Renders the diagnostic together with the line of the `document` in
+    /// which the error occurs, e.g.
+    ///
+    /// ```text
+    /// error: unexpected '=' in prefixes
+    ///  --> line 1, column 10
+    ///   |
+    /// 1 | PREFIX ex= <http://example.com/>
+    ///   |          ^
+    ///   = expected one of ':'
+    /// ```
+    pub fn render(&self, document: &str) -> String {
+        let line_str = document.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = " ".repeat(self.line.to_string().len());
+        let line_rest =
+            line_str.chars().count().saturating_sub(self.column - 1);
+        let span_len = document
+            .get(self.span.clone())
+            .map(|spanned| spanned.chars().count())
+            .unwrap_or(1)
+            .clamp(1, line_rest.max(1));
+
+        let mut result = format!(
+            "error: {}\n{} --> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self.message,
+            gutter,
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            line_str,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(span_len)
+        );
+        if !self.expected.is_empty() {
+            result += &format!(
+                "\n{} = expected one of {}",
+                gutter,
+                self.expected.join(", ")
+            );
+        }
+        result
+    }
+}
+
+/// Converts a span of character offsets to a span of byte offsets.
+fn byte_span(document: &str, char_span: Range<usize>) -> Range<usize> {
+    let to_byte_offset = |char_offset: usize| {
+        document
+            .char_indices()
+            .nth(char_offset)
+            .map(|(byte_offset, _)| byte_offset)
+            .unwrap_or(document.len())
+    };
+
+    to_byte_offset(char_span.start)..to_byte_offset(char_span.end)
+}
+
+/// Line and column (in characters) of the byte offset, both starting from 1.
+fn line_column(document: &str, byte_offset: usize) -> (usize, usize) {
+    let preceding = &document[..byte_offset];
+    let line = preceding.matches('\n').count() + 1;
+    let line_start = preceding.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    let column = preceding[line_start..].chars().count() + 1;
+
+    (line, column)
+}
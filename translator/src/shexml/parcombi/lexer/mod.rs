@@ -5,7 +5,7 @@ use chumsky::chain::Chain;
 use chumsky::prelude::*;
 use chumsky::Parser;
 
-use self::token::ShExMLToken;
+use self::token::{ShExMLToken, Span, SpannedToken};
 
 macro_rules! t {
     ($t:ty) => {
@@ -19,19 +19,37 @@ macro_rules! shape_node {
             .or_not()
             .then_ignore(just(':'))
             .then(pn_char().repeated().at_least(1))
-            .map(|(prefix_opt, local)| {
+            .map_with_span(|(prefix_opt, local), span| {
                 let mut prefix: String = "".to_string();
                 let local = local.into_iter().collect();
                 if let Some(found_prefix) = prefix_opt {
                     prefix = found_prefix.into_iter().collect();
                 }
-                ShExMLToken::$t { prefix, local }
+                (ShExMLToken::$t { prefix, local }, span)
             })
     };
 }
 
-fn token(st: &'static str, token: ShExMLToken) -> t!(ShExMLToken) {
-    just(st).padded().to(token)
+/// Wraps the constructor of a token such that the created token is paired
+/// with the span of its input.
+fn spanned<T>(
+    token_fn: impl Fn(T) -> ShExMLToken + Clone,
+) -> impl Fn(T, Span) -> SpannedToken + Clone {
+    move |value, span| (token_fn(value), span)
+}
+
+/// Pairs all the tokens created from the same input with its span.
+fn with_span(tokens: Vec<ShExMLToken>, span: Span) -> Vec<SpannedToken> {
+    tokens
+        .into_iter()
+        .map(|token| (token, span.clone()))
+        .collect()
+}
+
+fn token(st: &'static str, token: ShExMLToken) -> t!(SpannedToken) {
+    just(st)
+        .map_with_span(move |_, span| (token.clone(), span))
+        .padded()
 }
 
 fn within_angled_brackets() -> t!(String) {
@@ -42,15 +60,40 @@ fn within_angled_brackets() -> t!(String) {
         .map(|c| c.into_iter().collect::<String>())
 }
 
-pub fn shexml() -> t!(Vec<ShExMLToken>) {
-    prefixes()
-        .chain::<ShExMLToken, _, _>(sources())
-        .chain::<ShExMLToken, _, _>(iterators())
-        .chain::<ShExMLToken, _, _>(expressions())
-        .chain::<ShExMLToken, _, _>(shapes())
+pub fn shexml() -> t!(Vec<SpannedToken>) {
+    let declarations = recover_statement(
+        choice((just("SOURCE"), just("QUERY"), just("FUNCTIONS"))),
+        choice((sources(), queries(), function())),
+    );
+
+    recover_statement(just("PREFIX"), prefixes())
+        .repeated()
+        .at_least(1)
+        .flatten()
+        .chain::<SpannedToken, _, _>(
+            declarations.repeated().at_least(1).flatten(),
+        )
+        .chain::<SpannedToken, _, _>(iterators())
+        .chain::<SpannedToken, _, _>(expressions())
+        .chain::<SpannedToken, _, _>(shapes())
+        .then_ignore(end())
+}
+
+/// Lexes a statement if the input starts with one of its keywords.
+///
+/// An error inside the statement is recovered from by skipping the rest of
+/// the line, such that the errors in the following statements are reported
+/// as well.
+fn recover_statement<K>(
+    keyword: impl Parser<char, K, Error = Simple<char>> + Clone,
+    statement: t!(Vec<SpannedToken>),
+) -> t!(Vec<SpannedToken>) {
+    keyword.padded().rewind().ignore_then(
+        statement.recover_with(skip_until(['\n'], |_| Vec::new()).skip_start()),
+    )
 }
 
-pub fn shapes() -> t!(Vec<ShExMLToken>) {
+pub fn shapes() -> t!(Vec<SpannedToken>) {
     let shape_node = shape_node!(ShapeNode);
     let predicate = shape_node!(ShapeTerm).or(token("a ", ShExMLToken::Type));
     let graph_node = shape_node!(ShapeNode);
@@ -62,7 +105,7 @@ pub fn shapes() -> t!(Vec<ShExMLToken>) {
         .chain(token(";", ShExMLToken::PredicateSplit));
 
     let subject = prefix_namespace()
-        .chain::<ShExMLToken, _, _>(shape_node_expression())
+        .chain::<SpannedToken, _, _>(shape_node_expression())
         .or(shape_node!(ShapeTerm).map(|term| vec![term]));
 
     let single_shape = shape_node
@@ -71,7 +114,7 @@ pub fn shapes() -> t!(Vec<ShExMLToken>) {
         .padded()
         .chain(token("{", ShExMLToken::CurlStart))
         .padded()
-        .chain::<ShExMLToken, _, _>(
+        .chain::<SpannedToken, _, _>(
             pred_object.repeated().at_least(1).flatten(),
         )
         .padded()
@@ -85,18 +128,32 @@ pub fn shapes() -> t!(Vec<ShExMLToken>) {
                 .at_least(2)
                 .at_most(2),
         )
-        .chain::<ShExMLToken, _, _>(
+        .chain::<SpannedToken, _, _>(
             single_shape.clone().repeated().at_least(1).flatten(),
         )
         .padded()
-        .chain::<ShExMLToken, _, _>(
+        .chain::<SpannedToken, _, _>(
             token("]", ShExMLToken::SqBrackEnd)
                 .repeated()
                 .at_least(2)
                 .at_most(2),
         );
 
-    (with_graph.or(single_shape))
+    // Graphs built from expressions, e.g. `graphs:[films.country] [[`, are
+    // reported as unsupported instead of as unexpected '['
+    let expression_graph = prefix_namespace()
+        .chain::<SpannedToken, _, _>(shape_node_expression())
+        .padded()
+        .then_ignore(just("[[").padded())
+        .try_map(|_, span| {
+            Err(Simple::custom(
+                span,
+                "Graphs built from expressions are not supported, only \
+                 graphs with a static name",
+            ))
+        });
+
+    (with_graph.or(expression_graph).or(single_shape))
         .padded()
         .repeated()
         .at_least(1)
@@ -104,17 +161,25 @@ pub fn shapes() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:shapes")
 }
 
-fn shape_object() -> t!(Vec<ShExMLToken>) {
+fn shape_object() -> t!(Vec<SpannedToken>) {
     let language_tag = token("@", ShExMLToken::AtSymb).chain(
-        shape_node_expression().or(pn_char().repeated().at_least(1).map(
-            |chars| vec![ShExMLToken::LangTag(chars.into_iter().collect())],
-        )),
+        shape_node_expression().or(pn_char()
+            .repeated()
+            .at_least(1)
+            .map_with_span(|chars, span| {
+                let lang_tag =
+                    ShExMLToken::LangTag(chars.into_iter().collect());
+                vec![(lang_tag, span)]
+            })),
     );
 
-    let data_type_static = prefix_namespace().chain::<ShExMLToken, _, _>(
-        pn_char().repeated().at_least(1).map(|chars| {
-            vec![ShExMLToken::PrefixLN(chars.into_iter().collect())]
-        }),
+    let data_type_static = prefix_namespace().chain::<SpannedToken, _, _>(
+        pn_char()
+            .repeated()
+            .at_least(1)
+            .map_with_span(|chars, span| {
+                vec![(ShExMLToken::PrefixLN(chars.into_iter().collect()), span)]
+            }),
     );
 
     let data_type = choice((
@@ -130,19 +195,19 @@ fn shape_object() -> t!(Vec<ShExMLToken>) {
     let object = choice((
         shape_link,
         object_literal,
-        prefix_namespace().chain::<ShExMLToken, _, _>(shape_node_expression()),
+        prefix_namespace().chain::<SpannedToken, _, _>(shape_node_expression()),
         shape_node_expression(),
     ))
     .padded();
 
     object
         .padded()
-        .chain::<ShExMLToken, _, _>(language_tag.or(data_type).or_not())
+        .chain::<SpannedToken, _, _>(language_tag.or(data_type).or_not())
         .padded()
         .labelled("lexer:shape_object")
 }
 
-fn shape_node_expression() -> t!(Vec<ShExMLToken>) {
+fn shape_node_expression() -> t!(Vec<SpannedToken>) {
     let matching = shape_sub_ident()
         .padded()
         .chain(token("MATCHING", ShExMLToken::Matching))
@@ -151,7 +216,7 @@ fn shape_node_expression() -> t!(Vec<ShExMLToken>) {
     let if_block = shape_sub_ident()
         .padded()
         .chain(token("IF", ShExMLToken::If))
-        .chain::<ShExMLToken, _, _>(shape_function_application());
+        .chain::<SpannedToken, _, _>(shape_function_application());
 
     token("[", ShExMLToken::SqBrackStart)
         .chain(choice((
@@ -164,7 +229,7 @@ fn shape_node_expression() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:shape_node_expression")
 }
 
-fn shape_sub_ident() -> t!(Vec<ShExMLToken>) {
+fn shape_sub_ident() -> t!(Vec<SpannedToken>) {
     ident()
         .chain(
             token(".", ShExMLToken::Dot)
@@ -175,11 +240,11 @@ fn shape_sub_ident() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:shape_sub_ident")
 }
 
-fn shape_function_application() -> t!(Vec<ShExMLToken>) {
+fn shape_function_application() -> t!(Vec<SpannedToken>) {
     shape_sub_ident()
         .chain(token("(", ShExMLToken::BrackStart))
-        .chain::<ShExMLToken, _, _>(shape_sub_ident())
-        .chain::<ShExMLToken, _, _>(
+        .chain::<SpannedToken, _, _>(shape_sub_ident())
+        .chain::<SpannedToken, _, _>(
             token(",", ShExMLToken::Comma)
                 .padded()
                 .chain(shape_sub_ident())
@@ -190,22 +255,33 @@ fn shape_function_application() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:shape_function_application")
 }
 
-pub fn expressions() -> t!(Vec<ShExMLToken>) {
-    expression_stmt()
+pub fn expressions() -> t!(Vec<SpannedToken>) {
+    let keyword = choice((
+        just("EXPRESSION"),
+        just("MATCHER"),
+        just("AUTOINCREMENT"),
+        just("FUNCTIONS"),
+    ));
+    let statement = expression_stmt()
         .or(matcher())
         .or(autoincrement())
-        .or(function())
+        .or(function());
+
+    recover_statement(keyword, statement)
         .padded()
         .repeated()
         .flatten()
 }
 
-pub fn function() -> t!(Vec<ShExMLToken>) {
+pub fn function() -> t!(Vec<SpannedToken>) {
     let function_tag = token("FUNCTIONS", ShExMLToken::Function).padded();
     let function_ident = ident().padded();
 
-    let protocol = protocol().padded().map(ShExMLToken::FunctionLang);
-    let uri = protocol_iri_ref().or(path().map(ShExMLToken::URI));
+    let protocol = protocol()
+        .padded()
+        .map_with_span(spanned(ShExMLToken::FunctionLang));
+    let uri =
+        protocol_iri_ref().or(path().map_with_span(spanned(ShExMLToken::URI)));
 
     let function_exp = token("<", ShExMLToken::AngleStart)
         .padded()
@@ -217,44 +293,52 @@ pub fn function() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:function")
 }
 
-pub fn autoincrement() -> t!(Vec<ShExMLToken>) {
+pub fn autoincrement() -> t!(Vec<SpannedToken>) {
     let aut_inc_tag =
         token("AUTOINCREMENT", ShExMLToken::AutoIncrement).padded();
     let ident = ident().padded();
-    let prefix_str = text::ident::<char, _>()
-        .map(|chars: String| ShExMLToken::AutoIncPrefix(chars))
-        .delimited_by(just('"'), just('"'))
+    let quoted_str = none_of('"')
+        .repeated()
+        .collect::<String>()
+        .delimited_by(just('"'), just('"'));
+    let prefix_str = quoted_str
+        .clone()
+        .map_with_span(spanned(ShExMLToken::AutoIncPrefix))
         .then_ignore(just('+').padded());
 
     let sufix_str = just('+').padded().ignore_then(
-        text::ident()
-            .map(|chars: String| ShExMLToken::AutoIncSuffix(chars))
-            .delimited_by(just('"'), just('"')),
+        quoted_str.map_with_span(spanned(ShExMLToken::AutoIncSuffix)),
     );
 
     let start_inc = text::digits::<char, _>(10)
         .padded()
-        .map(|digit: String| ShExMLToken::AutoIncStart(digit.parse().unwrap()));
+        .map_with_span(spanned(|digit: String| {
+            ShExMLToken::AutoIncStart(digit.parse().unwrap())
+        }));
 
     let end_inc =
-        just("to").padded().ignore_then(text::digits(10).map(
-            |digit: String| ShExMLToken::AutoIncEnd(digit.parse().unwrap()),
+        just("to").padded().ignore_then(text::digits(10).map_with_span(
+            spanned(|digit: String| {
+                ShExMLToken::AutoIncEnd(digit.parse().unwrap())
+            }),
         ));
 
     let step_inc =
-        just("by").padded().ignore_then(text::digits(10).map(
-            |digit: String| ShExMLToken::AutoIncStep(digit.parse().unwrap()),
+        just("by").padded().ignore_then(text::digits(10).map_with_span(
+            spanned(|digit: String| {
+                ShExMLToken::AutoIncStep(digit.parse().unwrap())
+            }),
         ));
 
     let aut_inc_exp = prefix_str
         .or_not()
-        .chain::<ShExMLToken, _, _>(start_inc)
-        .chain::<ShExMLToken, _, _>(end_inc.or_not())
-        .chain::<ShExMLToken, _, _>(step_inc.or_not())
-        .chain::<ShExMLToken, _, _>(sufix_str.or_not());
+        .chain::<SpannedToken, _, _>(start_inc)
+        .chain::<SpannedToken, _, _>(end_inc.or_not())
+        .chain::<SpannedToken, _, _>(step_inc.or_not())
+        .chain::<SpannedToken, _, _>(sufix_str.or_not());
 
     let auto_inc_exp_delim = token("<", ShExMLToken::AngleStart)
-        .chain::<ShExMLToken, Vec<_>, _>(aut_inc_exp)
+        .chain::<SpannedToken, Vec<_>, _>(aut_inc_exp)
         .chain(token(">", ShExMLToken::AngleEnd));
 
     (aut_inc_tag.chain(ident).chain(auto_inc_exp_delim))
@@ -262,7 +346,7 @@ pub fn autoincrement() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:autoincrement")
 }
 
-pub fn matcher() -> t!(Vec<ShExMLToken>) {
+pub fn matcher() -> t!(Vec<SpannedToken>) {
     let mat_tag = token("MATCHER", ShExMLToken::Matcher);
     let mat_ident = ident().padded();
     let mats_value = none_of("<>,&")
@@ -275,7 +359,7 @@ pub fn matcher() -> t!(Vec<ShExMLToken>) {
                 .map(|c| c.to_string())
                 .collect::<Vec<String>>()
         })
-        .map(|mut splitted_str| {
+        .map_with_span(|mut splitted_str, span| {
             let mut result = vec![];
 
             if splitted_str.len() > 1 {
@@ -292,7 +376,7 @@ pub fn matcher() -> t!(Vec<ShExMLToken>) {
                 let value = splitted_str.pop().unwrap();
                 result.push(ShExMLToken::Value(value.trim().to_string()));
             }
-            result
+            with_span(result, span)
         });
 
     (mat_tag.chain(mat_ident).chain(
@@ -321,12 +405,12 @@ pub fn matcher() -> t!(Vec<ShExMLToken>) {
     .labelled("lexer:matcher")
 }
 
-pub fn expression_stmt() -> t!(Vec<ShExMLToken>) {
+pub fn expression_stmt() -> t!(Vec<SpannedToken>) {
     let expressiont_tag = token("EXPRESSION", ShExMLToken::Expression);
     let exp_ident = ident().padded();
 
     let sub_ident = ident()
-        .chain(just('.').to(ShExMLToken::Dot))
+        .chain(just('.').map_with_span(|_, span| (ShExMLToken::Dot, span)))
         .repeated()
         .at_least(1)
         .flatten()
@@ -340,9 +424,9 @@ pub fn expression_stmt() -> t!(Vec<ShExMLToken>) {
                 .repeated()
                 .at_least(1)
                 .delimited_by(just('"'), just('"'))
-                .map(|string_sep| {
+                .map_with_span(spanned(|string_sep: Vec<char>| {
                     ShExMLToken::StringSep(string_sep.into_iter().collect())
-                })
+                }))
                 .then_ignore(just('+').padded()),
         )
         .then(sub_ident.clone().padded())
@@ -369,7 +453,7 @@ pub fn expression_stmt() -> t!(Vec<ShExMLToken>) {
     let join = basic_expression
         .clone()
         .chain(union_tok.clone())
-        .chain::<ShExMLToken, _, _>(basic_expression.clone())
+        .chain::<SpannedToken, _, _>(basic_expression.clone())
         .chain(join_tok.clone())
         .chain(basic_expression.clone())
         .padded()
@@ -399,16 +483,16 @@ pub fn expression_stmt() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:expression_stmt")
 }
 
-pub fn iterators() -> t!(Vec<ShExMLToken>) {
+pub fn iterators() -> t!(Vec<SpannedToken>) {
     let header = iterator_header().padded();
 
     (recursive(|recur| {
         header
             .chain(token("{", ShExMLToken::BrackStart))
-            .chain::<ShExMLToken, _, _>(
+            .chain::<SpannedToken, _, _>(
                 field().repeated().at_least(1).flatten(),
             )
-            .chain::<ShExMLToken, _, _>(
+            .chain::<SpannedToken, _, _>(
                 token("}", ShExMLToken::BrackEnd)
                     .map(|tok| vec![tok])
                     .or(recur),
@@ -427,7 +511,7 @@ pub fn iterators() -> t!(Vec<ShExMLToken>) {
     .labelled("lexer:iterators")
 }
 
-fn field() -> t!(Vec<ShExMLToken>) {
+fn field() -> t!(Vec<SpannedToken>) {
     let field_tag = token("FIELD", ShExMLToken::Field);
     let push_field_tag = token("PUSHED_FIELD", ShExMLToken::PushField);
     let pop_field_tag = token("POPPED_FIELD", ShExMLToken::PopField);
@@ -435,7 +519,7 @@ fn field() -> t!(Vec<ShExMLToken>) {
     let field_query = within_angled_brackets()
         .padded()
         .delimited_by(just("<"), just(">"))
-        .map(ShExMLToken::FieldQuery);
+        .map_with_span(spanned(ShExMLToken::FieldQuery));
 
     choice((field_tag, push_field_tag, pop_field_tag))
         .chain(field_name)
@@ -443,11 +527,13 @@ fn field() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:field")
 }
 
-fn iterator_header() -> t!(Vec<ShExMLToken>) {
+fn iterator_header() -> t!(Vec<SpannedToken>) {
     let iterator_tag = token("ITERATOR", ShExMLToken::Iterator);
     let iterator_name = ident().padded();
 
-    let iterator_type = protocol().padded().map(ShExMLToken::IteratorType);
+    let iterator_type = protocol()
+        .padded()
+        .map_with_span(spanned(ShExMLToken::IteratorType));
     let csv_iterator_type = pn_char()
         .repeated()
         .at_least(1)
@@ -456,7 +542,7 @@ fn iterator_header() -> t!(Vec<ShExMLToken>) {
         .map(|iter_string| iter_string.to_lowercase())
         .try_map(|lowered_string, span| {
             if lowered_string == "csvperrow" {
-                Ok(ShExMLToken::IteratorType(lowered_string))
+                Ok((ShExMLToken::IteratorType(lowered_string), span))
             } else {
                 Err(Simple::custom(
                     span,
@@ -465,15 +551,15 @@ fn iterator_header() -> t!(Vec<ShExMLToken>) {
             }
         });
 
-    let iterator_query =
-        within_angled_brackets().map(ShExMLToken::IteratorQuery);
+    let iterator_query = within_angled_brackets()
+        .map_with_span(spanned(ShExMLToken::IteratorQuery));
 
     let iter_query_pair = token("<", ShExMLToken::AngleStart)
         .chain(
             iterator_type
                 .or(csv_iterator_type)
                 .or_not()
-                .chain::<ShExMLToken, _, _>(iterator_query.or_not()),
+                .chain::<SpannedToken, _, _>(iterator_query.or_not()),
         )
         .chain(token(">", ShExMLToken::AngleEnd));
 
@@ -486,14 +572,19 @@ fn iterator_header() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:iterator_header")
 }
 
-pub fn sources() -> t!(Vec<ShExMLToken>) {
+pub fn sources() -> t!(Vec<SpannedToken>) {
     let source_tag = token("SOURCE", ShExMLToken::Source);
     let source_name = ident().padded();
 
     let source_iri =
         token("<", ShExMLToken::AngleStart)
             .chain(protocol_iri_tokenize().or(
-                path().map(|c| vec![ShExMLToken::File, ShExMLToken::URI(c)]),
+                path().map_with_span(|c, span: Span| {
+                    vec![
+                        (ShExMLToken::File, span.clone()),
+                        (ShExMLToken::URI(c), span),
+                    ]
+                }),
             ))
             .chain(token(">", ShExMLToken::AngleEnd));
     source_tag
@@ -506,15 +597,49 @@ pub fn sources() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:sources")
 }
 
-fn ident() -> t!(ShExMLToken) {
+pub fn queries() -> t!(Vec<SpannedToken>) {
+    let query_tag = token("QUERY", ShExMLToken::Query);
+    let query_name = ident().padded();
+
+    let query_type = protocol()
+        .padded()
+        .map_with_span(spanned(ShExMLToken::IteratorType));
+    let inline_query = query_type.chain(
+        within_angled_brackets()
+            .map_with_span(spanned(ShExMLToken::IteratorQuery)),
+    );
+    let query_uri = protocol_iri_ref()
+        .map(|uri| vec![uri])
+        .or(inline_query)
+        .or(path().map_with_span(|uri, span| {
+            vec![(ShExMLToken::URI(uri), span)]
+        }));
+
+    let query_content = token("<", ShExMLToken::AngleStart)
+        .chain(query_uri)
+        .chain(token(">", ShExMLToken::AngleEnd));
+
+    query_tag
+        .chain(query_name)
+        .chain(query_content)
+        .padded()
+        .repeated()
+        .at_least(1)
+        .flatten()
+        .labelled("lexer:queries")
+}
+
+fn ident() -> t!(SpannedToken) {
     pn_char()
         .repeated()
         .at_least(1)
-        .map(|v| ShExMLToken::Ident(v.into_iter().collect()))
+        .map_with_span(spanned(|v: Vec<char>| {
+            ShExMLToken::Ident(v.into_iter().collect())
+        }))
         .labelled("lexer:ident")
 }
 
-pub fn prefixes() -> t!(Vec<ShExMLToken>) {
+pub fn prefixes() -> t!(Vec<SpannedToken>) {
     let prefix_tag = token("PREFIX", ShExMLToken::Prefix);
     let pname = prefix_namespace();
 
@@ -532,21 +657,22 @@ pub fn prefixes() -> t!(Vec<ShExMLToken>) {
         .labelled("lexer:prefixes")
 }
 
-fn prefix_namespace() -> t!(Vec<ShExMLToken>) {
+fn prefix_namespace() -> t!(Vec<SpannedToken>) {
     pn_prefix()
         .or(just("_").map(|_| vec!['_']))
         .or_not()
         .then_ignore(just(":"))
-        .padded()
         .flatten()
-        .map(|pname_vec| {
-            if pname_vec.is_empty() {
+        .map_with_span(|pname_vec, span| {
+            let tokens = if pname_vec.is_empty() {
                 vec![ShExMLToken::BasePrefix, ShExMLToken::PrefixSep]
             } else {
                 let prefix = pname_vec.into_iter().collect();
                 vec![ShExMLToken::PrefixNS(prefix), ShExMLToken::PrefixSep]
-            }
+            };
+            with_span(tokens, span)
         })
+        .padded()
         .labelled("lexer:prefix_namespace")
 }
 
@@ -574,9 +700,9 @@ fn protocol_iri_ref_new() -> t!(Vec<String>) {
         .chain(path())
 }
 
-fn protocol_iri_tokenize() -> t!(Vec<ShExMLToken>) {
-    protocol_iri_ref_new().map(|vec_string| {
-        if vec_string.len() == 1 {
+fn protocol_iri_tokenize() -> t!(Vec<SpannedToken>) {
+    protocol_iri_ref_new().map_with_span(|vec_string, span| {
+        let tokens = if vec_string.len() == 1 {
             vec_string
                 .into_iter()
                 .map(|path| ShExMLToken::URI(path))
@@ -606,15 +732,18 @@ fn protocol_iri_tokenize() -> t!(Vec<ShExMLToken>) {
             result.push(ShExMLToken::URI(uri_string));
 
             result
-        }
+        };
+        with_span(tokens, span)
     })
 }
 
-fn protocol_iri_ref() -> t!(ShExMLToken) {
+fn protocol_iri_ref() -> t!(SpannedToken) {
     let uri = protocol()
         .chain::<String, _, _>(just("//").map(|c| c.to_string()))
         .chain(path())
-        .map(|z: Vec<String>| ShExMLToken::URI(z.into_iter().collect()));
+        .map_with_span(spanned(|z: Vec<String>| {
+            ShExMLToken::URI(z.into_iter().collect())
+        }));
 
     uri
 }
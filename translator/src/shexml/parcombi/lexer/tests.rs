@@ -1,6 +1,14 @@
 #[cfg(test)]
 use super::*;
 
+#[cfg(test)]
+fn strip_spans(
+    tokens_opt: Option<Vec<SpannedToken>>,
+) -> Option<Vec<ShExMLToken>> {
+    tokens_opt
+        .map(|tokens| tokens.into_iter().map(|(token, _)| token).collect())
+}
+
 #[test]
 fn protocol_test() {
     let proc1 = "jdbc:".to_string();
@@ -55,6 +63,7 @@ EXPRESSION films <films_xml_file.film_xml UNION films_json_file.film_json>
 } "#;
 
     let (tokens_opt, errors) = shexml().parse_recovery(shexml_doc);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
 
@@ -75,6 +84,7 @@ fn function_if_shape_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -169,6 +179,7 @@ fn function_shape_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -253,6 +264,7 @@ fn matching_shape_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -335,6 +347,7 @@ fn graphed_shape_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -408,6 +421,7 @@ fn simple_single_shape_matching() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -469,6 +483,7 @@ fn simple_two_shape_linking_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -548,6 +563,7 @@ fn simple_single_shape_linking() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -601,6 +617,7 @@ fn simple_single_shape_object_literal() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -653,6 +670,7 @@ fn simple_single_shape_datatype_static() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -710,6 +728,7 @@ fn simple_single_shape_datatype_dynamic() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -788,6 +807,7 @@ fn simple_single_shape_languagetag_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -861,6 +881,7 @@ fn simple_single_shape_subject_fixed_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
 
@@ -906,6 +927,7 @@ fn simple_single_shape_class_type_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -956,6 +978,7 @@ fn simple_single_shape_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(shape_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:#?}", tokens_opt);
     let film_exp = ShExMLToken::Ident("films".to_string());
@@ -1017,6 +1040,7 @@ fn function_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(function_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:?}", tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1047,6 +1071,7 @@ fn auto_inc_only_start_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(match_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:?}", tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1076,6 +1101,7 @@ fn auto_inc_end_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(match_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:?}", tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1107,6 +1133,7 @@ fn auto_inc_start_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(match_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:?}", tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1137,6 +1164,7 @@ fn auto_inc_complete_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(match_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:?}", tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1160,6 +1188,39 @@ fn auto_inc_complete_test() {
     )
 }
 
+#[test]
+fn auto_inc_non_ident_strings_test() {
+    let match_str = "
+     AUTOINCREMENT my_code <\"something: \" + 1 by 2 + \" ending\">
+     ";
+
+    let (tokens_opt, errors) = autoincrement()
+        .padded()
+        .then_ignore(end())
+        .parse_recovery(match_str);
+    let tokens_opt = strip_spans(tokens_opt);
+
+    println!("{:?}", tokens_opt);
+    assert!(errors.len() == 0, "{:?}", errors);
+    let expected = Some(vec![
+        ShExMLToken::AutoIncrement,
+        ShExMLToken::Ident("my_code".to_string()),
+        ShExMLToken::AngleStart,
+        ShExMLToken::AutoIncPrefix("something: ".to_string()),
+        ShExMLToken::AutoIncStart(1),
+        ShExMLToken::AutoIncStep(2),
+        ShExMLToken::AutoIncSuffix(" ending".to_string()),
+        ShExMLToken::AngleEnd,
+    ]);
+
+    assert!(
+        tokens_opt == expected,
+        "Expected output is: {:#?}\nGenerated output was: {:#?}",
+        expected,
+        tokens_opt,
+    )
+}
+
 #[test]
 fn multiple_matching_matcher_test() {
     let match_str = "
@@ -1171,6 +1232,7 @@ fn multiple_matching_matcher_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(match_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     println!("{:?}", tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1186,6 +1248,7 @@ fn single_matcher_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(match_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     let expected_tokens = Some(vec![
         ShExMLToken::Matcher,
@@ -1223,6 +1286,7 @@ fn string_op_union_expression_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery_verbose(exp_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     assert!(errors.len() == 0, "{:?}", errors);
     println!("{:?}", tokens_opt);
@@ -1269,6 +1333,7 @@ fn string_op_expression_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(exp_str);
+    let tokens_opt = strip_spans(tokens_opt);
 
     assert!(errors.len() == 0, "{:?}", errors);
     println!("{:?}", tokens_opt);
@@ -1309,6 +1374,7 @@ fn iterator_expression_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(exp_str);
+    let tokens_opt = strip_spans(tokens_opt);
     let file_ident = ShExMLToken::Ident("file".to_string());
 
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1343,6 +1409,7 @@ fn join_union_expression_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(exp_str);
+    let tokens_opt = strip_spans(tokens_opt);
     let file_ident = ShExMLToken::Ident("file".to_string());
 
     assert!(errors.len() == 0, "{:?}", errors);
@@ -1422,6 +1489,7 @@ fn iterator_header_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(iter_str);
+    let tokens_opt = strip_spans(tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_tokens = Some(vec![
         ShExMLToken::Iterator,
@@ -1448,6 +1516,7 @@ fn iterator_csvperrow_test() {
         .padded()
         .then_ignore(end())
         .parse_recovery(iter_str);
+    let tokens_opt = strip_spans(tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_tokens = Some(vec![
         ShExMLToken::Iterator,
@@ -1471,6 +1540,7 @@ fn source_jdbc_test() {
     let source_str =
         "SOURCE sparql_endpoint <jdbc:sparql://localhost:6000/sparql/>";
     let (tokens_opt, errors) = sources().parse_recovery(source_str);
+    let tokens_opt = strip_spans(tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_tokens = Some(vec![
         ShExMLToken::Source,
@@ -1490,10 +1560,45 @@ fn source_jdbc_test() {
     );
 }
 
+#[test]
+fn queries_test() {
+    let query_str = "
+QUERY film_query <https://example.com/filmQuery.sql>
+QUERY films_sparql <sparql: SELECT ?film WHERE { ?film a dbo:Film . }>
+";
+    let (tokens_opt, errors) = queries().parse_recovery(query_str);
+    let tokens_opt = strip_spans(tokens_opt);
+    assert!(errors.len() == 0, "{:?}", errors);
+    let expected_tokens = Some(vec![
+        ShExMLToken::Query,
+        ShExMLToken::Ident("film_query".to_string()),
+        ShExMLToken::AngleStart,
+        ShExMLToken::URI("https://example.com/filmQuery.sql".to_string()),
+        ShExMLToken::AngleEnd,
+        ShExMLToken::Query,
+        ShExMLToken::Ident("films_sparql".to_string()),
+        ShExMLToken::AngleStart,
+        ShExMLToken::IteratorType("sparql:".to_string()),
+        ShExMLToken::IteratorQuery(
+            "SELECT ?film WHERE { ?film a dbo:Film . }".to_string(),
+        ),
+        ShExMLToken::AngleEnd,
+    ]);
+    assert!(
+        tokens_opt == expected_tokens,
+        "{:?} is the parsed tokens
+            {:?} is the expected tokens
+            ",
+        tokens_opt,
+        expected_tokens
+    );
+}
+
 #[test]
 fn source_local_path_test() {
     let source_str = "SOURCE json_file <file.json>";
     let (tokens_opt, errors) = sources().parse_recovery(source_str);
+    let tokens_opt = strip_spans(tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_tokens = Some(vec![
         ShExMLToken::Source,
@@ -1517,6 +1622,7 @@ fn source_local_path_test() {
 fn source_test() {
     let source_str = "SOURCE xml_file <https://example.com/file.xml>";
     let (tokens_opt, errors) = sources().parse_recovery(source_str);
+    let tokens_opt = strip_spans(tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_tokens = Some(vec![
         ShExMLToken::Source,
@@ -1541,6 +1647,7 @@ fn empty_prefix_name_test() {
     let base_prefix = "PREFIX : <https://base.com/>";
 
     let (tokens_opt, errors) = prefixes().parse_recovery(base_prefix);
+    let tokens_opt = strip_spans(tokens_opt);
 
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_tokens = Some(vec![
@@ -1566,6 +1673,7 @@ fn prefix_test() {
     let prefix_1 = "PREFIX ex: <https://example.com/>";
 
     let (tokens_opt, errors) = prefixes().parse_recovery(prefix_1);
+    let tokens_opt = strip_spans(tokens_opt);
 
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_tokens = Some(vec![
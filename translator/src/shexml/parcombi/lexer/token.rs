@@ -1,8 +1,15 @@
+/// Span of the characters in the ShExML document from which a token is
+/// lexed.
+pub type Span = std::ops::Range<usize>;
+
+pub type SpannedToken = (ShExMLToken, Span);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ShExMLToken {
     /// prologue
     Prefix,
     Source,
+    Query,
     Iterator,
     Matcher,
     Expression,
@@ -112,3 +119,66 @@ pub enum ShExMLToken {
     /// ]
     SqBrackEnd,
 }
+
+impl std::fmt::Display for ShExMLToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShExMLToken::Prefix => write!(f, "PREFIX"),
+            ShExMLToken::Source => write!(f, "SOURCE"),
+            ShExMLToken::Query => write!(f, "QUERY"),
+            ShExMLToken::Iterator => write!(f, "ITERATOR"),
+            ShExMLToken::Matcher => write!(f, "MATCHER"),
+            ShExMLToken::Expression => write!(f, "EXPRESSION"),
+            ShExMLToken::AutoIncrement => write!(f, "AUTOINCREMENT"),
+            ShExMLToken::Function => write!(f, "FUNCTIONS"),
+            ShExMLToken::File => write!(f, "file:"),
+            ShExMLToken::HTTP => write!(f, "http:"),
+            ShExMLToken::HTTPS => write!(f, "https:"),
+            ShExMLToken::JDBC(jdbc_type) => write!(f, "jdbc:{}", jdbc_type),
+            ShExMLToken::As => write!(f, "AS"),
+            ShExMLToken::Matching => write!(f, "MATCHING"),
+            ShExMLToken::Field => write!(f, "FIELD"),
+            ShExMLToken::PushField => write!(f, "PUSHED_FIELD"),
+            ShExMLToken::PopField => write!(f, "POPPED_FIELD"),
+            ShExMLToken::Union => write!(f, "UNION"),
+            ShExMLToken::Join => write!(f, "JOIN"),
+            ShExMLToken::StringSep(sep) => write!(f, "\"{}\"", sep),
+            ShExMLToken::If => write!(f, "IF"),
+            ShExMLToken::FunctionLang(lang) => write!(f, "{}", lang),
+            ShExMLToken::AutoIncPrefix(prefix) => write!(f, "\"{}\"", prefix),
+            ShExMLToken::AutoIncStart(start) => write!(f, "{}", start),
+            ShExMLToken::AutoIncEnd(end) => write!(f, "to {}", end),
+            ShExMLToken::AutoIncStep(step) => write!(f, "by {}", step),
+            ShExMLToken::AutoIncSuffix(suffix) => write!(f, "\"{}\"", suffix),
+            ShExMLToken::IteratorType(iter_type) => write!(f, "{}", iter_type),
+            ShExMLToken::IteratorQuery(query) => write!(f, "{}", query),
+            ShExMLToken::BasePrefix => write!(f, ""),
+            ShExMLToken::PrefixNS(prefix) => write!(f, "{}", prefix),
+            ShExMLToken::PrefixLN(local) => write!(f, "{}", local),
+            ShExMLToken::URI(uri) => write!(f, "{}", uri),
+            ShExMLToken::Ident(ident) => write!(f, "{}", ident),
+            ShExMLToken::Type => write!(f, "a"),
+            ShExMLToken::Value(value) => write!(f, "{}", value),
+            ShExMLToken::FieldQuery(query) => write!(f, "{}", query),
+            ShExMLToken::PrefixSep => write!(f, ":"),
+            ShExMLToken::AngleStart => write!(f, "<"),
+            ShExMLToken::AngleEnd => write!(f, ">"),
+            ShExMLToken::CurlStart => write!(f, "{{"),
+            ShExMLToken::CurlEnd => write!(f, "}}"),
+            ShExMLToken::PredicateSplit => write!(f, ";"),
+            ShExMLToken::ObjectSplit | ShExMLToken::Comma => write!(f, ","),
+            ShExMLToken::Dot => write!(f, "."),
+            ShExMLToken::MatcherSplit => write!(f, "&"),
+            ShExMLToken::AtSymb => write!(f, "@"),
+            ShExMLToken::ShapeNode { prefix, local }
+            | ShExMLToken::ShapeTerm { prefix, local } => {
+                write!(f, "{}:{}", prefix, local)
+            }
+            ShExMLToken::LangTag(lang_tag) => write!(f, "{}", lang_tag),
+            ShExMLToken::BrackStart => write!(f, "("),
+            ShExMLToken::BrackEnd => write!(f, ")"),
+            ShExMLToken::SqBrackStart => write!(f, "["),
+            ShExMLToken::SqBrackEnd => write!(f, "]"),
+        }
+    }
+}
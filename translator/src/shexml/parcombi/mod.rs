@@ -1,6 +1,7 @@
 pub mod errors;
 mod lexer;
 mod parser;
+pub mod printer;
 
 use std::fs::File;
 use std::io::Read;
@@ -8,6 +9,7 @@ use std::path::Path;
 
 use chumsky::Parser;
 use errors::ShExMLParseCombiResult;
+pub use lexer::token::{ShExMLToken, Span, SpannedToken};
 pub use parser::r#type::*;
 
 use super::parcombi::errors::{ParseCombiError, ParseCombiErrorKind};
@@ -24,23 +26,132 @@ pub fn parse_file<P: AsRef<Path>>(
 pub fn parse_string(
     shexml_doc_string: String,
 ) -> ShExMLParseCombiResult<ShExMLDocument> {
-    let tokens_res = lexer::shexml().parse(shexml_doc_string);
+    let tokens = tokenize(&shexml_doc_string)?;
 
-    let tokens = tokens_res.map_err(|err| {
-        ParseCombiError {
-            dbg_msg: format!("{:?}", err),
-            msg:     format!("{}", ParseCombiErrorKind::LexerError),
-            kind:    ParseCombiErrorKind::LexerError,
+    let end_of_input = shexml_doc_string.chars().count();
+    let (shexml_doc_opt, parser_errors) = parser::shexml()
+        .parse_recovery(parser::token_stream(tokens, end_of_input));
+
+    match shexml_doc_opt {
+        Some(shexml_doc) if parser_errors.is_empty() => Ok(shexml_doc),
+        _ => {
+            Err(ParseCombiError::from_simple_errors(
+                &shexml_doc_string,
+                parser_errors,
+                ParseCombiErrorKind::ParserError,
+            ))
         }
-    })?;
+    }
+}
 
-    let shexml_doc_res = parser::shexml().parse(tokens);
+/// Lexes the ShExML document into tokens paired with the span of the
+/// characters they are lexed from, e.g. to look up the identifiers in the
+/// document.
+pub fn tokenize(
+    shexml_doc_string: &str,
+) -> ShExMLParseCombiResult<Vec<SpannedToken>> {
+    let (tokens_opt, lexer_errors) = lexer::shexml()
+        .parse_recovery(blank_comment_lines(shexml_doc_string).as_str());
 
-    shexml_doc_res.map_err(|err| {
-        ParseCombiError {
-            dbg_msg: format!("{:?}", err),
-            msg:     format!("{}", ParseCombiErrorKind::ParserError),
-            kind:    ParseCombiErrorKind::ParserError,
+    match tokens_opt {
+        Some(tokens) if lexer_errors.is_empty() => Ok(tokens),
+        _ => {
+            Err(ParseCombiError::from_simple_errors(
+                shexml_doc_string,
+                lexer_errors,
+                ParseCombiErrorKind::LexerError,
+            ))
         }
-    })
+    }
+}
+
+/// Replaces the lines which are commented out with `#` by whitespace, keeping
+/// the positions of the other characters in the document intact.
+fn blank_comment_lines(shexml_doc_string: &str) -> String {
+    shexml_doc_string
+        .split_inclusive('\n')
+        .map(|line| {
+            if line.trim_start().starts_with('#') {
+                line.chars()
+                    .map(|c| if c.is_whitespace() { c } else { ' ' })
+                    .collect()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lexer_errors_location_test() {
+        let document = "PREFIX : <http://example.com/>
+SOURCE films_csv <>
+ITERATOR film_csv <csvperrow> {
+    FIELD id <id>
+}
+EXPRESSION films films_csv.film_csv.id>
+
+:Films :[films.id] {
+    :id [films.id] ;
+}
+";
+        let err = parse_string(document.to_string()).unwrap_err();
+
+        assert!(matches!(err.kind, ParseCombiErrorKind::LexerError));
+        let locations: Vec<_> = err
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.column))
+            .collect();
+        assert_eq!(locations, vec![(2, 19), (6, 18)]);
+        assert_eq!(err.diagnostics[1].expected, vec!["'<'".to_string()]);
+        assert!(
+            err.msg.contains("6 | EXPRESSION films films_csv.film_csv.id>"),
+            "{}",
+            err.msg
+        );
+    }
+
+    #[test]
+    fn parser_errors_location_test() {
+        let document = "PREFIX : <http://example.com/>
+SOURCE films_csv <films.csv>
+ITERATOR film_csv <csvperrow> {
+    FIELD id <id>
+}
+EXPRESSION films <films_csv.film_csv.id>
+MATCHER first <a & b AS c>
+MATCHER second <d>
+
+:Films :[films.id] {
+    :id [films.id] ;
+}
+";
+        let err = parse_string(document.to_string()).unwrap_err();
+
+        assert!(matches!(err.kind, ParseCombiErrorKind::ParserError));
+        let locations: Vec<_> = err
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.column))
+            .collect();
+        assert_eq!(locations, vec![(7, 18), (8, 18)]);
+        assert!(err.diagnostics[0].message.starts_with("unexpected '&'"));
+        assert!(err.diagnostics[0].expected.contains(&"'AS'".to_string()));
+    }
+
+    #[test]
+    fn blank_comment_lines_test() {
+        let document = "PREFIX : <http://example.com/>\n  # a comment\n:a";
+        let blanked = blank_comment_lines(document);
+
+        assert_eq!(
+            blanked,
+            "PREFIX : <http://example.com/>\n             \n:a"
+        );
+    }
 }
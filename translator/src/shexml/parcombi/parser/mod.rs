@@ -4,6 +4,7 @@ use std::collections::HashMap;
 
 use chumsky::prelude::*;
 use chumsky::Parser;
+use chumsky::Stream;
 
 use self::r#type::*;
 use super::lexer::token::*;
@@ -43,24 +44,104 @@ fn token_prefix_shex_pns() -> t!(PrefixNameSpace) {
     .labelled("parser:token_prefix_shex_pns")
 }
 
+/// Declarations which can be given in any order before the iterators.
+enum Declaration {
+    Sources(Vec<Source>),
+    Queries(Vec<Query>),
+    Function(ExpressionEnum),
+}
+
+/// Tokens at which the parser continues after an error in a statement: the
+/// keywords starting a new statement and the `>` ending a statement.
+const STATEMENT_BOUNDARIES: [ShExMLToken; 9] = [
+    ShExMLToken::Prefix,
+    ShExMLToken::Source,
+    ShExMLToken::Query,
+    ShExMLToken::Function,
+    ShExMLToken::Iterator,
+    ShExMLToken::Expression,
+    ShExMLToken::Matcher,
+    ShExMLToken::AutoIncrement,
+    ShExMLToken::AngleEnd,
+];
+
+/// Creates the token stream for the parser from the lexed tokens, keeping
+/// the spans of the tokens in the ShExML document.
+pub fn token_stream(
+    tokens: Vec<SpannedToken>,
+    end_of_input: usize,
+) -> Stream<'static, ShExMLToken, Span, std::vec::IntoIter<SpannedToken>> {
+    Stream::from_iter(end_of_input..end_of_input + 1, tokens.into_iter())
+}
+
+/// Parses a statement if the input starts with one of the given `keywords`.
+///
+/// An error inside the statement is recovered from by skipping the tokens
+/// until the end of the statement or the start of the next statement, such
+/// that the errors in the following statements are reported as well.
+fn recover_statement<O, const N: usize>(
+    keywords: [ShExMLToken; N],
+    statement: t!(O),
+) -> t!(Option<O>) {
+    let recovered_statement = statement
+        .map(Some)
+        .recover_with(skip_until(STATEMENT_BOUNDARIES, |_| None).skip_start());
+
+    one_of(keywords)
+        .rewind()
+        .ignore_then(recovered_statement)
+        .then_ignore(just(ShExMLToken::AngleEnd).or_not())
+}
+
 pub fn shexml() -> t!(ShExMLDocument) {
+    let declaration = recover_statement(
+        [
+            ShExMLToken::Source,
+            ShExMLToken::Query,
+            ShExMLToken::Function,
+        ],
+        choice((
+            sources().map(Declaration::Sources),
+            queries().map(Declaration::Queries),
+            function().map(Declaration::Function),
+        )),
+    );
+
     prefixes()
-        .then(sources())
+        .then(declaration.repeated().at_least(1).flatten())
         .then(iterators())
-        .map(|((prefixes, sources), iters)| (prefixes, sources, iters))
         .then(expressions())
         .then(graph_shapes())
+        .then_ignore(end())
         .map(
             |(
-                ((mut prefixes, sources, iterators), expressions),
+                (((mut prefixes, declarations), iterators), expressions),
                 graph_shapes,
             )| {
+                let mut sources = Vec::new();
+                let mut queries = Vec::new();
+                let mut leading_functions = Vec::new();
+                for declaration in declarations {
+                    match declaration {
+                        Declaration::Sources(decl_sources) => {
+                            sources.extend(decl_sources)
+                        }
+                        Declaration::Queries(decl_queries) => {
+                            queries.extend(decl_queries)
+                        }
+                        Declaration::Function(function) => {
+                            leading_functions.push(function)
+                        }
+                    }
+                }
+
                 let mut matchers = Vec::new();
                 let mut auto_increments = Vec::new();
                 let mut expression_stmts = Vec::new();
                 let mut functions = Vec::new();
 
-                for expr in expressions {
+                // Functions can also be declared before the iterators
+                for expr in leading_functions.into_iter().chain(expressions) {
                     match expr {
                         ExpressionEnum::ExpressionStmt(stmt) => {
                             expression_stmts.push(stmt)
@@ -87,6 +168,7 @@ pub fn shexml() -> t!(ShExMLDocument) {
                 ShExMLDocument {
                     prefixes,
                     sources,
+                    queries,
                     iterators,
                     expression_stmts,
                     matchers,
@@ -412,13 +494,22 @@ fn shape_expression() -> t!(ShapeExpression) {
 }
 
 fn expressions() -> t!(Vec<ExpressionEnum>) {
-    (expression_stmt()
+    let keywords = [
+        ShExMLToken::Expression,
+        ShExMLToken::Matcher,
+        ShExMLToken::Function,
+        ShExMLToken::AutoIncrement,
+    ];
+    let statement = expression_stmt()
         .or(matcher())
         .or(function())
-        .or(auto_increment()))
-    .repeated()
-    .at_least(1)
-    .labelled("parser:expressions")
+        .or(auto_increment());
+
+    recover_statement(keywords, statement)
+        .repeated()
+        .at_least(1)
+        .flatten()
+        .labelled("parser:expressions")
 }
 
 fn function() -> t!(ExpressionEnum) {
@@ -616,6 +707,34 @@ fn sources() -> t!(Vec<Source>) {
         .labelled("parser:sources")
 }
 
+fn queries() -> t!(Vec<Query>) {
+    let inline_query = unfold_token_value!(IteratorType)
+        .try_map(|iter_type, span| {
+            iter_type
+                .parse::<IteratorType>()
+                .map_err(|err| Simple::custom(span, format!("{:?}", err)))
+        })
+        .then(unfold_token_value!(IteratorQuery))
+        .map(|(iter_type, query)| {
+            (Some(iter_type), QueryContent::Inline(query))
+        });
+    let external_query = unfold_token_value!(URI)
+        .map(|uri| (None, QueryContent::External(uri)));
+
+    just(ShExMLToken::Query)
+        .ignore_then(unfold_token_value!(Ident))
+        .then(inline_query.or(external_query).delimited_by(
+            just(ShExMLToken::AngleStart),
+            just(ShExMLToken::AngleEnd),
+        ))
+        .map(|(ident, (iter_type, content))| {
+            Query::new(ident, iter_type, content)
+        })
+        .repeated()
+        .at_least(1)
+        .labelled("parser:queries")
+}
+
 fn iterators() -> t!(Vec<Iterator>) {
     let fields = field().repeated().at_least(1);
 
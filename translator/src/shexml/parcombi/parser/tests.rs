@@ -4,9 +4,21 @@ use crate::shexml::parcombi::parser;
 use crate::shexml::parcombi::lexer;
 use crate::shexml::parcombi::*; 
 use chumsky::prelude::*; 
+#[cfg(test)]
+use chumsky::Stream;
+#[cfg(test)]
+use crate::shexml::parcombi::lexer::token::{ShExMLToken, Span, SpannedToken};
 
  
 
+#[cfg(test)]
+fn stream(
+    tokens: Vec<SpannedToken>,
+) -> Stream<'static, ShExMLToken, Span, std::vec::IntoIter<SpannedToken>> {
+    let end_of_input = tokens.last().map(|(_, span)| span.end).unwrap_or(0);
+    parser::token_stream(tokens, end_of_input)
+}
+
 fn assert_parse_expected<T: std::fmt::Debug + PartialEq + Eq>(
     parsed_items: Option<T>,
     expected_items: Option<T>,
@@ -21,6 +33,47 @@ fn assert_parse_expected<T: std::fmt::Debug + PartialEq + Eq>(
     );
 }
 
+
+/// Items which are printed as ShExML by the round trip tests.
+#[cfg(test)]
+trait Printed {
+    fn printed(&self) -> String;
+}
+
+#[cfg(test)]
+impl<T: std::fmt::Display> Printed for Vec<T> {
+    fn printed(&self) -> String {
+        printer::print_lines(self)
+    }
+}
+
+#[cfg(test)]
+impl Printed for ExpressionEnum {
+    fn printed(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Checks that the printed items are parsed back into the same items.
+#[cfg(test)]
+fn assert_round_trip<T: Printed + std::fmt::Debug + PartialEq>(
+    expected_items: &Option<T>,
+    lexer: impl Parser<char, Vec<SpannedToken>, Error = Simple<char>>,
+    parser: impl Parser<ShExMLToken, T, Error = Simple<ShExMLToken>>,
+) {
+    let printed = expected_items.as_ref().unwrap().printed();
+
+    let (tokens_opt, errors) =
+        lexer.then_ignore(end()).parse_recovery(printed.as_str());
+    assert!(errors.is_empty(), "{}\n{:?}", printed, errors);
+
+    let (parsed_items, errors) = parser
+        .then_ignore(end())
+        .parse_recovery(stream(tokens_opt.unwrap()));
+    assert!(errors.is_empty(), "{}\n{:?}", printed, errors);
+    assert_eq!(&parsed_items, expected_items, "{}", printed);
+}
+
 #[test]
 fn shexml_simple_test() {
     let document_str = "
@@ -56,11 +109,16 @@ EXPRESSION films <films_xml_file.film_xml UNION films_json_file.film_json>
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shexml().parse_recovery_verbose(tokens_opt.unwrap());
+        parser::shexml().parse_recovery_verbose(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
     assert!(parsed_items.is_some(), "{:?}", parsed_items);
+
+    let document = parsed_items.unwrap();
+    let printed = printer::print(&document);
+    let reparsed = parse_string(printed.clone());
+    assert_eq!(reparsed.ok(), Some(document), "{}", printed);
 }
 
 #[test]
@@ -89,7 +147,7 @@ fn multiple_graph_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::graph_shapes().parse_recovery(tokens_opt.unwrap());
+        parser::graph_shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     let subject = Subject {
         prefix:     PrefixNameSpace::BasePrefix,
@@ -210,6 +268,7 @@ fn multiple_graph_test() {
     ]);
 
     assert!(errors.len() == 0, "{:?}", errors);
+    assert_round_trip(&expected_items, lexer::shapes(), parser::graph_shapes());
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -235,7 +294,7 @@ fn graph_multiple_shapes_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::graph_shapes().parse_recovery(tokens_opt.unwrap());
+        parser::graph_shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     let subject = Subject {
         prefix:     PrefixNameSpace::BasePrefix,
@@ -346,6 +405,7 @@ fn graph_multiple_shapes_test() {
     }]);
 
     assert!(errors.len() == 0, "{:?}", errors);
+    assert_round_trip(&expected_items, lexer::shapes(), parser::graph_shapes());
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -368,7 +428,7 @@ fn graph_shape_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::graph_shapes().parse_recovery(tokens_opt.unwrap());
+        parser::graph_shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     let subject = Subject {
         prefix:     PrefixNameSpace::BasePrefix,
@@ -468,6 +528,7 @@ fn graph_shape_test() {
     }]);
 
     assert!(errors.len() == 0, "{:?}", errors);
+    assert_round_trip(&expected_items, lexer::shapes(), parser::graph_shapes());
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -490,7 +551,7 @@ fn shape_condition_if_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -583,6 +644,7 @@ fn shape_condition_if_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -606,7 +668,7 @@ fn shape_function_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -681,6 +743,7 @@ fn shape_function_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -703,7 +766,7 @@ fn shape_simple_matching_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -784,6 +847,7 @@ fn shape_simple_matching_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -810,7 +874,7 @@ fn shape_multiple_link_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -922,6 +986,7 @@ fn shape_multiple_link_test() {
 
     let expected_items = Some(vec![film_shape, actor_shape]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -944,7 +1009,7 @@ fn shape_simple_link_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1022,6 +1087,7 @@ fn shape_simple_link_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1042,7 +1108,7 @@ fn shape_simple_object_literal_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1076,6 +1142,7 @@ fn shape_simple_object_literal_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1097,7 +1164,7 @@ fn shape_simple_static_datatype_languagetag_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1158,6 +1225,7 @@ fn shape_simple_static_datatype_languagetag_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1178,7 +1246,7 @@ fn shape_simple_subject_fixed_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1211,6 +1279,7 @@ fn shape_simple_subject_fixed_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1231,7 +1300,7 @@ fn shape_simple_class_type_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1265,6 +1334,7 @@ fn shape_simple_class_type_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1287,7 +1357,7 @@ fn shape_simple_bnode_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1339,6 +1409,7 @@ fn shape_simple_bnode_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1361,7 +1432,7 @@ fn shape_simple_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (parsed_items, errors) =
-        parser::shapes().parse_recovery(tokens_opt.unwrap());
+        parser::shapes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1413,6 +1484,7 @@ fn shape_simple_test() {
         pred_obj_pairs: pred_obj_pairs.into_iter().collect(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::shapes(), parser::shapes());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1437,7 +1509,7 @@ fn expressions_test() {
     assert!(errors.len() == 0, "{:?}", errors);
 
     let (expressions, errors) =
-        parser::expressions().parse_recovery(tokens_opt.unwrap());
+        parser::expressions().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1505,7 +1577,14 @@ fn expressions_test() {
         step:   None,
     };
 
-    for exp in expressions.unwrap() {
+    let expressions = expressions.unwrap();
+    assert_round_trip(
+        &Some(expressions.clone()),
+        lexer::expressions(),
+        parser::expressions(),
+    );
+
+    for exp in expressions {
         match exp {
             ExpressionEnum::ExpressionStmt(stmt) => {
                 assert!(stmt == expected_stmt, "{:?}", stmt)
@@ -1537,7 +1616,7 @@ fn function_test() {
     println!("{:?}", tokens_opt);
 
     let (parsed_items, errors) =
-        parser::function().parse_recovery(tokens_opt.unwrap());
+        parser::function().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.is_empty(), "{:?}", errors);
 
@@ -1550,6 +1629,81 @@ fn function_test() {
         uri:       "https://raw.githubusercontent.com/herminiogg/ShExML/enhancement-%23121/src/test/resources/functions.scala".to_string(),
     }));
 
+    assert_round_trip(&expected_items, lexer::function(), parser::function());
+    assert_parse_expected(parsed_items, expected_items)
+}
+
+#[test]
+fn queries_test() {
+    let query_str = "
+QUERY film_query <https://example.com/filmQuery.sql>
+QUERY film_sql <sql: SELECT * FROM films;>
+";
+    let (tokens_opt, errors) = lexer::queries()
+        .padded()
+        .then_ignore(end())
+        .parse_recovery(query_str);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let (parsed_items, errors) =
+        parser::queries().parse_recovery(stream(tokens_opt.unwrap()));
+
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let expected_items = Some(vec![
+        Query {
+            ident:     "film_query".to_string(),
+            iter_type: Some(IteratorType::SQL),
+            content:   QueryContent::External(
+                "https://example.com/filmQuery.sql".to_string(),
+            ),
+        },
+        Query {
+            ident:     "film_sql".to_string(),
+            iter_type: Some(IteratorType::SQL),
+            content:   QueryContent::Inline(
+                "SELECT * FROM films;".to_string(),
+            ),
+        },
+    ]);
+
+    assert_round_trip(&expected_items, lexer::queries(), parser::queries());
+    assert_parse_expected(parsed_items, expected_items)
+}
+
+#[test]
+fn sources_jdbc_http_test() {
+    let source_str = "
+SOURCE films_database <jdbc:postgresql://localhost/films>
+SOURCE films_endpoint <https://query.wikidata.org/sparql>
+";
+    let (tokens_opt, errors) = lexer::sources()
+        .padded()
+        .then_ignore(end())
+        .parse_recovery(source_str);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let (parsed_items, errors) =
+        parser::sources().parse_recovery(stream(tokens_opt.unwrap()));
+
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let expected_items = Some(vec![
+        Source {
+            ident:       "films_database".to_string(),
+            source_type: SourceType::JDBC("postgresql:".to_string()),
+            uri:         "jdbc:postgresql://localhost/films".to_string(),
+        },
+        Source {
+            ident:       "films_endpoint".to_string(),
+            source_type: SourceType::HTTPS,
+            uri:         "https://query.wikidata.org/sparql".to_string(),
+        },
+    ]);
+
+    assert_round_trip(&expected_items, lexer::sources(), parser::sources());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1569,7 +1723,7 @@ fn auto_inc_only_start_test() {
     println!("{:?}", tokens_opt);
 
     let (parsed_items, errors) =
-        parser::auto_increment().parse_recovery(tokens_opt.unwrap());
+        parser::auto_increment().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.is_empty(), "{:?}", errors);
 
@@ -1583,6 +1737,11 @@ fn auto_inc_only_start_test() {
             step:   None,
         }));
 
+    assert_round_trip(
+        &expected_items,
+        lexer::autoincrement(),
+        parser::auto_increment(),
+    );
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1601,7 +1760,7 @@ fn auto_inc_start_test() {
     println!("{:?}", tokens_opt);
 
     let (parsed_items, errors) =
-        parser::auto_increment().parse_recovery(tokens_opt.unwrap());
+        parser::auto_increment().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.is_empty(), "{:?}", errors);
 
@@ -1615,6 +1774,11 @@ fn auto_inc_start_test() {
             step:   None,
         }));
 
+    assert_round_trip(
+        &expected_items,
+        lexer::autoincrement(),
+        parser::auto_increment(),
+    );
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1633,7 +1797,7 @@ fn auto_inc_test() {
     println!("{:?}", tokens_opt);
 
     let (parsed_items, errors) =
-        parser::auto_increment().parse_recovery(tokens_opt.unwrap());
+        parser::auto_increment().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1647,6 +1811,11 @@ fn auto_inc_test() {
             step:   Some(2),
         }));
 
+    assert_round_trip(
+        &expected_items,
+        lexer::autoincrement(),
+        parser::auto_increment(),
+    );
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1663,7 +1832,7 @@ fn matcher_multiple_test() {
         .parse_recovery(match_str);
     assert!(errors.len() == 0, "{:?}", errors);
     let (parsed_items, errors) =
-        parser::matcher().parse_recovery(tokens_opt.unwrap());
+        parser::matcher().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1690,6 +1859,7 @@ fn matcher_multiple_test() {
         .collect(),
     }));
 
+    assert_round_trip(&expected_items, lexer::matcher(), parser::matcher());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1706,7 +1876,7 @@ fn matcher_single_test() {
 
     assert!(errors.len() == 0, "{:?}", errors);
     let (parsed_items, errors) =
-        parser::matcher().parse_recovery(tokens_opt.unwrap());
+        parser::matcher().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
     let values_set = HashSet::from_iter(vec![
@@ -1723,6 +1893,7 @@ fn matcher_single_test() {
             .collect(),
     }));
 
+    assert_round_trip(&expected_items, lexer::matcher(), parser::matcher());
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -1740,7 +1911,7 @@ fn expression_join_union_test() {
     assert!(errors.len() == 0, "{:?}", errors);
     println!("{:?}", tokens_opt);
     let (parsed_items, errors) =
-        parser::expression_stmt().parse_recovery(tokens_opt.unwrap());
+        parser::expression_stmt().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
     let source_ident = "file".to_string();
@@ -1778,6 +1949,11 @@ fn expression_join_union_test() {
         expr_enum,
     }));
 
+    assert_round_trip(
+        &expected_items,
+        lexer::expression_stmt(),
+        parser::expression_stmt(),
+    );
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -1795,7 +1971,7 @@ fn expression_simple_test() {
 
     println!("{:?}", tokens_opt);
     let (parsed_items, errors) =
-        parser::expression_stmt().parse_recovery(tokens_opt.unwrap());
+        parser::expression_stmt().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
 
@@ -1814,6 +1990,11 @@ fn expression_simple_test() {
         expr_enum,
     }));
 
+    assert_round_trip(
+        &expected_items,
+        lexer::expression_stmt(),
+        parser::expression_stmt(),
+    );
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -1831,7 +2012,7 @@ fn expression_string_op_test() {
     assert!(errors.len() == 0, "{:?}", errors);
     println!("{:?}", tokens_opt);
     let (parsed_items, errors) =
-        parser::expression_stmt().parse_recovery(tokens_opt.unwrap());
+        parser::expression_stmt().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
     let source_ident = "file".to_string();
@@ -1856,6 +2037,11 @@ fn expression_string_op_test() {
 
     let expected_items = Some(ExpressionEnum::ExpressionStmt(stmt));
 
+    assert_round_trip(
+        &expected_items,
+        lexer::expression_stmt(),
+        parser::expression_stmt(),
+    );
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -1919,9 +2105,10 @@ fn iterator_nested_test() {
     }]);
 
     let (parsed_items, errors) =
-        parser::iterators().parse_recovery(tokens_opt.unwrap().0);
+        parser::iterators().parse_recovery(stream(tokens_opt.unwrap().0));
 
     assert!(errors.len() == 0, "{:?}", errors);
+    assert_round_trip(&expected_items, lexer::iterators(), parser::iterators());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -1990,9 +2177,10 @@ fn iterator_nested_same_level_test() {
     }]);
 
     let (parsed_items, errors) =
-        parser::iterators().parse_recovery(tokens_opt.unwrap().0);
+        parser::iterators().parse_recovery(stream(tokens_opt.unwrap().0));
 
     assert!(errors.len() == 0, "{:?}", errors);
+    assert_round_trip(&expected_items, lexer::iterators(), parser::iterators());
     assert_parse_expected(parsed_items, expected_items)
 }
 #[test]
@@ -2010,7 +2198,8 @@ ITERATOR example <xpath: /path/to/entity> {
 
     println!("{:#?}", tokens_opt);
     assert!(errors.len() == 0, "{:?}", errors);
-    let parsed_items = parser::iterators().parse(tokens_opt.unwrap()).ok();
+    let parsed_items =
+        parser::iterators().parse(stream(tokens_opt.unwrap())).ok();
 
     let fields = vec![
         Field {
@@ -2037,6 +2226,7 @@ ITERATOR example <xpath: /path/to/entity> {
         fields,
         nested_iterator: vec![],
     }]);
+    assert_round_trip(&expected_items, lexer::iterators(), parser::iterators());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -2054,7 +2244,8 @@ ITERATOR example <csvperrow> {
         .parse_recovery(iter_str);
 
     assert!(errors.len() == 0, "{:?}", errors);
-    let parsed_items = parser::iterators().parse(tokens_opt.unwrap()).ok();
+    let parsed_items =
+        parser::iterators().parse(stream(tokens_opt.unwrap())).ok();
 
     let fields = vec![
         Field {
@@ -2081,6 +2272,7 @@ ITERATOR example <csvperrow> {
         fields,
         nested_iterator: vec![],
     }]);
+    assert_round_trip(&expected_items, lexer::iterators(), parser::iterators());
     assert_parse_expected(parsed_items, expected_items)
 }
 #[test]
@@ -2093,7 +2285,7 @@ fn prefix_multiple_test() {
     let (tokens_opt, _) = lexer::prefixes().parse_recovery(prefix_1);
     println!("{:?}", tokens_opt);
     let (parsed_items, error) =
-        parser::prefixes().parse_recovery(tokens_opt.unwrap());
+        parser::prefixes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(error.len() == 0, "{:#?}", error);
     let expected_items = Some(vec![
@@ -2106,6 +2298,7 @@ fn prefix_multiple_test() {
             uri:    "https://example23.com/".to_string(),
         },
     ]);
+    assert_round_trip(&expected_items, lexer::prefixes(), parser::prefixes());
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -2116,7 +2309,7 @@ fn prefix_test() {
     let (tokens_opt, _) = lexer::prefixes().parse_recovery(prefix_1);
     println!("{:?}", tokens_opt);
     let (parsed_items, error) =
-        parser::prefixes().parse_recovery(tokens_opt.unwrap());
+        parser::prefixes().parse_recovery(stream(tokens_opt.unwrap()));
 
     assert!(error.len() == 0, "{:#?}", error);
     let expected_items = Some(vec![Prefix {
@@ -2124,6 +2317,7 @@ fn prefix_test() {
         uri:    "https://example.com/".to_string(),
     }]);
 
+    assert_round_trip(&expected_items, lexer::prefixes(), parser::prefixes());
     assert_parse_expected(parsed_items, expected_items);
 }
 
@@ -2136,7 +2330,7 @@ fn source_multiple_test() {
     let (tokens_opt, _) = lexer::sources().parse_recovery(source_str);
     println!("{:?}", tokens_opt);
     let (parsed_items, errors) =
-        parser::sources().parse_recovery(tokens_opt.clone().unwrap());
+        parser::sources().parse_recovery(stream(tokens_opt.clone().unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_items = Some(vec![
@@ -2151,6 +2345,7 @@ fn source_multiple_test() {
             source_type: SourceType::File,
         },
     ]);
+    assert_round_trip(&expected_items, lexer::sources(), parser::sources());
     assert_parse_expected(parsed_items, expected_items)
 }
 
@@ -2159,7 +2354,7 @@ fn source_test() {
     let source_str = "SOURCE xml_file <https://example.com/file.xml>";
     let (tokens_opt, _) = lexer::sources().parse_recovery(source_str);
     let (parsed_items, errors) =
-        parser::sources().parse_recovery(tokens_opt.clone().unwrap());
+        parser::sources().parse_recovery(stream(tokens_opt.clone().unwrap()));
 
     assert!(errors.len() == 0, "{:?}", errors);
     let expected_items = Some(vec![Source {
@@ -2167,5 +2362,6 @@ fn source_test() {
         uri:         "https://example.com/file.xml".to_string(),
         source_type: SourceType::HTTPS,
     }]);
+    assert_round_trip(&expected_items, lexer::sources(), parser::sources());
     assert_parse_expected(parsed_items, expected_items)
 }
@@ -10,10 +10,11 @@ pub enum ShExMLError {
     ParseError(String),
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ShExMLDocument {
     pub prefixes:         Vec<Prefix>,
     pub sources:          Vec<Source>,
+    pub queries:          Vec<Query>,
     pub iterators:        Vec<Iterator>,
     pub expression_stmts: Vec<ExpressionStmt>,
     pub auto_increments:  Vec<AutoIncrement>,
@@ -35,6 +36,12 @@ impl ShExMLDocument {
             .map(|source| (source.ident.clone(), source))
             .collect();
 
+        let queries = self
+            .queries
+            .into_iter()
+            .map(|query| (query.ident.clone(), query))
+            .collect();
+
         let iterators = self
             .iterators
             .into_iter()
@@ -86,6 +93,7 @@ impl ShExMLDocument {
         IndexedShExMLDocument {
             prefixes,
             sources,
+            queries,
             iterators,
             expression_stmts,
             auto_increments,
@@ -101,6 +109,7 @@ impl ShExMLDocument {
 pub struct IndexedShExMLDocument {
     pub prefixes:         HashMap<String, Prefix>,
     pub sources:          HashMap<String, Source>,
+    pub queries:          HashMap<String, Query>,
     pub iterators:        HashMap<String, Iterator>,
     pub expression_stmts: HashMap<String, ExpressionStmt>,
     pub auto_increments:  HashMap<String, AutoIncrement>,
@@ -145,6 +154,57 @@ pub struct Source {
     pub uri:         String,
 }
 
+/// Query declared with `QUERY ident <...>` which can be used as the query
+/// of an iterator by referring to its identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Query {
+    pub ident:     String,
+    pub iter_type: Option<IteratorType>,
+    pub content:   QueryContent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueryContent {
+    /// Query written inside the mapping document, e.g. `<sql: SELECT ...>`
+    Inline(String),
+    /// URI of the file containing the query
+    External(String),
+}
+
+impl Query {
+    /// Creates a new query. The iterator type of an external query without
+    /// an explicit type is inferred from the file extension of its URI.
+    pub fn new(
+        ident: String,
+        iter_type: Option<IteratorType>,
+        content: QueryContent,
+    ) -> Self {
+        let iter_type = iter_type.or_else(|| {
+            match &content {
+                QueryContent::External(uri) => {
+                    let lowered_uri = uri.to_lowercase();
+                    if lowered_uri.ends_with(".sparql")
+                        || lowered_uri.ends_with(".rq")
+                    {
+                        Some(IteratorType::SPARQL)
+                    } else if lowered_uri.ends_with(".sql") {
+                        Some(IteratorType::SQL)
+                    } else {
+                        None
+                    }
+                }
+                QueryContent::Inline(_) => None,
+            }
+        });
+
+        Query {
+            ident,
+            iter_type,
+            content,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Iterator {
     pub ident:           String,
@@ -466,6 +526,27 @@ impl ShapeExpression {
 
         result
     }
+
+    /// Extracts the shape references to the fields of expressions used by
+    /// the shape expression, including the parameters of functions.
+    pub fn extract_references(&self) -> Vec<&ShapeReference> {
+        match self {
+            ShapeExpression::Reference(reference)
+            | ShapeExpression::Matching { reference, .. } => vec![reference],
+            ShapeExpression::Conditional {
+                reference,
+                conditional_expr,
+            } => {
+                let mut result = vec![reference];
+                result.extend(conditional_expr.extract_references());
+                result
+            }
+            ShapeExpression::Function { params_idents, .. } => {
+                params_idents.iter().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
 }
 
 fn shape_expr_ref_serialize<S>(
@@ -489,6 +570,38 @@ pub struct Object {
     pub datatype:   Option<DataType>,
 }
 
+impl Object {
+    /// Extracts the expression identifiers referenced by the object,
+    /// including the ones used for its language tag or datatype.
+    pub fn extract_expr_idents(&self) -> HashSet<&str> {
+        let mut result = self.expression.extract_expr_idents();
+
+        if let Some(language_expr) = &self.language {
+            result.extend(language_expr.extract_expr_idents());
+        }
+        if let Some(datatype) = &self.datatype {
+            result.extend(datatype.local_expr.extract_expr_idents());
+        }
+
+        result
+    }
+
+    /// Extracts the shape references used by the object, including the ones
+    /// used for its language tag or datatype.
+    pub fn extract_references(&self) -> Vec<&ShapeReference> {
+        let mut result = self.expression.extract_references();
+
+        if let Some(language_expr) = &self.language {
+            result.extend(language_expr.extract_references());
+        }
+        if let Some(datatype) = &self.datatype {
+            result.extend(datatype.local_expr.extract_references());
+        }
+
+        result
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct DataType {
     pub prefix:     Option<PrefixNameSpace>,
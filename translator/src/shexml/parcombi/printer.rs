@@ -0,0 +1,478 @@
+//! Pretty-printer turning a parsed [ShExMLDocument] back into canonical
+//! ShExML text.
+//!
+//! The printed text parses back into the same document. Comments and the
+//! original layout of the document are not kept, and the predicate-object
+//! pairs of shapes and the keys of matchers are printed in sorted order.
+
+use std::fmt::{Display, Formatter, Result};
+
+use super::parser::r#type::*;
+
+const INDENT: &str = "    ";
+
+/// Prints the ShExML document in its canonical form.
+pub fn print(document: &ShExMLDocument) -> String {
+    document.to_string()
+}
+
+/// Prints every item on its own line.
+pub fn print_lines<T: Display>(items: &[T]) -> String {
+    items.iter().map(|item| format!("{}\n", item)).collect()
+}
+
+impl Display for ShExMLDocument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        // The parser adds a default rdf prefix to every document, which
+        // would otherwise be duplicated when the output is parsed again.
+        let default_rdf = Prefix {
+            prefix: PrefixNameSpace::NamedPrefix(
+                vocab::rdf::PREFIX.to_string(),
+            ),
+            uri:    vocab::rdf::IRI.to_string(),
+        };
+        let prefixes = match self.prefixes.split_last() {
+            Some((last, prefixes)) if *last == default_rdf => prefixes,
+            _ => &self.prefixes,
+        };
+
+        let declarations =
+            print_lines(&self.sources) + &print_lines(&self.queries);
+        let expressions = print_lines(&self.expression_stmts)
+            + &print_lines(&self.matchers)
+            + &print_lines(&self.auto_increments)
+            + &print_lines(&self.functions);
+        let sections = [
+            print_lines(prefixes),
+            declarations,
+            print_lines(&self.iterators),
+            expressions,
+        ];
+        // Every graph is separated by an empty line, just like the sections
+        let graph_sections = self
+            .graph_shapes
+            .iter()
+            .map(|graph_shapes| format!("{}\n", graph_shapes));
+
+        let non_empty_sections: Vec<_> = sections
+            .into_iter()
+            .chain(graph_sections)
+            .filter(|section| !section.is_empty())
+            .collect();
+        write!(f, "{}", non_empty_sections.join("\n"))
+    }
+}
+
+impl Display for Prefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "PREFIX {} <{}>", self.prefix, self.uri)
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "SOURCE {} <{}>", self.ident, self.uri)
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match (&self.content, &self.iter_type) {
+            (QueryContent::Inline(query), Some(iter_type)) => {
+                write!(f, "QUERY {} <{} {}>", self.ident, iter_type, query)
+            }
+            (QueryContent::Inline(query), None)
+            | (QueryContent::External(query), _) => {
+                write!(f, "QUERY {} <{}>", self.ident, query)
+            }
+        }
+    }
+}
+
+impl Display for IteratorType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            IteratorType::JSONPath => write!(f, "jsonpath:"),
+            IteratorType::XPath => write!(f, "xpath:"),
+            IteratorType::CSVRows => write!(f, "csvperrow"),
+            IteratorType::SQL => write!(f, "sql:"),
+            IteratorType::SPARQL => write!(f, "sparql:"),
+        }
+    }
+}
+
+impl Display for Iterator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let iter_query = match (&self.iter_type, &self.query) {
+            (Some(iter_type), Some(query)) => {
+                format!("{} {}", iter_type, query)
+            }
+            (Some(iter_type), None) => iter_type.to_string(),
+            (None, Some(query)) => query.clone(),
+            (None, None) => String::new(),
+        };
+
+        writeln!(f, "ITERATOR {} <{}> {{", self.ident, iter_query)?;
+        for field in &self.fields {
+            writeln!(f, "{}{}", INDENT, field)?;
+        }
+        for nested_iterator in &self.nested_iterator {
+            write!(f, "{}", indent(&nested_iterator.to_string()))?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let field_type = match self.field_type {
+            FieldType::Normal => "FIELD",
+            FieldType::Push => "PUSHED_FIELD",
+            FieldType::Pop => "POPPED_FIELD",
+        };
+
+        write!(f, "{} {} <{}>", field_type, self.ident, self.query)
+    }
+}
+
+impl Display for ExpressionEnum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ExpressionEnum::ExpressionStmt(stmt) => write!(f, "{}", stmt),
+            ExpressionEnum::MatcherExp(matcher) => write!(f, "{}", matcher),
+            ExpressionEnum::AutoIncrementExp(auto_increment) => {
+                write!(f, "{}", auto_increment)
+            }
+            ExpressionEnum::FunctionExp(function) => write!(f, "{}", function),
+        }
+    }
+}
+
+impl Display for ExpressionStmt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "EXPRESSION {} <{}>", self.ident, self.expr_enum)
+    }
+}
+
+impl Display for ExpressionStmtEnum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ExpressionStmtEnum::Join(left, right) => {
+                write!(f, "{} JOIN {}", left, right)
+            }
+            ExpressionStmtEnum::Union(left, right) => {
+                write!(f, "{} UNION {}", left, right)
+            }
+            ExpressionStmtEnum::ConcatenateString {
+                left_reference,
+                concate_string,
+                right_reference,
+            } => {
+                write!(
+                    f,
+                    "{} + \"{}\" + {}",
+                    left_reference, concate_string, right_reference
+                )
+            }
+            ExpressionStmtEnum::Basic { reference } => {
+                write!(f, "{}", reference)
+            }
+        }
+    }
+}
+
+impl Display for ExpressionReferenceIdent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}.{}", self.source_ident, self.iterator_ident)?;
+        if let Some(field) = &self.field {
+            write!(f, ".{}", field)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Matcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut rename_pairs: Vec<_> = self.rename_map.iter().collect();
+        rename_pairs.sort_by_key(|(key, _)| *key);
+
+        let rename_strings: Vec<_> = rename_pairs
+            .into_iter()
+            .map(|(key, values)| {
+                let mut values: Vec<_> =
+                    values.iter().map(String::as_str).collect();
+                values.sort();
+                format!("{} AS {}", values.join(", "), key)
+            })
+            .collect();
+
+        write!(f, "MATCHER {} <{}>", self.ident, rename_strings.join(" & "))
+    }
+}
+
+impl Display for AutoIncrement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "AUTOINCREMENT {} <", self.ident)?;
+        if let Some(prefix) = &self.prefix {
+            write!(f, "\"{}\" + ", prefix)?;
+        }
+        write!(f, "{}", self.start)?;
+        if let Some(end) = self.end {
+            write!(f, " to {}", end)?;
+        }
+        if let Some(step) = self.step {
+            write!(f, " by {}", step)?;
+        }
+        if let Some(suffix) = &self.suffix {
+            write!(f, " + \"{}\"", suffix)?;
+        }
+        write!(f, ">")
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "FUNCTIONS {} <{} {}>",
+            self.ident, self.lang_type, self.uri
+        )
+    }
+}
+
+impl Display for GraphShapes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let shapes = print_lines(&self.shapes);
+
+        // Shapes outside of a named graph belong to the base graph
+        if self.ident == ShapeIdent::base() {
+            write!(f, "{}", shapes.trim_end())
+        } else {
+            writeln!(f, "{} [[", self.ident)?;
+            write!(f, "{}", indent(&shapes))?;
+            write!(f, "]]")
+        }
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "{} {} {{", self.ident, self.subject)?;
+
+        let mut pred_obj_pairs: Vec<_> = self
+            .pred_obj_pairs
+            .iter()
+            .map(|(pred, obj)| (print_predicate(pred), obj))
+            .collect();
+        pred_obj_pairs.sort_by(|left, right| left.0.cmp(&right.0));
+
+        for (pred, obj) in pred_obj_pairs {
+            writeln!(f, "{}{} {} ;", INDENT, pred, obj)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Prints `rdf:type` predicates with the `a` shorthand.
+fn print_predicate(predicate: &Predicate) -> String {
+    match &predicate.prefix {
+        PrefixNameSpace::NamedPrefix(prefix)
+            if prefix == vocab::rdf::PREFIX && predicate.local == "type" =>
+        {
+            "a".to_string()
+        }
+        _ => predicate.to_string(),
+    }
+}
+
+impl Display for Subject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        print_prefixed_expression(f, &self.prefix, &self.expression)
+    }
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match (&self.prefix, &self.expression) {
+            (_, ShapeExpression::Link { other_shape_ident }) => {
+                write!(f, "@{}", other_shape_ident)?
+            }
+            (Some(prefix), expression) => {
+                print_prefixed_expression(f, prefix, expression)?
+            }
+            (None, expression) => write!(f, "[{}]", expression)?,
+        }
+
+        if let Some(language) = &self.language {
+            match language {
+                ShapeExpression::Static { value } => write!(f, " @{}", value)?,
+                language => write!(f, " @[{}]", language)?,
+            }
+        }
+        if let Some(datatype) = &self.datatype {
+            write!(f, " {}", datatype)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match &self.prefix {
+            Some(prefix) => {
+                print_prefixed_expression(f, prefix, &self.local_expr)
+            }
+            None => write!(f, "[{}]", self.local_expr),
+        }
+    }
+}
+
+/// Prints a static expression as a prefixed name, e.g. `:Film`, and any
+/// other expression within square brackets, e.g. `:[films.id]`.
+fn print_prefixed_expression(
+    f: &mut Formatter<'_>,
+    prefix: &PrefixNameSpace,
+    expression: &ShapeExpression,
+) -> Result {
+    match expression {
+        ShapeExpression::Static { value } => write!(f, "{}{}", prefix, value),
+        expression => write!(f, "{}[{}]", prefix, expression),
+    }
+}
+
+impl Display for ShapeExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ShapeExpression::Reference(reference) => {
+                write!(f, "{}", reference)
+            }
+            ShapeExpression::Link { other_shape_ident } => {
+                write!(f, "@{}", other_shape_ident)
+            }
+            ShapeExpression::Static { value } => write!(f, "{}", value),
+            ShapeExpression::Matching {
+                reference,
+                matcher_ident,
+            } => write!(f, "{} MATCHING {}", reference, matcher_ident),
+            ShapeExpression::Conditional {
+                reference,
+                conditional_expr,
+            } => write!(f, "{} IF {}", reference, conditional_expr),
+            ShapeExpression::Function {
+                fun_method_ident,
+                params_idents,
+            } => {
+                let params: Vec<_> = params_idents
+                    .iter()
+                    .map(|param| param.to_string())
+                    .collect();
+                write!(f, "{}({})", fun_method_ident, params.join(", "))
+            }
+        }
+    }
+}
+
+/// Indents every line of the given text by one level.
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("{}{}\n", INDENT, line)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use walkdir::WalkDir;
+
+    use super::*;
+    use crate::shexml::parcombi::{parse_file, parse_string};
+
+    #[test]
+    fn print_canonical_document_test() {
+        let document = r#"PREFIX : <http://example.com/>
+PREFIX xs: <http://www.w3.org/2001/XMLSchema#>
+
+SOURCE films_json <films.json>
+SOURCE films_db <jdbc:postgresql://localhost/films>
+QUERY films_sql <sql: SELECT * FROM films;>
+QUERY actors_sparql <actors.sparql>
+
+ITERATOR film_json <jsonpath: $.films[*]> {
+    FIELD id <id>
+    FIELD name <name>
+    PUSHED_FIELD year <year>
+    ITERATOR actors <actors[*]> {
+        FIELD name <name>
+        POPPED_FIELD year <year>
+    }
+}
+ITERATOR film_sql <sql: films_sql> {
+    FIELD id <id>
+}
+
+EXPRESSION films <films_json.film_json UNION films_db.film_sql>
+EXPRESSION labels <films_json.film_json.id + "-" + films_json.film_json.name>
+MATCHER countries <España, Spain AS ES & UK, United Kingdom AS GB>
+AUTOINCREMENT film_code <"film-" + 1 to 100 by 2 + "-code">
+FUNCTIONS helper <scala: https://example.com/functions.scala>
+
+:Films :[films.id] {
+    :actor @:Actors ;
+    :country [films.country MATCHING countries] ;
+    :label [labels] @en ;
+    :name [films.name] @[films.language] ;
+    :year [films.year] xs:gYear ;
+    a :Film ;
+}
+:Actors :[films.actors.name] {
+    :name [helper.upper(films.actors.name)] ;
+    :yearBorn [films.actors.year IF helper.adult(films.actors.year)] xs:[films.type] ;
+}
+
+:Graph [[
+    :Films :[films.id] {
+        :code :[film_code] ;
+    }
+]]
+"#;
+        let parsed = parse_string(document.to_string()).unwrap();
+        let printed = print(&parsed);
+
+        assert_eq!(printed, document);
+        assert_eq!(parse_string(printed).unwrap(), parsed);
+    }
+
+    #[test]
+    fn print_round_trip_test_documents_test() {
+        let test_dir =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/resources/test/shexml");
+        let shexml_files = WalkDir::new(test_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.path().extension() == Some("shexml".as_ref())
+            });
+
+        for shexml_file in shexml_files {
+            // Documents which the parser does not support are skipped
+            let Ok(parsed) = parse_file(shexml_file.path()) else {
+                continue;
+            };
+            let printed = print(&parsed);
+            let reparsed = parse_string(printed.clone());
+
+            assert_eq!(
+                reparsed.ok(),
+                Some(parsed),
+                "{}:\n{}",
+                shexml_file.path().display(),
+                printed
+            );
+        }
+    }
+}
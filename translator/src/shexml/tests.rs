@@ -1,5 +1,6 @@
 use super::*;
 use crate::error::TranslationError;
+use crate::options::{OutputTarget, SerializationFormat};
 use crate::test_case;
 
 #[ignore]
@@ -11,3 +12,584 @@ fn translate_to_plan_test() -> Result<(), TranslationError> {
     ShExMLTranslator::translate_to_plan(shexml_document)?;
     Ok(())
 }
+
+#[test]
+fn translate_join_across_sources_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!(
+        "shexml/unorganized/JsonAndXmlEventsMappingWithJoin.shexml"
+    );
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+    let join_configs: Vec<_> = graph
+        .node_weights()
+        .filter_map(|node| {
+            match &node.operator {
+                operator::Operator::JoinOp { config } => Some(config),
+                _ => None,
+            }
+        })
+        .collect();
+
+    assert_eq!(join_configs.len(), 1);
+    assert_eq!(
+        join_configs[0].left_right_attr_pairs,
+        vec![(
+            "events_iterator.venues_names".to_string(),
+            "join_venues_union.performances_iterator.venues_names_json"
+                .to_string()
+        )]
+    );
+
+    let joined_rename = graph.node_weights().any(|node| {
+        match &node.operator {
+            operator::Operator::RenameOp { config } => {
+                config.rename_pairs.get(
+                    "join_venues_union.performances_iterator.venues_ids",
+                ) == Some(&"venues_union".to_string())
+            }
+            _ => false,
+        }
+    });
+    assert!(joined_rename);
+
+    Ok(())
+}
+
+#[test]
+fn translate_join_reference_output_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!(
+        "shexml/unorganized/JsonAndXmlEventsMappingWithJoin.shexml"
+    );
+    let expected_output = std::fs::read_to_string(test_case!(
+        "shexml/unorganized/JsonAndXmlEventsMappingWithJoin.nt"
+    ))
+    .unwrap();
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let triples = serializer_triples(&plan);
+    for expected_triple in expected_output.lines() {
+        let predicate = expected_triple.split(' ').nth(1).unwrap();
+        assert!(
+            triples.iter().any(|triple| triple.contains(predicate)),
+            "No triple generated for: {}",
+            expected_triple
+        );
+    }
+
+    // The exhibition 444 without a venue with the same name in the
+    // performances still has a location in the reference output
+    assert!(expected_output.contains("<http://ex.com/444>"));
+    let graph = plan.graph.borrow();
+    assert!(graph.node_weights().all(|node| {
+        match &node.operator {
+            operator::Operator::JoinOp { config } => {
+                config.join_type == operator::JoinType::LeftJoin
+            }
+            _ => true,
+        }
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn translate_shape_link_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!("shexml/rml_conversion/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+
+    // The linked blank node is generated from the same mapping tuples, also
+    // for the source without any quads of the linked shape
+    let linked_subject = operator::Function::BlankNode {
+        inner_function: operator::Function::Reference {
+            value: "films.name".to_string(),
+        }
+        .into(),
+    };
+    let extends_with_link = graph
+        .node_weights()
+        .filter(|node| {
+            match &node.operator {
+                operator::Operator::ExtendOp { config } => {
+                    config
+                        .extend_pairs
+                        .values()
+                        .any(|func| *func == linked_subject)
+                }
+                _ => false,
+            }
+        })
+        .count();
+    assert_eq!(extends_with_link, 2);
+
+    let director_triples = serializer_triples(&plan)
+        .into_iter()
+        .filter(|triple| triple.contains("<http://example.com/director>"))
+        .count();
+    assert_eq!(director_triples, 2);
+
+    Ok(())
+}
+
+#[test]
+fn translate_shape_link_across_iterators_test() -> Result<(), TranslationError>
+{
+    let input_shexml = test_case!("shexml/link_join/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+    let link_join = graph
+        .node_weights()
+        .find_map(|node| {
+            match &node.operator {
+                operator::Operator::JoinOp { config }
+                    if config.join_type == operator::JoinType::InnerJoin =>
+                {
+                    Some(config)
+                }
+                _ => None,
+            }
+        })
+        .unwrap();
+    assert_eq!(
+        link_join.left_right_attr_pairs,
+        vec![(
+            "events_iterator.venues_names".to_string(),
+            "link_venues_union.performances_iterator.venues_names_json"
+                .to_string()
+        )]
+    );
+
+    let linked_rename = graph.node_weights().any(|node| {
+        match &node.operator {
+            operator::Operator::RenameOp { config } => {
+                config.rename_pairs.get(
+                    "link_venues_union.performances_iterator.performances_ids",
+                ) == Some(&"performances.performances_ids".to_string())
+            }
+            _ => false,
+        }
+    });
+    assert!(linked_rename);
+
+    let triples = serializer_triples(&plan);
+    assert!(triples
+        .contains(&"?_sm_0 <http://ex.com/sameVenueAs> ?_om_0 .".to_string()));
+
+    let input_shexml = test_case!("shexml/link_join/without_join.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+    assert!(ShExMLTranslator::translate_to_plan(shexml_document).is_err());
+
+    Ok(())
+}
+
+fn serializer_triples<T>(plan: &Plan<T>) -> Vec<String> {
+    plan.graph
+        .borrow()
+        .node_weights()
+        .filter_map(|node| {
+            match &node.operator {
+                operator::Operator::SerializerOp { config } => {
+                    Some(config.template.clone())
+                }
+                _ => None,
+            }
+        })
+        .flat_map(|template| {
+            template
+                .lines()
+                .map(|line| line.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[test]
+fn translate_multiple_graphs_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!("shexml/multiple_graphs/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let triples = serializer_triples(&plan);
+    let films_graph = "<http://example.com/graphs/Films> .";
+    let countries_graph = "<http://example.com/graphs/Countries> .";
+
+    let has_triple = |predicate: &str, graph: Option<&str>| {
+        triples.iter().any(|triple| {
+            let terms: Vec<_> = triple.split_whitespace().collect();
+            terms[1] == predicate
+                && match graph {
+                    Some(graph) => triple.ends_with(graph),
+                    None => terms.len() == 4,
+                }
+        })
+    };
+
+    assert!(has_triple("<http://example.com/year>", Some(films_graph)));
+    assert!(has_triple("<http://example.com/country>", Some(films_graph)));
+    assert!(has_triple("<http://example.com/name>", Some(countries_graph)));
+    assert!(has_triple("<http://example.com/label>", None));
+    assert!(!has_triple("<http://example.com/label>", Some(films_graph)));
+
+    let country_link = triples
+        .iter()
+        .find(|triple| triple.contains("<http://example.com/country>"))
+        .unwrap();
+    assert!(country_link.contains("?Countries_sm_"));
+
+    Ok(())
+}
+
+#[test]
+fn expression_graph_unsupported_test() {
+    let input_shexml =
+        test_case!("shexml/multiple_graphs/expression_graph.shexml");
+    let err = parcombi::parse_file(input_shexml).unwrap_err();
+
+    let diagnostic = &err.diagnostics[0];
+    assert_eq!((diagnostic.line, diagnostic.column), (11, 1));
+    assert!(diagnostic
+        .message
+        .contains("Graphs built from expressions are not supported"));
+}
+
+#[test]
+fn translate_shape_in_named_and_default_graph_test(
+) -> Result<(), TranslationError> {
+    let input_shexml = test_case!(
+        "shexml/unorganized/GraphMultipleElementIteratorExpressionTest.shexml"
+    );
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let triples = serializer_triples(&plan);
+    let named: Vec<_> = triples
+        .iter()
+        .filter(|triple| triple.ends_with("<http://example.com/MyFilms> ."))
+        .collect();
+    let default: Vec<_> = triples
+        .iter()
+        .filter(|triple| triple.split_whitespace().count() == 4)
+        .collect();
+
+    assert!(!named.is_empty());
+    assert_eq!(named.len(), default.len());
+    assert!(triples
+        .iter()
+        .all(|triple| named.contains(&triple) || default.contains(&triple)));
+
+    Ok(())
+}
+
+#[test]
+fn translate_with_options_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!(
+        "shexml/unorganized/GraphMultipleElementIteratorExpressionTest.shexml"
+    );
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+    let options = TranslationOptions {
+        serialization: SerializationFormat::NTriples,
+        target: OutputTarget::File("films.nt".to_string()),
+        ..Default::default()
+    };
+
+    let plan = ShExMLTranslator::translate_to_plan_with_options(
+        shexml_document,
+        &options,
+    )?;
+
+    // N-Triples cannot serialize the quads of the named graph
+    let triples = serializer_triples(&plan);
+    assert!(!triples.is_empty());
+    assert!(triples
+        .iter()
+        .all(|triple| triple.split_whitespace().count() == 4));
+
+    let graph = plan.graph.borrow();
+    let targets: Vec<_> = graph
+        .node_weights()
+        .filter_map(|node| {
+            match &node.operator {
+                operator::Operator::TargetOp { config } => Some(config),
+                _ => None,
+            }
+        })
+        .collect();
+    assert!(!targets.is_empty());
+    assert!(targets
+        .iter()
+        .all(|target| *target == &options.default_target()));
+
+    Ok(())
+}
+
+#[test]
+fn translate_nested_iterators_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!("shexml/nested_iterators/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+    let unnest_attributes: Vec<_> = graph
+        .node_weights()
+        .filter_map(|node| {
+            match &node.operator {
+                operator::Operator::UnnestOp { config } => {
+                    Some(config.attribute.as_str())
+                }
+                _ => None,
+            }
+        })
+        .collect();
+    assert_eq!(
+        unnest_attributes,
+        vec!["film_json.actors", "film_json.actors.awards"]
+    );
+
+    let rename_pairs = graph
+        .node_weights()
+        .find_map(|node| {
+            match &node.operator {
+                operator::Operator::RenameOp { config } => {
+                    Some(&config.rename_pairs)
+                }
+                _ => None,
+            }
+        })
+        .unwrap();
+    for (from, to) in [
+        ("film_json.title", "films.title"),
+        ("film_json.actors.name", "films.actors.name"),
+        ("film_json.actors.awards.name", "films.actors.awards.name"),
+    ] {
+        assert_eq!(rename_pairs.get(from), Some(&to.to_string()));
+    }
+
+    let triples = serializer_triples(&plan);
+    assert!(triples
+        .iter()
+        .any(|triple| triple.contains("<http://example.com/actor>")));
+    assert!(triples
+        .iter()
+        .any(|triple| triple.contains("<http://example.com/award>")));
+
+    Ok(())
+}
+
+#[test]
+fn translate_sibling_nested_iterators_test() -> Result<(), TranslationError> {
+    let input_shexml =
+        test_case!("shexml/sibling_nested_iterators/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+
+    // Sibling nested iterators are both unnested from the source
+    let unnest_parents: Vec<_> = graph
+        .raw_edges()
+        .iter()
+        .filter_map(|edge| {
+            match &graph[edge.target()].operator {
+                operator::Operator::UnnestOp { config } => {
+                    Some((
+                        config.attribute.as_str(),
+                        &graph[edge.source()].operator,
+                    ))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+    assert_eq!(unnest_parents.len(), 2);
+    for (attribute, parent) in unnest_parents {
+        assert!(
+            matches!(parent, operator::Operator::SourceOp { .. }),
+            "Unnest of {} is not applied to the source but to {:?}",
+            attribute,
+            parent
+        );
+    }
+
+    let mut triples = serializer_triples(&plan);
+    triples.sort();
+    assert_eq!(
+        triples,
+        vec![
+            "?_sm_0 <http://example.com/actor> ?_om_0 .",
+            "?_sm_0 <http://example.com/review> ?_om_0 .",
+            "?_sm_0 <http://example.com/title> ?_om_0 .",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn translate_sibling_nested_iterators_across_sources_test(
+) -> Result<(), TranslationError> {
+    let input_shexml = test_case!("shexml/simple/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let triples = serializer_triples(&plan);
+    for predicate in ["goodReview", "badReview"] {
+        let pattern = format!("<http://example.com/{}>", predicate);
+        assert_eq!(
+            triples
+                .iter()
+                .filter(|triple| triple.contains(&pattern))
+                .count(),
+            2
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn translate_dynamic_literals_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!("shexml/dynamic_literals/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+    let rename_pairs = graph
+        .node_weights()
+        .find_map(|node| {
+            match &node.operator {
+                operator::Operator::RenameOp { config } => {
+                    Some(&config.rename_pairs)
+                }
+                _ => None,
+            }
+        })
+        .unwrap();
+
+    // Expressions only referenced by the language tag are still renamed
+    assert_eq!(
+        rename_pairs.get("film_json.language"),
+        Some(&"film_languages".to_string())
+    );
+    assert_eq!(
+        rename_pairs.get("film_json.rating_type"),
+        Some(&"films.rating_type".to_string())
+    );
+
+    // The attribute also referenced by the iterator expression is copied
+    let copied = graph.node_weights().find_map(|node| {
+        match &node.operator {
+            operator::Operator::ExtendOp { config } => {
+                config.extend_pairs.get("films.language")
+            }
+            _ => None,
+        }
+    });
+    assert_eq!(
+        copied,
+        Some(&Function::Reference {
+            value: "film_languages".to_string(),
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn translate_matcher_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!("shexml/matcher/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+    let lookups: Vec<_> = graph
+        .node_weights()
+        .filter_map(|node| {
+            match &node.operator {
+                operator::Operator::LookupOp { config } => Some(config),
+                _ => None,
+            }
+        })
+        .collect();
+
+    assert_eq!(lookups.len(), 1);
+    let lookup = lookups[0];
+    assert_eq!(lookup.attribute, "films.country");
+    assert_eq!(lookup.alias, "films.country_matching_countries");
+    assert_eq!(
+        lookup.miss_policy,
+        operator::LookupMissPolicy::Passthrough
+    );
+    assert_eq!(
+        lookup.table.config.get(operator::LOOKUP_INLINE_TABLE).unwrap(),
+        "key,value\nEspagne,Spain\nEspaña,Spain\nSpain,Spain\n\
+         UK,United Kingdom\nUnited Kingdom,United Kingdom\n"
+    );
+
+    // The object of the matched country uses the looked up value
+    let uses_lookup = graph.node_weights().any(|node| {
+        match &node.operator {
+            operator::Operator::ExtendOp { config } => {
+                config.extend_pairs.values().any(|function| {
+                    format!("{:?}", function)
+                        .contains("films.country_matching_countries")
+                })
+            }
+            _ => false,
+        }
+    });
+    assert!(uses_lookup);
+
+    Ok(())
+}
+
+#[test]
+fn translate_auto_increment_test() -> Result<(), TranslationError> {
+    let input_shexml = test_case!("shexml/auto_increment/input.shexml");
+    let shexml_document = parcombi::parse_file(input_shexml).unwrap();
+
+    let plan = ShExMLTranslator::translate_to_plan(shexml_document)?;
+    let graph = plan.graph.borrow();
+    let extend_functions: Vec<_> = graph
+        .node_weights()
+        .filter_map(|node| {
+            match &node.operator {
+                operator::Operator::ExtendOp { config } => Some(config),
+                _ => None,
+            }
+        })
+        .flat_map(|config| config.extend_pairs.values())
+        .map(|function| format!("{:?}", function))
+        .collect();
+    let auto_increment_count = |prefix: &str| {
+        let prefix = format!("prefix: Some({:?})", prefix);
+        extend_functions
+            .iter()
+            .filter(|function| function.contains(&prefix))
+            .count()
+    };
+
+    // Every auto increment counts the tuples of a single source
+    assert_eq!(auto_increment_count("film_"), 1);
+    assert_eq!(auto_increment_count("batch_"), 1);
+
+    // The shape only referencing an auto increment is serialized once
+    let triples = serializer_triples(&plan);
+    let predicate_count = |predicate: &str| {
+        triples
+            .iter()
+            .filter(|triple| triple.contains(predicate))
+            .count()
+    };
+    assert_eq!(predicate_count("<http://example.com/type>"), 1);
+    assert_eq!(predicate_count("<http://example.com/code>"), 1);
+
+    Ok(())
+}
@@ -1,10 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
 use log::{debug, trace};
+use plan::error::PlanError;
 
 use crate::shexml::parcombi::{
-    GraphShapes, IndexedShExMLDocument, Object, Predicate, ShapeExpression,
-    ShapeIdent, Subject,
+    GraphShapes, IndexedShExMLDocument, Object, Predicate, Shape,
+    ShapeExpression, ShapeIdent, Subject,
 };
 
 #[derive(Debug, Clone)]
@@ -13,6 +14,70 @@ pub struct IndexVariableTerm<'a> {
     pub object_variable_index:  HashMap<&'a Object, String>,
 }
 
+impl IndexVariableTerm<'_> {
+    /// Gets the variable of the `subj`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subject is not part of the variablelized
+    /// quads.
+    pub fn subject_variable(&self, subj: &Subject) -> Result<&str, PlanError> {
+        self.subject_variable_index
+            .get(subj)
+            .map(String::as_str)
+            .ok_or_else(|| {
+                PlanError::GenericError(format!(
+                    "No variable found for subject {:?}",
+                    subj
+                ))
+            })
+    }
+
+    /// Gets the variable of the term generated for the `obj` of a quad in
+    /// the graph `graph_ident`.
+    ///
+    /// The object of a shape link reuses the variable of the linked
+    /// subject, if it is part of the variablelized quads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object is not part of the variablelized
+    /// quads.
+    pub fn object_term_variable(
+        &self,
+        indexed_document: &IndexedShExMLDocument,
+        graph_ident: &ShapeIdent,
+        obj: &Object,
+    ) -> Result<&str, PlanError> {
+        let linked_subj_variable =
+            linked_subject(indexed_document, graph_ident, obj).and_then(
+                |linked_subj| self.subject_variable_index.get(linked_subj),
+            );
+        match linked_subj_variable {
+            Some(linked_subj_variable) => Ok(linked_subj_variable),
+            None => self.object_variable(obj),
+        }
+    }
+
+    /// Gets the variable of the `obj`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object is not part of the variablelized
+    /// quads.
+    pub fn object_variable(&self, obj: &Object) -> Result<&str, PlanError> {
+        self.object_variable_index
+            .get(obj)
+            .map(String::as_str)
+            .ok_or_else(|| {
+                PlanError::GenericError(format!(
+                    "No variable found for object {:?}",
+                    obj
+                ))
+            })
+    }
+}
+
 pub fn variablelize_quads<'a>(
     quads: &'a ShExMLQuads<'a>,
 ) -> IndexVariableTerm<'a> {
@@ -67,16 +132,28 @@ fn check_subj_expr_ident(subj: &Subject, expr_idents: &HashSet<&str>) -> bool {
             reference,
             matcher_ident: _,
         } => expr_idents.contains(reference.expr_ident.as_str()),
-        ShapeExpression::Conditional {
-            reference,
-            conditional_expr: _,
-        } => expr_idents.contains(reference.expr_ident.as_str()),
+        ShapeExpression::Conditional { .. }
+        | ShapeExpression::Function { .. } => {
+            check_all_expr_idents(&subj.expression, expr_idents)
+        }
         ShapeExpression::Static { value: _ } => true,
 
         _ => false,
     }
 }
 
+/// Checks if all the expressions referenced by the shape expression
+/// (including the parameters of functions) are in the given `expr_idents`.
+fn check_all_expr_idents(
+    expression: &ShapeExpression,
+    expr_idents: &HashSet<&str>,
+) -> bool {
+    expression
+        .extract_expr_idents()
+        .iter()
+        .all(|expr_ident| expr_idents.contains(expr_ident))
+}
+
 fn check_obj_expr_ident(
     indexed_document: &IndexedShExMLDocument,
     obj: &Object,
@@ -124,46 +201,137 @@ fn check_obj_related_expression(
             reference,
             matcher_ident: _,
         } => expr_idents.contains(reference.expr_ident.as_str()),
-        ShapeExpression::Conditional {
-            reference,
-            conditional_expr: _,
-        } => expr_idents.contains(reference.expr_ident.as_str()),
+        ShapeExpression::Conditional { .. }
+        | ShapeExpression::Function { .. } => {
+            check_all_expr_idents(expression, expr_idents)
+        }
         ShapeExpression::Static { value: _ } => true,
         ShapeExpression::Link { other_shape_ident } => {
             debug!("Object is a shape link!");
             trace!("Other shape ident is: {}", other_shape_ident);
-            let shape_map = &indexed_document.shapes;
-            trace!("Shape map is: {:#?}", shape_map);
-            let shape_opt = shape_map
-                .get(&(graph_ident.clone(), other_shape_ident.clone()));
-            if let Some(shape) = shape_opt {
+            trace!("Shape map is: {:#?}", indexed_document.shapes);
+            let shape_opt = get_linked_shape(
+                indexed_document,
+                graph_ident,
+                other_shape_ident,
+            );
+            if let Some((_, shape)) = shape_opt {
                 trace!("Other Shape map is: {:#?}", shape);
                 check_subj_expr_ident(&shape.subject, expr_idents)
             } else {
                 false
             }
         }
-        shape_expr => {
-            debug!("Unsupported shape expression for object to generate quads: {:?}", shape_expr);
-            false
+    }
+}
+
+/// Gets the shape linked to by a shape in the graph `graph_ident`, together
+/// with the ident of the graph the linked shape belongs to.
+///
+/// The linked shape is looked up in the same graph first, and otherwise in
+/// the default graph or any of the other named graphs.
+pub fn get_linked_shape<'a>(
+    indexed_document: &'a IndexedShExMLDocument,
+    graph_ident: &ShapeIdent,
+    other_shape_ident: &ShapeIdent,
+) -> Option<(&'a ShapeIdent, &'a Shape)> {
+    let shape_map = &indexed_document.shapes;
+    let base_graph_ident = ShapeIdent::base();
+
+    shape_map
+        .get_key_value(&(graph_ident.clone(), other_shape_ident.clone()))
+        .or_else(|| {
+            shape_map
+                .get_key_value(&(base_graph_ident, other_shape_ident.clone()))
+        })
+        .or_else(|| {
+            shape_map
+                .iter()
+                .filter(|((_, shape_ident), _)| {
+                    shape_ident == other_shape_ident
+                })
+                .min_by_key(|((graph_ident, _), _)| graph_ident.to_string())
+        })
+        .map(|((graph_ident, _), shape)| (graph_ident, shape))
+}
+
+/// Gets the subject of the shape linked to by the `obj`, if it is a shape
+/// link of a shape in the graph `graph_ident`.
+pub fn linked_subject<'a>(
+    indexed_document: &'a IndexedShExMLDocument,
+    graph_ident: &ShapeIdent,
+    obj: &Object,
+) -> Option<&'a Subject> {
+    match &obj.expression {
+        ShapeExpression::Link { other_shape_ident } => {
+            get_linked_shape(indexed_document, graph_ident, other_shape_ident)
+                .map(|(_, shape)| &shape.subject)
         }
+        _ => None,
     }
 }
 
 pub type ShExMLQuads<'a> =
     Vec<(&'a Subject, &'a Predicate, &'a Object, &'a ShapeIdent)>;
 
+/// Gets the quads which can be generated from the source with the given
+/// `expr_idents`.
+///
+/// Auto increments count the mapping tuples of the source iterated by the
+/// shape using them, so quads with auto increments are only generated from
+/// the sources they reference. Quads which only reference auto increments
+/// are generated from a single source, the one for which
+/// `with_auto_increment_quads` is true.
 pub fn get_quads_from_same_source<'a>(
     indexed_document: &'a IndexedShExMLDocument,
     graph_shapes: impl std::iter::Iterator<Item = &'a GraphShapes>,
     expr_idents: HashSet<&'a str>,
+    with_auto_increment_quads: bool,
+) -> ShExMLQuads<'a> {
+    let auto_increments: HashSet<&str> = indexed_document
+        .auto_increments
+        .keys()
+        .map(|ident| ident.as_str())
+        .collect();
+    let quads = get_quads_from_shapes(
+        indexed_document,
+        graph_shapes,
+        expr_idents.union(&auto_increments).copied().collect(),
+        |subj_check, obj_check| subj_check && obj_check,
+    );
+
+    quads
+        .into_iter()
+        .filter(|(subj, _, obj, _)| {
+            let mut quad_expr_idents = subj.expression.extract_expr_idents();
+            quad_expr_idents.extend(obj.extract_expr_idents());
+
+            with_auto_increment_quads
+                || quad_expr_idents.is_disjoint(&auto_increments)
+                || !quad_expr_idents.is_disjoint(&expr_idents)
+        })
+        .collect()
+}
+
+/// Gets the quads of the shape links from the shapes generated from the
+/// source with the given `expr_idents` to the shapes which cannot be
+/// generated from the same source.
+pub fn get_link_quads_to_different_source<'a>(
+    indexed_document: &'a IndexedShExMLDocument,
+    graph_shapes: impl std::iter::Iterator<Item = &'a GraphShapes>,
+    expr_idents: HashSet<&'a str>,
 ) -> ShExMLQuads<'a> {
     get_quads_from_shapes(
         indexed_document,
         graph_shapes,
         expr_idents,
-        |subj_check, obj_check| subj_check && obj_check,
+        |subj_check, obj_check| subj_check && !obj_check,
     )
+    .into_iter()
+    .filter(|(_, _, obj, _)| {
+        matches!(obj.expression, ShapeExpression::Link { .. })
+    })
+    .collect()
 }
 
 pub fn get_quads_from_different_source<'a>(
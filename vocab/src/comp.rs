@@ -7,4 +7,6 @@ pub mod CLASS {
 
     pub const ZIP: PAIR = (IRI, "zip");
     pub const GZIP: PAIR = (IRI, "gzip");
+
+    pub const TERMS: &[PAIR] = &[ZIP, GZIP];
 }
@@ -8,6 +8,8 @@ pub mod CLASS {
     pub const TABLE: PAIR = (IRI, "Table");
     pub const DIALECT: PAIR = (IRI, "Dialect");
     pub const SCHEMA: PAIR = (IRI, "Schema");
+
+    pub const TERMS: &[PAIR] = &[TABLE, DIALECT, SCHEMA];
 }
 
 pub mod PROPERTY {
@@ -31,4 +33,22 @@ pub mod PROPERTY {
     pub const SKIP_COLUMNS: PAIR = (IRI, "skipColumns");
     pub const SKIP_ROWS: PAIR = (IRI, "skipRows");
     pub const SKIP_INITIAL_SPACE: PAIR = (IRI, "skipInitialSpace");
+
+    pub const TERMS: &[PAIR] = &[
+        DIALECT,
+        URL,
+        TRIM,
+        COMMENT_PREFIX,
+        DELIMITER,
+        DOUBLE_QUOTE,
+        ENCODING,
+        HEADER,
+        HEADER_ROW_COUNT,
+        LINE_TERMINATORS,
+        QUOTE_CHARS,
+        SKIP_BLANK_ROWS,
+        SKIP_COLUMNS,
+        SKIP_ROWS,
+        SKIP_INITIAL_SPACE,
+    ];
 }
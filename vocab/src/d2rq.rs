@@ -6,6 +6,8 @@ pub mod CLASS {
     use super::IRI;
     use crate::PAIR;
     pub const DATABASE: PAIR = (IRI, "Database");
+
+    pub const TERMS: &[PAIR] = &[DATABASE];
 }
 
 pub mod PROPERTY {
@@ -19,4 +21,13 @@ pub mod PROPERTY {
     pub const JDBCDSN : PAIR = (IRI, "jdbcDSN");
     pub const JDBCDriver : PAIR = (IRI, "jdbcDriver");
 
+    pub const TERMS: &[PAIR] = &[
+        SQLQUERY,
+        TABLENAME,
+        COLUMN,
+        USERNAME,
+        PASSWORD,
+        JDBCDSN,
+        JDBCDriver,
+    ];
 }
\ No newline at end of file
@@ -6,4 +6,6 @@ pub mod PROPERTY {
     use crate::PAIR;
 
     pub const FUNCTION_VALUE: PAIR = (IRI, "functionValue");
+
+    pub const TERMS: &[PAIR] = &[FUNCTION_VALUE];
 }
@@ -7,4 +7,6 @@ pub mod PROPERTY {
     use crate::PAIR;
 
     pub const EXECUTES: PAIR = (IRI, "executes");
+
+    pub const TERMS: &[PAIR] = &[EXECUTES];
 }
@@ -25,4 +25,27 @@ pub mod CLASS {
     pub const SPARQL_JSON: PAIR = (IRI, "SPARQL_Results_JSON");
     pub const SPARQL_CSV: PAIR = (IRI, "SPARQL_Results_CSV");
     pub const SPARQL_TSV: PAIR = (IRI, "SPARQL_Results_TSV");
+
+    pub const TERMS: &[PAIR] = &[
+        NTRIPLES,
+        NQUADS,
+        JSONLD,
+        N3,
+        LD_PATCH,
+        MICRODATA,
+        OWL_XML,
+        OWL_FUNCTIONAL,
+        OWL_MANCHESTER,
+        POWDER,
+        POWDER_S,
+        TURTLE,
+        TRIG,
+        RDFA,
+        RDF_JSON,
+        RDF_XML,
+        SPARQL_XML,
+        SPARQL_JSON,
+        SPARQL_CSV,
+        SPARQL_TSV,
+    ];
 }
@@ -6,6 +6,8 @@ pub mod CLASS {
     use crate::PAIR;
 
     pub const EVENTSTREAM: PAIR = (IRI, "EventStream");
+
+    pub const TERMS: &[PAIR] = &[EVENTSTREAM];
 }
 
 pub mod PROPERTY {
@@ -14,4 +16,6 @@ pub mod PROPERTY {
 
     pub const TIMESTAMPPATH: PAIR = (IRI, "timestampPath");
     pub const VERSIONOFPATH: PAIR = (IRI, "versionOfPath");
+
+    pub const TERMS: &[PAIR] = &[TIMESTAMPPATH, VERSIONOFPATH];
 } 
\ No newline at end of file
@@ -20,6 +20,15 @@
 //! Idem for the properties of the ontologies which are behind the `PROPERTY` sub-module of each 
 //! ontology modules.
 //! 
+//! Every sub-module also lists all of its terms in `TERMS`, e.g. to offer them for completion 
+//! while editing a mapping document.
+//! 
+//! ```
+//! use vocab::rml_core;
+//! 
+//! assert!(rml_core::PROPERTY::TERMS.contains(&rml_core::PROPERTY::PARENT_TRIPLES_MAP));
+//! ```
+//! 
 use std::fmt::Display;
 
 pub mod comp;
@@ -29,6 +38,7 @@ pub mod fnml;
 pub mod fno;
 pub mod formats;
 pub mod ldes;
+pub mod owl;
 pub mod query;
 pub mod r2rml;
 pub mod rdf;
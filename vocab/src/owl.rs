@@ -0,0 +1,20 @@
+pub const PREFIX: &str = "owl";
+pub const IRI: &str = "http://www.w3.org/2002/07/owl#";
+
+pub mod PROPERTY {
+    use super::IRI;
+    use crate::PAIR;
+
+    pub const IMPORTS: PAIR = (IRI, "imports");
+
+    pub const TERMS: &[PAIR] = &[IMPORTS];
+}
+
+pub mod CLASS {
+    use super::IRI;
+    use crate::PAIR;
+
+    pub const ONTOLOGY: PAIR = (IRI, "Ontology");
+
+    pub const TERMS: &[PAIR] = &[ONTOLOGY];
+}
@@ -8,4 +8,6 @@ pub mod CLASS {
     pub const CSV: PAIR = (IRI, "CSV");
     pub const XPATH: PAIR = (IRI, "XPath");
     pub const HTML: PAIR = (IRI, "CSS3");
+
+    pub const TERMS: &[PAIR] = &[JSONPATH, CSV, XPATH, HTML];
 }
@@ -29,6 +29,33 @@ pub mod PROPERTY {
     pub const DEFAULTGRAPH: PAIR = (IRI, "defaultGraph");
     pub const TABLENAME: PAIR = (IRI, "tableName");
     pub const SQLVERSION: PAIR = (IRI, "sqlVersion");
+
+    pub const TERMS: &[PAIR] = &[
+        PREDICATEOBJECTMAP,
+        PREDICATE,
+        PREDICATEMAP,
+        OBJECT,
+        OBJECTMAP,
+        TRIPLESMAP,
+        SUBJECTMAP,
+        SUBJECT,
+        CONSTANT,
+        TEMPLATE,
+        TERMTYPE,
+        COLUMN,
+        CLASS,
+        PARENTTRIPLESMAP,
+        JOINCONDITION,
+        PARENT,
+        CHILD,
+        GRAPH,
+        GRAPHMAP,
+        DATATYPE,
+        LANGUAGE,
+        DEFAULTGRAPH,
+        TABLENAME,
+        SQLVERSION,
+    ];
 }
 
 pub mod CLASS {
@@ -41,4 +68,14 @@ pub mod CLASS {
     pub const BLANKNODE: PAIR = (SUPER_IRI, "BlankNode");
     pub const LITERAL: PAIR = (SUPER_IRI, "Literal");
     pub const DEFAULTGRAPH: PAIR = (SUPER_IRI, "DefaultGraph");
+
+    pub const TERMS: &[PAIR] = &[
+        PREDICATEOBJECTMAP,
+        OBJECTMAP,
+        TRIPLESMAP,
+        IRI,
+        BLANKNODE,
+        LITERAL,
+        DEFAULTGRAPH,
+    ];
 }
@@ -6,6 +6,10 @@ pub mod PROPERTY {
     use crate::PAIR;
 
     pub const TYPE: PAIR = (IRI, "type");
+    pub const FIRST: PAIR = (IRI, "first");
+    pub const REST: PAIR = (IRI, "rest");
+
+    pub const TERMS: &[PAIR] = &[TYPE, FIRST, REST];
 }
 
 pub mod CLASS {
@@ -17,4 +21,14 @@ pub mod CLASS {
     pub const RDF_ALT: PAIR = (IRI, "Alt");
     pub const RDF_BAG: PAIR = (IRI, "Bag");
     pub const RDF_SEQ: PAIR = (IRI, "Seq");
+    pub const LANG_STRING: PAIR = (IRI, "langString");
+
+    pub const TERMS: &[PAIR] = &[
+        RDF_OBJECT,
+        RDF_LIST,
+        RDF_ALT,
+        RDF_BAG,
+        RDF_SEQ,
+        LANG_STRING,
+    ];
 }
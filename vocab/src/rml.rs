@@ -14,4 +14,15 @@ pub mod PROPERTY {
     pub const SOURCE: PAIR = (IRI, "source");
     pub const LOGICALTARGET: PAIR = (IRI, "logicalTarget");
     pub const QUERY: PAIR = (IRI, "query");
+
+    pub const TERMS: &[PAIR] = &[
+        LANGUAGE_MAP,
+        REFERENCE,
+        LOGICALSOURCE,
+        ITERATOR,
+        REFERENCEFORMULATION,
+        SOURCE,
+        LOGICALTARGET,
+        QUERY,
+    ];
 }
@@ -11,6 +11,15 @@ pub mod PROPERTY {
     pub const CARTESIAN_PRODUCT: PAIR = (IRI, "cartesianProduct"); 
     pub const ALLOW_EMPTY: PAIR = (IRI, "allowEmptyListAndContainer"); 
     pub const APPEND: PAIR = (IRI, "append"); 
+
+    pub const TERMS: &[PAIR] = &[
+        GATHER,
+        GATHER_AS,
+        STRATEGY,
+        CARTESIAN_PRODUCT,
+        ALLOW_EMPTY,
+        APPEND,
+    ];
 }
 
 pub mod CLASS {
@@ -19,4 +28,6 @@ pub mod CLASS {
 
     pub const GATHER_MAP: PAIR = (IRI, "GatherMap");
     pub const STRATEGY: PAIR = (IRI, "Strategy");
+
+    pub const TERMS: &[PAIR] = &[GATHER_MAP, STRATEGY];
 }
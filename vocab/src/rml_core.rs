@@ -53,6 +53,38 @@ pub mod PROPERTY {
 
     pub const GRAPH: PAIR = (IRI, "graph");
     pub const GRAPH_MAP: PAIR = (IRI, "graphMap");
+
+    pub const TERMS: &[PAIR] = &[
+        REFERENCE,
+        CONSTANT,
+        TEMPLATE,
+        LOGICAL_TARGET,
+        TERMTYPE,
+        DATATYPE_MAP,
+        LANGUAGE_MAP,
+        DATATYPE,
+        LANGUAGE,
+        JOIN_CONDITION,
+        PARENT_TRIPLES_MAP,
+        PARENT_MAP,
+        CHILD_MAP,
+        PARENT,
+        CHILD,
+        PREDICATE,
+        OBJECT,
+        PREDICATE_MAP,
+        OBJECT_MAP,
+        CLASS,
+        SUBJECT,
+        SUBJECT_MAP,
+        PREDICATE_OBJECT_MAP,
+        LOGICAL_SOURCE,
+        BASE_IRI,
+        ITERATOR,
+        REFERENCE_FORMULATION,
+        GRAPH,
+        GRAPH_MAP,
+    ];
 }
 
 pub mod CLASS {
@@ -73,4 +105,18 @@ pub mod CLASS {
     pub const URI : PAIR = (SUPER_IRI, "URI");
     pub const UNSAFE_IRI : PAIR = (SUPER_IRI, "UnsafeIRI");
     pub const UNSAFE_URI : PAIR = (SUPER_IRI, "UnsafeURI");
+
+    pub const TERMS: &[PAIR] = &[
+        PREDICATEOBJECT_MAP,
+        OBJECT_MAP,
+        SUBJECT_MAP,
+        TRIPLES_MAP,
+        IRI,
+        BLANKNODE,
+        LITERAL,
+        DEFAULT_GRAPH,
+        URI,
+        UNSAFE_IRI,
+        UNSAFE_URI,
+    ];
 }
@@ -17,6 +17,19 @@ pub mod PROPERTY {
     pub const PARAMETER: PAIR = (IRI, "parameter"); 
     pub const INPUT_VALUE_MAP: PAIR = (IRI, "inputValueMap");
     pub const INPUT_VALUE: PAIR = (IRI, "inputValue");
+
+    pub const TERMS: &[PAIR] = &[
+        RETURN_MAP,
+        RETURN,
+        FUNCTION_EXECUTION,
+        INPUT,
+        FUNCTION_MAP,
+        FUNCTION,
+        PARAMETER_MAP,
+        PARAMETER,
+        INPUT_VALUE_MAP,
+        INPUT_VALUE,
+    ];
 }
 
 pub mod CLASS {
@@ -27,4 +40,12 @@ pub mod CLASS {
     pub const INPUT: PAIR = (IRI, "Input");
     pub const RETURN_MAP: PAIR = (IRI, "ReturnMap");
     pub const FUNCTION_EXECUTION: PAIR = (IRI, "FunctionExecution");
+
+    pub const TERMS: &[PAIR] = &[
+        PARAMETER_MAP,
+        FUNCTION_MAP,
+        INPUT,
+        RETURN_MAP,
+        FUNCTION_EXECUTION,
+    ];
 }
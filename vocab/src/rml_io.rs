@@ -19,6 +19,21 @@ pub mod PROPERTY {
     pub const ROOT: PAIR = (IRI, "root");
     pub const PATH: PAIR = (IRI, "path");
     pub const MODE: PAIR = (IRI, "mode");
+
+    pub const TERMS: &[PAIR] = &[
+        NAMESPACE,
+        NAMESPACE_PREFIX,
+        NAMESPACE_URL,
+        NULL,
+        SOURCE,
+        ENCODING,
+        COMPRESSION,
+        TARGET,
+        SERIALIZATION,
+        ROOT,
+        PATH,
+        MODE,
+    ];
 }
 
 pub mod CLASS {
@@ -52,4 +67,27 @@ pub mod CLASS {
     pub const CSVW: PAIR = (IRI, "CSVWReferenceFormulation"); 
     pub const SQL_QUERY: PAIR = (IRI, "SQLQuery"); 
     pub const SQL_TABLE: PAIR = (IRI, "SQLTable"); 
+
+    pub const TERMS: &[PAIR] = &[
+        STD_OUT,
+        CURRENT_WORKING_DIR,
+        MAPPING_DIR,
+        UTF_8,
+        UTF_16,
+        NONE,
+        GZIP,
+        ZIP,
+        TARGZ,
+        LOGICAL_SOURCE,
+        SOURCE,
+        FILE_PATH,
+        RELATIVE_PATH,
+        RELATIVE_PATH_SOURCE,
+        JSONPATH,
+        CSV,
+        XPATH,
+        CSVW,
+        SQL_QUERY,
+        SQL_TABLE,
+    ];
 }
@@ -17,6 +17,19 @@ pub mod PROPERTY {
     pub const INNER_JOIN: PAIR = (IRI, "innerJoin");
     pub const TARGET_VIEW: PAIR = (IRI, "targetView");
     pub const STRUCTURAL_ANNOTATION: PAIR = (IRI, "structuralAnnotation");
+
+    pub const TERMS: &[PAIR] = &[
+        VIEW_ON,
+        FIELD,
+        FIELD_NAME,
+        PARENT_LOGICAL_VIEW,
+        ON_FIELDS,
+        TARGET_FIELDS,
+        LEFT_JOIN,
+        INNER_JOIN,
+        TARGET_VIEW,
+        STRUCTURAL_ANNOTATION,
+    ];
 }
 
 pub mod CLASS {
@@ -36,4 +49,19 @@ pub mod CLASS {
     pub const UNIQUE_ANNOTATION: PAIR = (IRI, "UniqueAnnotation");
     pub const PRIMARY_KEY_ANNOTATION: PAIR = (IRI, "PrimaryKeyAnnotation");
     pub const INCLUSION_DEPENDENCY_ANNOTATION: PAIR = (IRI, "InclusionDependencyAnnotation");
+
+    pub const TERMS: &[PAIR] = &[
+        LOGICAL_VIEW_JOIN,
+        LOGICAL_VIEW,
+        FIELD,
+        ITERABLE_FIELD,
+        EXRESSION_FIELD,
+        STRUCTURAL_ANNOTATION,
+        FOREIGN_KEY_ANNOTATION,
+        IRI_SAFE_ANNOTATION,
+        NON_NULLABLE_ANNOTATION,
+        UNIQUE_ANNOTATION,
+        PRIMARY_KEY_ANNOTATION,
+        INCLUSION_DEPENDENCY_ANNOTATION,
+    ];
 }
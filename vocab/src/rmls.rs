@@ -6,6 +6,8 @@ pub mod CLASS {
     pub const KAFKASTREAM: PAIR = (IRI, "KafkaStream");
     pub const TCPSOCKETSTREAM: PAIR = (IRI, "TcpSocketStream");
     pub const HTML: PAIR = (IRI, "HTML");
+
+    pub const TERMS: &[PAIR] = &[KAFKASTREAM, TCPSOCKETSTREAM, HTML];
 }
 
 pub mod PROPERTY {
@@ -20,4 +22,6 @@ pub mod PROPERTY {
     // TCP PROPERTIES
     pub const HOSTNAME: PAIR = (IRI, "hostName");
     pub const PORT: PAIR = (IRI, "port");
+
+    pub const TERMS: &[PAIR] = &[TOPIC, GROUPID, BROKER, HOSTNAME, PORT];
 }
\ No newline at end of file
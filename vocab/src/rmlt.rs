@@ -6,6 +6,8 @@ pub mod CLASS {
     use crate::PAIR;
 
     pub const LOGICALTARGET: PAIR = (IRI, "LogicalTarget");
+
+    pub const TERMS: &[PAIR] = &[LOGICALTARGET];
 }
 
 pub mod PROPERTY {
@@ -18,4 +20,13 @@ pub mod PROPERTY {
     pub const LDES: PAIR = (IRI, "ldes");
     pub const LDESBASE: PAIR = (IRI, "ldesBaseIRI");  
     pub const LDESIMMUTABLE: PAIR = (IRI, "ldesGenerateImmutableIRI");
+
+    pub const TERMS: &[PAIR] = &[
+        TARGET,
+        SERIALIZATION,
+        COMPRESSION,
+        LDES,
+        LDESBASE,
+        LDESIMMUTABLE,
+    ];
 }
@@ -6,6 +6,8 @@ pub mod CLASS {
     use crate::PAIR;
 
     pub const COLLECTION: PAIR = (IRI, "Collection");
+
+    pub const TERMS: &[PAIR] = &[COLLECTION];
 }
 
 pub mod PROPERTY {
@@ -13,4 +15,6 @@ pub mod PROPERTY {
     use crate::PAIR;
 
     pub const SHAPE: PAIR = (IRI, "shape");
+
+    pub const TERMS: &[PAIR] = &[SHAPE];
 } 
\ No newline at end of file
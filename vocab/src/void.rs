@@ -8,6 +8,12 @@ pub mod PROPERTY {
     pub const DATA_DUMP: PAIR = (IRI, "dataDump");
     pub const URI_LOOKUP_ENDPOINT: PAIR = (IRI, "uriLookupEndpoint");
     pub const SPARQL_ENDPOINT: PAIR = (IRI, "sparqlEndpoint");
+
+    pub const TERMS: &[PAIR] = &[
+        DATA_DUMP,
+        URI_LOOKUP_ENDPOINT,
+        SPARQL_ENDPOINT,
+    ];
 }
 
 pub mod CLASS {
@@ -16,4 +22,6 @@ pub mod CLASS {
 
     pub const DATASET: PAIR = (IRI, "Dataset");
     pub const LINKSET: PAIR = (IRI, "Linkset");
+
+    pub const TERMS: &[PAIR] = &[DATASET, LINKSET];
 }
@@ -9,10 +9,28 @@ pub mod TYPE {
     pub const XSD_INT: PAIR = (IRI, "int"); // signed 32-bit integer
     pub const XSD_INTEGER: PAIR = (IRI, "integer"); // integer value
     pub const XSD_DOUBLE: PAIR = (IRI, "double");
+    pub const XSD_DECIMAL: PAIR = (IRI, "decimal");
+    pub const XSD_FLOAT: PAIR = (IRI, "float");
     pub const XSD_LONG: PAIR = (IRI, "long");
     pub const XSD_POSITIVE_INTEGER: PAIR = (IRI, "positiveInteger");
     pub const XSD_BOOLEAN: PAIR = (IRI, "boolean");
     pub const XSD_DATETIME: PAIR = (IRI, "dateTime");
+    pub const XSD_DATE: PAIR = (IRI, "date");
 
     pub const XSD_ANY: PAIR = (IRI, "any");
+
+    pub const TERMS: &[PAIR] = &[
+        XSD_STRING,
+        XSD_INT,
+        XSD_INTEGER,
+        XSD_DOUBLE,
+        XSD_DECIMAL,
+        XSD_FLOAT,
+        XSD_LONG,
+        XSD_POSITIVE_INTEGER,
+        XSD_BOOLEAN,
+        XSD_DATETIME,
+        XSD_DATE,
+        XSD_ANY,
+    ];
 }